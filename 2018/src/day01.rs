@@ -0,0 +1,64 @@
+use aoc_common::solution::{Error, Solution};
+use std::io::BufRead;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<i64>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse(input.as_bytes())?)
+    }
+
+    fn part_one(freqs: &Self::Input) -> Result<i64, Error> {
+        Ok(part1(freqs))
+    }
+
+    fn part_two(freqs: &Self::Input) -> Result<i64, Error> {
+        part2(freqs).ok_or_else(|| "there should be one repeating in an infinite cycle".into())
+    }
+}
+
+pub fn part1(freqs: &[i64]) -> i64 {
+    freqs.iter().sum::<i64>()
+}
+
+/// The first frequency reached twice while cycling through the changes.
+pub fn part2(freqs: &[i64]) -> Option<i64> {
+    use std::collections::HashSet;
+
+    let calibrated = freqs.iter().cycle().scan(0, |acc, next| {
+        *acc += next;
+        Some(*acc)
+    });
+
+    let mut seen = HashSet::new();
+
+    for freq in calibrated {
+        if !seen.insert(freq) {
+            return Some(freq);
+        }
+    }
+
+    None
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<Vec<i64>, std::io::Error> {
+    aoc_common::io::try_fold_lines(input, Vec::new(), |values, buffer| {
+        let num = buffer
+            .trim()
+            .parse::<i64>()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+
+        values.push(num);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn scan_works_as_expected() {
+    assert_eq!(part2(&[1i64, -2, 3, 1]), Some(2));
+}
@@ -0,0 +1,118 @@
+use aoc_common::solution::{Error, Solution};
+use std::io::BufRead;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<String>;
+    type PartOne = i64;
+    type PartTwo = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse(input.as_bytes())?)
+    }
+
+    fn part_one(ids: &Self::Input) -> Result<i64, Error> {
+        Ok(part1(ids))
+    }
+
+    fn part_two(ids: &Self::Input) -> Result<String, Error> {
+        Ok(part2(ids))
+    }
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<Vec<String>, std::io::Error> {
+    input.lines().collect::<Result<Vec<_>, _>>()
+}
+
+/// Checksum of the box ids.
+pub fn part1<S: AsRef<str>>(ss: &[S]) -> i64 {
+    let mut repeated_counts = [0i64; 2];
+
+    for serial in ss {
+        let mut char_counts = [0i64; 30];
+
+        for ch in serial.as_ref().chars() {
+            char_counts[(ch as u8 - b'a') as usize] += 1;
+        }
+
+        let mut visited = [false; 2];
+
+        for &counter in char_counts.iter() {
+            let index = match counter {
+                2 => 0,
+                3 => 1,
+                _ => continue,
+            };
+
+            if !visited[index] {
+                repeated_counts[index] += 1;
+                visited[index] = true;
+            }
+        }
+    }
+
+    repeated_counts.iter().product()
+}
+
+/// Common letters of the two box ids which differ by a single letter.
+pub fn part2<S: AsRef<str>>(ss: &[S]) -> String {
+    for (y, left) in ss.iter().enumerate() {
+        let left = left.as_ref();
+
+        for (x, right) in ss.iter().enumerate() {
+            if x == y {
+                continue;
+            }
+
+            let right = right.as_ref();
+            assert_eq!(left.len(), right.len());
+
+            let mut differences = left
+                .chars()
+                .zip(right.chars())
+                .enumerate()
+                .filter(|&(_, (a, b))| a != b)
+                .map(|(i, _)| i);
+
+            let first = differences.next();
+
+            if let Some(i) = first {
+                if differences.next().is_none() {
+                    let mut ret = String::new();
+                    ret.push_str(&left[..i]);
+                    ret.push_str(&left[i + 1..]);
+                    return ret;
+                }
+            }
+
+            continue;
+        }
+    }
+
+    panic!("couldn't find an id with edit distance of 1");
+}
+
+#[test]
+fn part1_example() {
+    let input: Vec<&'static str> = "abcdef,bababc,abbcde,abcccd,aabcdd,abcdee,ababab"
+        .split(',')
+        .collect::<Vec<_>>();
+
+    assert_eq!(part1(&input), 12);
+}
+
+#[test]
+fn part1_accepts_vec_of_strings() {
+    let vec_of_strings = vec![String::from("a"), String::from("b")];
+    part1(&vec_of_strings);
+}
+
+#[test]
+fn part2_example() {
+    let input = &[
+        "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+    ];
+
+    assert_eq!(part2(&input[..]), "fgij");
+}
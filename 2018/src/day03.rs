@@ -0,0 +1,136 @@
+use aoc_common::geometry::Point2;
+use aoc_common::parse::{self, Cursor, ParseError};
+use aoc_common::solution::{Error, Solution};
+use std::collections::{hash_map::Entry, HashMap};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Claims;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse(input)?)
+    }
+
+    fn part_one(claims: &Self::Input) -> Result<usize, Error> {
+        Ok(part1(claims))
+    }
+
+    fn part_two(claims: &Self::Input) -> Result<usize, Error> {
+        part2(claims).ok_or_else(|| "there should be exactly one undisputed claim".into())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Dispute {
+    Undisputed,
+    Disputed,
+}
+
+/// The claimed inches of fabric with the claim id and the number of claims, and whether each of
+/// the claims overlaps with any other claim.
+pub struct Claims {
+    inches: HashMap<Point2, (usize, u32)>,
+    disputes: HashMap<usize, Dispute>,
+}
+
+pub fn parse(input: &str) -> Result<Claims, ParseError> {
+    let mut inches = HashMap::new();
+    // keep track of undisputed claims, which will initially contain all but as there arrive
+    // competing claims in their region, the id will be removed
+    let mut disputes = HashMap::new();
+
+    for (id, corner, size) in parse::lines(input, parse_claim)? {
+        let points = (0..size.1)
+            .flat_map(|y| (0..size.0).map(move |x| (x, y)))
+            .map(|(x, y)| corner + (x as i64, y as i64));
+
+        for p in points {
+            match inches.entry(p) {
+                Entry::Vacant(ve) => {
+                    ve.insert((id, 1));
+                    disputes.entry(id).or_insert(Dispute::Undisputed);
+                }
+                Entry::Occupied(oe) if oe.get().0 == id => unreachable!("duplicate inch claim"),
+                Entry::Occupied(mut oe) => {
+                    let (original_id, count) = oe.get_mut();
+                    *count += 1;
+
+                    // both the original and the new claim must now be marked as disputed
+                    disputes.insert(*original_id, Dispute::Disputed);
+                    disputes.insert(id, Dispute::Disputed);
+                }
+            }
+        }
+    }
+
+    Ok(Claims { inches, disputes })
+}
+
+/// Square inches of fabric within two or more claims.
+pub fn part1(claims: &Claims) -> usize {
+    claims.inches.values().filter(|&&(_, c)| c > 1).count()
+}
+
+/// The only claim which doesn't overlap with any other.
+pub fn part2(claims: &Claims) -> Option<usize> {
+    claims
+        .disputes
+        .iter()
+        .filter(|(_, v)| **v == Dispute::Undisputed)
+        .map(|(k, _)| *k)
+        .single()
+        .ok()
+}
+
+trait IteratorExt {
+    fn single<T>(self) -> Result<T, Option<(T, T)>>
+    where
+        Self: Iterator<Item = T>;
+}
+
+impl<Iter: Iterator> IteratorExt for Iter {
+    fn single<T>(mut self) -> Result<T, Option<(T, T)>>
+    where
+        Self: Iterator<Item = T>,
+    {
+        let only = self.next();
+        match only {
+            Some(only) => {
+                let next = self.next();
+
+                if next.is_none() {
+                    return Ok(only);
+                }
+
+                Err(Some((only, next.unwrap())))
+            }
+            None => Err(None),
+        }
+    }
+}
+
+/// Parses a claim like `#int @ Left,Top: WidthxHeight`.
+fn parse_claim(c: &mut Cursor) -> Result<(usize, Point2, (u64, u64)), ParseError> {
+    c.literal("#")?;
+    let id = c.number()?;
+    c.literal(" @ ")?;
+    let left = c.number::<i64>()?;
+    c.literal(",")?;
+    let top = c.number::<i64>()?;
+    c.literal(": ")?;
+    let width = c.number()?;
+    c.literal("x")?;
+    let height = c.number()?;
+
+    Ok((id, Point2::from((left, top)), (width, height)))
+}
+
+#[test]
+fn example() {
+    let claims = parse("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n").unwrap();
+    assert_eq!(part1(&claims), 4);
+    assert_eq!(part2(&claims), Some(3));
+}
@@ -0,0 +1,96 @@
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    // the polymer is the first line of the input
+    type Input = String;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.lines().next().unwrap_or_default().to_owned())
+    }
+
+    fn part_one(polymer: &Self::Input) -> Result<usize, Error> {
+        Ok(part1(polymer))
+    }
+
+    fn part_two(polymer: &Self::Input) -> Result<usize, Error> {
+        Ok(part2(polymer))
+    }
+}
+
+/// Length of the polymer after all of the reactions.
+pub fn part1(polymer: &str) -> usize {
+    reduce(polymer.trim().chars()).len()
+}
+
+/// Length of the shortest polymer after removing all units of a single type.
+pub fn part2(polymer: &str) -> usize {
+    let mut reduced = Vec::new();
+
+    (b'a'..=b'z')
+        .map(move |removed| {
+            reduced.clear();
+            let filtered = polymer
+                .trim()
+                .chars()
+                .filter(|ch| ch.to_ascii_lowercase() as u8 != removed);
+            reduce_into(filtered, &mut reduced);
+            reduced.len()
+        })
+        .min()
+        .unwrap()
+}
+
+fn reduce(buffer: impl Iterator<Item = char>) -> Vec<u8> {
+    let sz = buffer.size_hint();
+    let mut reduced = Vec::with_capacity(sz.1.unwrap_or(sz.0));
+
+    reduce_into(buffer, &mut reduced);
+
+    reduced
+}
+
+fn reduce_into(buffer: impl Iterator<Item = char>, reduced: &mut Vec<u8>) {
+    let mut last = None;
+
+    for ch in buffer {
+        let prev = std::mem::replace(&mut last, Some(ch));
+
+        match (prev, ch) {
+            (None, ch) => reduced.push(ch as u8),
+            (Some(a), b) if !reaction(a, b) => reduced.push(b as u8),
+            (Some(_), _) => {
+                reduced.pop().unwrap();
+
+                while reduced.len() > 2 {
+                    let last = &reduced[reduced.len() - 1];
+                    let prev = &reduced[reduced.len() - 2];
+
+                    if !reaction(*last as char, *prev as char) {
+                        break;
+                    }
+
+                    reduced.truncate(reduced.len() - 2);
+                }
+
+                last = reduced.last().map(|&byte| byte as char);
+            }
+        }
+    }
+}
+
+fn reaction(a: char, b: char) -> bool {
+    a != b && a.to_ascii_lowercase() == b.to_ascii_lowercase()
+}
+
+#[test]
+fn part1_example() {
+    let input = "dabAcCaCBAcCcaDA";
+
+    let reduced = reduce(input.chars());
+
+    assert_eq!(reduced, b"dabCBAcaDA");
+}
@@ -0,0 +1,195 @@
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::io::BufRead;
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<f64>;
+    type PartOne = f64;
+    type PartTwo = f64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_masses(input.as_bytes())?)
+    }
+
+    fn part_one(masses: &Self::Input) -> Result<f64, Error> {
+        fuel_requirements(masses)
+            .map(|(sum, _)| sum)
+            .map_err(|e| format!("Invalid fuel requirement: {:?}", e).into())
+    }
+
+    fn part_two(masses: &Self::Input) -> Result<f64, Error> {
+        fuel_requirements(masses)
+            .map(|(_, stage2_sum)| stage2_sum)
+            .map_err(|e| format!("Invalid fuel requirement: {:?}", e).into())
+    }
+}
+
+/// Reads the masses of the modules, one per line.
+pub fn parse_masses<R: BufRead>(input: R) -> Result<Vec<f64>, String> {
+    let mut masses = Vec::new();
+
+    for (line_num, line) in input.lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read input: {}", e))?;
+        if line.is_empty() {
+            continue;
+        }
+
+        let mass = match f64::from_str(&line) {
+            Ok(mass) if mass >= 0.0 => mass,
+            Ok(mass) => return Err(format!("Negative mass at line {}: {}", line_num, mass)),
+            Err(e) => return Err(format!("Bad mass at line {}: \"{}\" ({})", line_num, line, e)),
+        };
+
+        masses.push(mass);
+    }
+
+    Ok(masses)
+}
+
+/// Returns the fuel required by the modules, and the fuel required by the modules including the
+/// fuel for the fuel of each module.
+pub fn fuel_requirements(masses: &[f64]) -> Result<(f64, f64), NegativeFuel> {
+    let mut sum = 0.0f64;
+    let mut stage2_sum = 0.0f64;
+
+    for &mass in masses {
+        let module = Module::from_mass(mass);
+        let f = module.fuel_required()?;
+
+        sum += f.0;
+
+        // not really on board how this is calculated for each module
+        stage2_sum += FuelModule::from(f).fuel_required().0;
+    }
+
+    Ok((sum, stage2_sum + sum))
+}
+
+/// Fuel required by the fuel of all modules, as if it was a module of its own.
+pub fn fuel_for_fuel(fuel: f64) -> f64 {
+    FuelModule::from(Fuel::new(fuel)).fuel_required().0
+}
+
+#[derive(Debug)]
+struct Module { mass: f64, }
+
+impl Module {
+    fn from_mass(mass: f64) -> Self {
+        assert!(mass >= 0.0);
+        Self { mass }
+    }
+
+    fn fuel_required(&self) -> Result<Fuel, <Fuel as TryFrom<f64>>::Error> {
+        Fuel::try_from((self.mass / 3.0).floor() - 2.0)
+    }
+}
+
+#[derive(Clone)]
+struct FuelModule { fuel: Fuel }
+
+impl From<Fuel> for FuelModule {
+    fn from(fuel: Fuel) -> Self {
+        Self { fuel }
+    }
+}
+
+impl FuelModule {
+    fn fuel_required(&self) -> Fuel {
+        let mut sum = 0.0f64;
+
+        let mut next = FuelModule::clone(self);
+
+        loop {
+            let amount = next.as_module().fuel_required().unwrap_or_default();
+            if amount.0 == 0.0f64 {
+                return Fuel::new(sum);
+            }
+            sum += amount.0;
+            next = FuelModule::from(amount);
+        }
+    }
+
+    fn as_module(&self) -> Module {
+        Module::from_mass(self.fuel.0)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+struct Fuel(f64);
+
+impl std::default::Default for Fuel {
+    fn default() -> Self {
+        Self(0.0)
+    }
+}
+
+impl Fuel {
+    fn new(fuel: f64) -> Self {
+        assert!(fuel >= 0.0);
+        Self(fuel)
+    }
+}
+
+impl PartialEq<f64> for Fuel {
+    fn eq(&self, other: &f64) -> bool { self.0 == *other }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct NegativeFuel(f64);
+
+impl TryFrom<f64> for Fuel {
+    type Error = NegativeFuel;
+
+    fn try_from(f: f64) -> Result<Self, Self::Error> {
+        if f < 0.0 {
+            Err(NegativeFuel(f))
+        } else {
+            Ok(Fuel(f))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+    use super::{Module, Fuel, FuelModule};
+
+    #[test]
+    fn module_fuel_examples() {
+        let masses = &[12, 14, 1969, 100756];
+        let answers = &[2, 2, 654, 33583];
+
+        let solutions = masses
+            .iter()
+            .cloned()
+            .map(|m| m as f64)
+            .map(Module::from_mass)
+            .map(|m| m.fuel_required())
+            .zip(answers.iter().cloned().map(|a| a as f64));
+
+        for (actual, expected) in solutions {
+            assert_eq!(actual.unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn fuel_fuel_examples() {
+        let fuel_masses = &[2, 654, 33583];
+        let answers = &[0, 966 - 654, 50346 - 33583];
+
+        let solutions = fuel_masses
+            .iter()
+            .map(|m| *m as f64)
+            .map(|m| Fuel::try_from(m).unwrap())
+            .map(FuelModule::from)
+            .map(|fm| fm.fuel_required())
+            .zip(answers.iter().map(|a| *a as f64));
+
+        for (actual, expected) in solutions {
+            assert_eq!(actual.0, expected);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use intcode::{Program, Word};
+use intcode::util::parse_program;
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Word>;
+    type PartOne = Word;
+    type PartTwo = Word;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_program(input.as_bytes())?)
+    }
+
+    fn part_one(data: &Self::Input) -> Result<Word, Error> {
+        Ok(stage1(data))
+    }
+
+    fn part_two(data: &Self::Input) -> Result<Word, Error> {
+        find_coords(data, 19690720)
+            .map(|(i, j)| 100 * i + j)
+            .ok_or_else(|| "Did not find...".into())
+    }
+}
+
+pub fn stage1(data: &[Word]) -> Word {
+    let mut data = data.to_vec();
+
+    // restore
+    data[1] = 12;
+    data[2] = 2;
+
+    Program::wrap_and_eval(&mut data).expect("Invalid program");
+    data[0]
+}
+
+pub fn find_coords(input: &[Word], magic: Word) -> Option<(Word, Word)> {
+    let mut copy = input.to_vec();
+
+    for i in 0..100 {
+        for j in 0..100 {
+            copy[1] = i;
+            copy[2] = j;
+
+            Program::wrap_and_eval(&mut copy)
+                .expect("Failed to execute program");
+
+            if copy[0] == magic {
+                return Some((i, j));
+            }
+
+            copy.copy_from_slice(&input[..]);
+        }
+    }
+
+    None
+}
+
+#[test]
+fn full_stage1() {
+    intcode::with_parsed_program(2, |data| {
+        assert_eq!(stage1(data), 3224742);
+    });
+}
+
+#[test]
+fn full_stage2() {
+    intcode::with_parsed_program(2, |data| {
+        let magic = 19690720;
+        let res = find_coords(data, magic).map(|(noun, verb)| 100 * noun + verb);
+        assert_eq!(res, Some(7960));
+    });
+}
@@ -0,0 +1,53 @@
+use intcode::{Environment, Program, Word};
+use intcode::util::parse_program;
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<Word>;
+    type PartOne = Word;
+    type PartTwo = Word;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_program(input.as_bytes())?)
+    }
+
+    fn part_one(data: &Self::Input) -> Result<Word, Error> {
+        Ok(stage1(data))
+    }
+
+    fn part_two(data: &Self::Input) -> Result<Word, Error> {
+        Ok(stage2(data))
+    }
+}
+
+pub fn stage1(data: &[Word]) -> Word {
+    let mut data = data.to_vec();
+    let mut env = Environment::collector(Some(1));
+
+    Program::wrap_and_eval_with_env(data.as_mut_slice(), &mut env).unwrap();
+
+    let output = env.unwrap_collected();
+    *output.last().expect("No output?")
+}
+
+pub fn stage2(data: &[Word]) -> Word {
+    let mut data = data.to_vec();
+    let mut env = Environment::once(Some(5));
+
+    Program::wrap_and_eval_with_env(data.as_mut_slice(), &mut env).unwrap();
+
+    let output = env.unwrap_input_consumed_once();
+    output.expect("No output?")
+}
+
+#[test]
+fn full_stage1() {
+    intcode::with_parsed_program(5, |data| assert_eq!(stage1(data), 9938601));
+}
+
+#[test]
+fn full_stage2() {
+    intcode::with_parsed_program(5, |data| assert_eq!(stage2(data), 4283952));
+}
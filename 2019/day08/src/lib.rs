@@ -0,0 +1,86 @@
+use intcode::util::ocr;
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<u8>;
+    type PartOne = usize;
+    type PartTwo = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.trim_end().as_bytes().to_vec())
+    }
+
+    fn part_one(raw: &Self::Input) -> Result<usize, Error> {
+        Ok(stage1(raw))
+    }
+
+    fn part_two(raw: &Self::Input) -> Result<String, Error> {
+        Ok(stage2(raw)?)
+    }
+}
+
+pub const WIDTH: usize = 25;
+pub const HEIGHT: usize = 6;
+
+/// Number of ones multiplied by the number of twos on the layer with the fewest zeroes.
+pub fn stage1(raw: &[u8]) -> usize {
+    let (ones, twos) = raw.chunks(WIDTH * HEIGHT)
+        .enumerate()
+        .map(|(i, chunk)| (i, chunk, chunk.iter().filter(|b| (**b - '0' as u8) == 0).count()))
+        .min_by_key(|(_, _, zeroes)| *zeroes)
+        .into_iter()
+        .fold((0, 0), |(mut ones, mut twos), (_, chunk, _)| {
+            for b in chunk {
+                match b {
+                    b'1' => ones += 1,
+                    b'2' => twos += 1,
+                    _ => {},
+                }
+            }
+            (ones, twos)
+        });
+
+    ones * twos
+}
+
+/// Reads the message from the decoded image.
+pub fn stage2(raw: &[u8]) -> Result<String, ocr::OcrError> {
+    let image = to_image(raw, WIDTH, HEIGHT);
+    ocr::read_image(&image, WIDTH, HEIGHT, |&color| color == b'1')
+}
+
+pub fn to_image(raw: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let black = '0' as u8;
+    let white = '1' as u8;
+    let transparent = '2' as u8;
+
+    assert_eq!(raw.len() % (width * height), 0);
+
+    raw.chunks(width * height)
+        .fold(vec![transparent; width * height], |mut image, layer| {
+            for (i, b) in layer.iter().enumerate() {
+                match b {
+                    b'2' => {},
+                    color if *color  == black || *color == white => {
+                        if image[i] != transparent {
+                            continue;
+                        }
+                        image[i] = *color;
+                    },
+                    other => panic!("unexpected color {:?}", other),
+                }
+            }
+            image
+        })
+}
+
+#[test]
+fn stage2_example() {
+    let input = b"0222112222120000";
+    let expected = b"0110";
+
+    let image = to_image(input, 2, 2);
+    assert_eq!(&image[..], expected);
+}
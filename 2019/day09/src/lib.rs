@@ -0,0 +1,45 @@
+use intcode::{Environment, Program, Word};
+use intcode::util::parse_program;
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<Word>;
+    type PartOne = Word;
+    type PartTwo = Word;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_program(input.as_bytes())?)
+    }
+
+    fn part_one(data: &Self::Input) -> Result<Word, Error> {
+        Ok(boost(data, 1))
+    }
+
+    fn part_two(data: &Self::Input) -> Result<Word, Error> {
+        Ok(boost(data, 2))
+    }
+}
+
+pub fn boost(data: &[Word], input: Word) -> Word {
+    let mut data = data.to_vec();
+    let mut env = Environment::once(Some(input));
+
+    Program::wrap(&mut data)
+        .with_memory_expansion()
+        .eval_with_env(&mut env)
+        .unwrap();
+
+    env.unwrap_input_consumed_once().unwrap()
+}
+
+#[test]
+fn stage1_full() {
+    intcode::with_parsed_program(9, |input| assert_eq!(boost(input, 1), 3638931938));
+}
+
+#[test]
+fn stage2_full() {
+    intcode::with_parsed_program(9, |input| assert_eq!(boost(input, 2), 86025));
+}
@@ -0,0 +1,159 @@
+use intcode::{Word, Memory, Program, ExecutionState, Registers};
+use intcode::util::{GameDisplay, ocr};
+use aoc_common::geometry::{BoundingBox, Direction, Point2};
+use std::collections::HashMap;
+use intcode::util::parse_program;
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Word>;
+    type PartOne = usize;
+    type PartTwo = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_program(input.as_bytes())?)
+    }
+
+    fn part_one(data: &Self::Input) -> Result<usize, Error> {
+        Ok(stage1(data))
+    }
+
+    fn part_two(data: &Self::Input) -> Result<String, Error> {
+        Ok(stage2(data))
+    }
+}
+
+enum RobotState {
+    PaintCommand,
+    DirectionedMove,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+enum Color {
+    White,
+    Black,
+}
+
+// number of painted at least once areas
+pub fn stage1(data: &[Word]) -> usize {
+    registration_paint(data, Color::Black).0.len()
+}
+
+pub fn stage2(data: &[Word]) -> String {
+    let (painted, _) = registration_paint(data, Color::White);
+
+    let gd = painted.into_iter()
+        .filter(|(_, color)| *color == Color::White)
+        .fold(GameDisplay::default(), |mut gd, (p, _)| {
+            gd.insert(&(p.x(), p.y()), true);
+            gd
+        });
+
+    ocr::read_display(&gd, |&white| white).unwrap()
+}
+
+pub fn stage2_picture(data: &[Word]) -> String {
+    let (painted, bounds) = registration_paint(data, Color::White);
+
+    let mut ret = String::new();
+
+    for y in bounds.min().y() ..= bounds.max().y() {
+        for x in bounds.min().x() ..= bounds.max().x() {
+            let color = painted.get(&Point2::new([x, y])).unwrap_or(&Color::Black);
+            ret += if color == &Color::Black { "X" } else { " " };
+        }
+
+        ret += "\n"
+    }
+
+    ret
+}
+
+fn registration_paint(data: &[Word], start_on: Color) -> (HashMap<Point2, Color>, BoundingBox<2>) {
+    let mut painted = HashMap::new();
+    let mut program: Program = Memory::from(data)
+        .with_memory_expansion()
+        .into();
+
+    let mut regs = Registers::default();
+
+    let mut coords = Point2::ORIGIN;
+    painted.insert(coords, start_on);
+
+    let mut bounds = BoundingBox::from_point(coords);
+
+    let mut direction = Direction::Up;
+
+    let mut robot_state = RobotState::PaintCommand;
+
+    loop {
+        regs = match program.eval_from_instruction(regs).unwrap() {
+            ExecutionState::Paused(regs) => regs,
+            ExecutionState::HaltedAt(_regs) => break,
+            ExecutionState::InputIO(io) => {
+                let color = painted.get(&coords).unwrap_or(&Color::Black);
+                program.handle_input_completion(
+                    io,
+                    (*color == Color::White) as Word,
+                ).unwrap()
+            },
+            ExecutionState::OutputIO(io, value) => {
+                assert!(value == 0 || value == 1);
+                robot_state = match (robot_state, value) {
+                    (RobotState::PaintCommand, x) => {
+                        let color = if x == 0 {
+                            Color::Black
+                        } else {
+                            Color::White
+                        };
+                        painted.insert(coords, color);
+                        RobotState::DirectionedMove
+                    },
+                    (RobotState::DirectionedMove, x) => {
+                        direction = if x == 0 {
+                            direction.turn_left()
+                        } else {
+                            direction.turn_right()
+                        };
+
+                        let next_coords = coords + direction;
+                        assert_ne!(next_coords, coords);
+                        coords = next_coords;
+                        bounds.extend(coords);
+                        RobotState::PaintCommand
+                    },
+                };
+
+                program.handle_output_completion(io)
+            }
+        };
+    }
+
+    (painted, bounds)
+}
+
+#[test]
+fn full_stage1() {
+    intcode::with_parsed_program(11, |data| assert_eq!(stage1(data), 2883));
+}
+
+#[test]
+fn full_stage2() {
+    intcode::with_parsed_program(11, |data| assert_eq!(stage2(data), "LEPCPLGZ"));
+}
+
+#[test]
+fn full_stage2_picture() {
+    let expected =
+"X XXXX    X   XXX  XX   XX XXXXX  XX    XXX
+X XXXX XXXX XX X XX X XX X XXXX XX XXXX XXX
+X XXXX   XX XX X XXXX XX X XXXX XXXXXX XXXX
+X XXXX XXXX   XX XXXX   XX XXXX X  XX XXXXX
+X XXXX XXXX XXXX XX X XXXX XXXX XX X XXXXXX
+X    X    X XXXXX  XX XXXX    XX   X    XXX
+";
+
+    intcode::with_parsed_program(11, |data| assert_eq!(stage2_picture(data), expected));
+}
@@ -0,0 +1,209 @@
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+use intcode::{util::GameDisplay, Program, Environment, Word, ExecutionState, Registers};
+use intcode::util::image::{FrameRecorder, Rgb};
+use intcode::util::parse_program_n_lines;
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Word>;
+    type PartOne = usize;
+    type PartTwo = Word;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_program_n_lines(input.as_bytes(), Some(1))?)
+    }
+
+    fn part_one(data: &Self::Input) -> Result<usize, Error> {
+        Ok(stage1(data))
+    }
+
+    fn part_two(data: &Self::Input) -> Result<Word, Error> {
+        Ok(stage2(data, Screen::Hidden))
+    }
+}
+
+pub fn stage1(data: &[Word]) -> usize {
+    let mut data = data.to_vec();
+    let mut env = Environment::collector(None);
+
+    Program::wrap(&mut data)
+        .with_memory_expansion()
+        .eval_with_env(&mut env)
+        .unwrap();
+
+    let collected = env.unwrap_collected();
+
+    let mut uniq = HashSet::new();
+    for p in collected.chunks(3).filter(|chunk| chunk[2] == 2).map(|chunk| (chunk[0], chunk[1])) {
+        uniq.insert(p);
+    }
+
+    uniq.len()
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TileKind {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball
+}
+
+impl Default for TileKind {
+    fn default() -> Self {
+        TileKind::Empty
+    }
+}
+
+impl TileKind {
+    #[allow(dead_code)]
+    fn is_indestructible(&self) -> bool {
+        match *self {
+            TileKind::Wall | TileKind::Paddle | TileKind::Ball => true,
+            _ => false
+        }
+    }
+
+    pub fn color(&self) -> Rgb {
+        match *self {
+            TileKind::Empty  => Rgb::BLACK,
+            TileKind::Wall   => Rgb(128, 128, 128),
+            TileKind::Block  => Rgb(200, 80, 40),
+            TileKind::Paddle => Rgb(60, 120, 220),
+            TileKind::Ball   => Rgb::WHITE,
+        }
+    }
+}
+
+impl TryFrom<Word> for TileKind {
+    type Error = Word;
+
+    fn try_from(w: Word) -> Result<Self, Self::Error> {
+        Ok(match w {
+            0 => TileKind::Empty,
+            1 => TileKind::Wall,
+            2 => TileKind::Block,
+            3 => TileKind::Paddle,
+            4 => TileKind::Ball,
+            x => return Err(x),
+        })
+    }
+}
+
+impl fmt::Display for TileKind {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use TileKind::*;
+        let ch = match *self {
+            Empty  => ' ',
+            Wall   => '█',
+            Block  => 'X',
+            Paddle => 'P',
+            Ball   => 'O',
+        };
+        write!(fmt, "{}", ch)
+    }
+}
+
+/// Where the frames of the game go while it is played.
+pub enum Screen<'a> {
+    /// Nowhere, for solving.
+    Hidden,
+    /// Drawn on the terminal with a short pause after every frame.
+    Terminal,
+    /// Recorded for an animation.
+    Recorder(&'a mut FrameRecorder<fn(&TileKind) -> Rgb>),
+}
+
+pub fn stage2(data: &[Word], mut screen: Screen) -> Word {
+    use std::collections::VecDeque;
+
+    let mut data = data.to_vec();
+    data[0] = 2; // infinite coins
+
+    let mut program = Program::wrap(&mut data)
+        .with_memory_expansion();
+
+    let mut disp = GameDisplay::default();
+    let mut regs = Registers::default();
+
+    let mut buffer = VecDeque::new();
+
+    let mut score = 0;
+
+    let mut last_ball_pos: Option<((Word, Word), usize)> = None;
+    let mut last_paddle_pos: Option<((Word, Word), usize)> = None;
+
+    let mut round = 0;
+
+    loop {
+        regs = match program.eval_from_instruction(regs).unwrap() {
+            ExecutionState::Paused(_regs) => unreachable!("Pausing not implemented yet?"),
+            ExecutionState::HaltedAt(_regs) => break,
+            ExecutionState::InputIO(io) => {
+
+                let dx = match (last_ball_pos, last_paddle_pos) {
+                    (Some((bp, _)), Some((pp, _))) => bp.0 - pp.0,
+                    _ => 0,
+                };
+
+                round += 1;
+                program.handle_input_completion(io, dx.signum()).unwrap()
+            },
+            ExecutionState::OutputIO(io, value) => {
+
+                buffer.push_back(value);
+
+                while buffer.len() >= 3 {
+                    let x = buffer.pop_front().unwrap();
+                    let y = buffer.pop_front().unwrap();
+                    let value = buffer.pop_front().unwrap();
+
+                    if x == -1 && y == 0 {
+                        score = value;
+                        continue;
+                    }
+
+                    let prev = *disp.get(&(x, y))
+                        .unwrap_or(&TileKind::Empty);
+
+                    let kind = TileKind::try_from(value).unwrap();
+
+                    let mut render = false;
+
+                    match (prev, kind) {
+                        (_, TileKind::Ball) => { render = true; last_ball_pos = Some(((x, y), round)); },
+                        (_, TileKind::Paddle) => { render = true; last_paddle_pos = Some(((x, y), round)); },
+                        (TileKind::Ball, TileKind::Empty) => render = false,
+                        _ => {},
+                    }
+
+                    disp.insert(&(x, y), kind);
+
+                    if render && last_ball_pos.as_ref().map(|(_, rnd)| *rnd) == last_paddle_pos.as_ref().map(|(_, rnd)| *rnd) {
+                        match screen {
+                            Screen::Hidden => {},
+                            Screen::Terminal => {
+                                print!("{}{}{}", termion::clear::All, termion::cursor::Goto(1, 1), disp);
+                                std::thread::sleep(std::time::Duration::from_millis(10));
+                            },
+                            Screen::Recorder(ref mut recorder) => recorder.record(&disp),
+                        }
+                    }
+                }
+
+                program.handle_output_completion(io)
+            },
+        };
+    }
+
+    if let Screen::Terminal = screen {
+        println!();
+    }
+
+    score
+}
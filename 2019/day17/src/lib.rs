@@ -0,0 +1,857 @@
+use std::fmt;
+use std::convert::TryFrom;
+use std::collections::{VecDeque, HashSet, HashMap};
+use intcode::{Word, util::GameDisplay, Program, Registers, ExecutionState};
+use aoc_common::geometry::Direction;
+use intcode::util::parse_program_n_lines;
+use intcode::util::compress::{Compression, Compressor};
+use aoc_common::search::Search;
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    // the program and the map it prints at startup
+    type Input = (Vec<Word>, GameDisplay<Tile>);
+    type PartOne = i64;
+    type PartTwo = Word;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let data = parse_program_n_lines(input.as_bytes(), Some(1))?;
+        let gd = ScaffoldProgram::new(data.clone()).print_map();
+        Ok((data, gd))
+    }
+
+    fn part_one((_, gd): &Self::Input) -> Result<i64, Error> {
+        Ok(alignment_parameters(gd))
+    }
+
+    fn part_two((data, gd): &Self::Input) -> Result<Word, Error> {
+        let (_, compression) = part2_find_path(gd);
+        let routines = movement_functions().render(&compression);
+        part2_dust_collected(&routines, data.clone())
+    }
+}
+
+/// The robot takes the main routine and three movement functions of at most 20 characters.
+pub fn movement_functions() -> Compressor<fn(&Action) -> Action> {
+    Compressor { functions: 3, budget: 20, render: |a| *a }
+}
+
+/// The route which compresses best with the compression it was ranked by.
+pub fn part2_find_path(gd: &GameDisplay<Tile>) -> (Vec<Action>, Compression<Action>) {
+    RoutePlanner::new(gd)
+        .ranked(&movement_functions())
+        .into_iter()
+        .next()
+        .expect("none of the routes could be compressed")
+}
+
+/// Plans the routes of the robot over every scaffold cell.
+///
+/// The shortest route does not always compress into the movement functions, so the planner
+/// enumerates the routes up to some actions longer than the shortest, including the ones turning
+/// at the intersections and travelling some segments again, and ranks them by how well they
+/// compress.
+pub struct RoutePlanner<'a> {
+    gd: &'a GameDisplay<Tile>,
+    start: ((Word, Word), Direction),
+    visitable: usize,
+    /// The straight line of scaffolding each cell is on, for the cells which are not on both a
+    /// horizontal and a vertical line.
+    lines: HashMap<(Word, Word), usize>,
+    /// Segments a route can travel without visiting anything new, including turning back from the
+    /// dead ends.
+    pub revisits: usize,
+    /// Actions a route can have over the shortest one.
+    pub slack: usize,
+    /// Most routes enumerated.
+    pub limit: usize,
+}
+
+impl<'a> RoutePlanner<'a> {
+    pub fn new(gd: &'a GameDisplay<Tile>) -> Self {
+        let start = gd.iter()
+            .find_map(|(p, t)| t.robot_direction().map(|d| (p, d)))
+            .expect("robot should be on the map");
+
+        let visitable = gd.cells()
+            .iter()
+            .filter(|t| t.can_visit())
+            .count();
+
+        // lines are numbered by the axis and the first cell of the line
+        let open = |p: (Word, Word)| gd.get(&p).map(Tile::can_visit).unwrap_or(false);
+        let mut numbers = HashMap::new();
+
+        let lines = gd.iter()
+            .filter(|(_, t)| t.can_visit())
+            .filter_map(|(p, _)| {
+                let horizontal = open(p.step(Direction::Left)) || open(p.step(Direction::Right));
+                let vertical = open(p.step(Direction::Up)) || open(p.step(Direction::Down));
+
+                let back = match (horizontal, vertical) {
+                    (true, false) => Direction::Left,
+                    (false, true) => Direction::Up,
+                    _ => return None,
+                };
+
+                let first = std::iter::successors(Some(p), |q| Some(q.step(back)).filter(|q| open(*q)))
+                    .last()
+                    .unwrap();
+
+                let next = numbers.len();
+                Some((p, *numbers.entry((back, first)).or_insert(next)))
+            })
+            .collect::<HashMap<_, _>>();
+
+        RoutePlanner { gd, start, visitable, lines, revisits: 1, slack: 4, limit: 10_000 }
+    }
+
+    /// Segments from the position. Turning back is only considered at the dead ends.
+    fn segments(&self, pos: (Word, Word), dir: Direction) -> Vec<Segment> {
+        let mut directions = frontier(self.gd, pos, dir).map(|(d, _)| d).collect::<Vec<_>>();
+
+        if directions.is_empty() && self.gd.get(&pos.step(dir.reverse())).map(Tile::can_visit).unwrap_or(false) {
+            directions.push(dir.reverse());
+        }
+
+        directions.into_iter()
+            .map(|new_dir| {
+                let (final_pos, steps) = travel_straight(self.gd, pos.step(new_dir), new_dir)
+                    .expect("frontier point should be travellable");
+
+                let cells = std::iter::successors(Some(pos), |p| Some(p.step(new_dir)))
+                    .skip(1)
+                    .take(steps + 1)
+                    .collect::<Vec<_>>();
+
+                assert_eq!(cells.last(), Some(&final_pos));
+
+                let cost = ActionsBetweenDirections::from((dir, new_dir)).count() + 1;
+                Segment { dir: new_dir, cells, cost }
+            })
+            .collect()
+    }
+
+    /// Route over every cell with the fewest actions, where at most `revisits` segments visit
+    /// nothing new. Every turn and move is counted as an action, even the moves continuing
+    /// straight over the intersections.
+    pub fn shortest(&self) -> Option<Vec<Action>> {
+        let (pos, dir) = self.start;
+        let mut seen = HashSet::new();
+        seen.insert(pos);
+
+        // the states include all of the visited cells, so the routes at the same place and
+        // heading which have visited less at the same cost are pruned.
+        let dominates = |more: &Route, less: &Route| {
+            more.revisits >= less.revisits && less.seen.is_subset(&more.seen)
+        };
+
+        let mut search = Search::tree(|route: &Route| {
+            self.segments(route.pos, route.dir)
+                .into_iter()
+                .filter_map(|Segment { dir, cells, cost }| {
+                    let mut seen = route.seen.clone();
+                    seen.extend(cells.iter().copied());
+
+                    let revisits = match seen.len() == route.seen.len() {
+                        true => route.revisits.checked_sub(1)?,
+                        false => route.revisits,
+                    };
+
+                    Some((Route { pos: *cells.last().unwrap(), dir, seen, revisits }, cost))
+                })
+                .collect::<Vec<_>>()
+        }).dominance(|route: &Route| (route.pos, route.dir), dominates);
+
+        let start = Route { pos, dir, seen, revisits: self.revisits };
+        let found = search.dijkstra(start, |route| route.seen.len() == self.visitable)?;
+
+        Some(found.path.windows(2)
+            .flat_map(|w| {
+                let steps = (w[1].pos.0 - w[0].pos.0).abs() + (w[1].pos.1 - w[0].pos.1).abs();
+                ActionsBetweenDirections::from((w[0].dir, w[1].dir))
+                    .chain(std::iter::once(Action::Move(steps as usize)))
+            })
+            .collect())
+    }
+
+    /// Routes over every cell with at most `slack` actions more than the shortest and at most
+    /// `revisits` segments visiting nothing new, with the consecutive moves combined.
+    pub fn routes(&self) -> Vec<Vec<Action>> {
+        let max_cost = match self.shortest() {
+            Some(shortest) => shortest.len() + self.slack,
+            None => return Vec::new(),
+        };
+
+        let (pos, dir) = self.start;
+        let mut walk = Walk {
+            visits: HashMap::new(),
+            covered: 0,
+            uncovered: Vec::new(),
+            lines_left: 0,
+            actions: Vec::new(),
+            cost: 0,
+            revisits: self.revisits,
+        };
+
+        for &line in self.lines.values() {
+            if line >= walk.uncovered.len() {
+                walk.uncovered.resize(line + 1, 0);
+            }
+            walk.uncovered[line] += 1;
+        }
+
+        walk.lines_left = walk.uncovered.len();
+        self.visit(&mut walk, pos);
+
+        let mut routes = Vec::new();
+        self.enumerate(pos, dir, &mut walk, max_cost, &mut routes);
+        routes
+    }
+
+    fn enumerate(&self, pos: (Word, Word), dir: Direction, walk: &mut Walk, max_cost: usize, routes: &mut Vec<Vec<Action>>) {
+        if routes.len() >= self.limit {
+            return;
+        }
+
+        if walk.covered == self.visitable {
+            routes.push(Instructions(&walk.actions).combine_consecutive().collect());
+            return;
+        }
+
+        // every line with cells left needs a move, and all but the first a turn before it
+        if walk.cost + (2 * walk.lines_left).saturating_sub(1) > max_cost {
+            return;
+        }
+
+        for Segment { dir: new_dir, cells, cost } in self.segments(pos, dir) {
+            if walk.cost + cost > max_cost {
+                continue;
+            }
+
+            let new = cells.iter().filter(|p| !walk.visits.contains_key(p)).count();
+
+            if new == 0 && walk.revisits == 0 {
+                continue;
+            }
+
+            for &p in &cells {
+                self.visit(walk, p);
+            }
+
+            let actions = walk.actions.len();
+            walk.actions.extend(ActionsBetweenDirections::from((dir, new_dir)));
+            walk.actions.push(Action::Move(cells.len()));
+            walk.cost += cost;
+            if new == 0 {
+                walk.revisits -= 1;
+            }
+
+            self.enumerate(*cells.last().unwrap(), new_dir, walk, max_cost, routes);
+
+            if new == 0 {
+                walk.revisits += 1;
+            }
+            walk.cost -= cost;
+            walk.actions.truncate(actions);
+
+            for &p in &cells {
+                self.unvisit(walk, p);
+            }
+        }
+    }
+
+    fn visit(&self, walk: &mut Walk, p: (Word, Word)) {
+        let visits = walk.visits.entry(p).or_insert(0);
+        *visits += 1;
+
+        if *visits == 1 {
+            walk.covered += 1;
+            if let Some(&line) = self.lines.get(&p) {
+                walk.uncovered[line] -= 1;
+                if walk.uncovered[line] == 0 {
+                    walk.lines_left -= 1;
+                }
+            }
+        }
+    }
+
+    fn unvisit(&self, walk: &mut Walk, p: (Word, Word)) {
+        let visits = walk.visits.get_mut(&p).expect("only visited cells are unvisited");
+        *visits -= 1;
+
+        if *visits == 0 {
+            walk.visits.remove(&p);
+            walk.covered -= 1;
+            if let Some(&line) = self.lines.get(&p) {
+                if walk.uncovered[line] == 0 {
+                    walk.lines_left += 1;
+                }
+                walk.uncovered[line] += 1;
+            }
+        }
+    }
+
+    /// The routes which compress, the ones compressing into the fewest calls and then the fewest
+    /// characters first.
+    pub fn ranked<R>(&self, compressor: &Compressor<R>) -> Vec<(Vec<Action>, Compression<Action>)>
+        where R: Fn(&Action) -> Action,
+    {
+        let mut ranked = self.routes()
+            .into_iter()
+            .filter_map(|route| {
+                let best = compressor.compressions(&route)
+                    .into_iter()
+                    .min_by_key(|c| compressed_size(compressor, c))?;
+                Some((route, best))
+            })
+            .collect::<Vec<_>>();
+
+        // sorting is stable, so among the equally compressible the enumeration order is kept
+        ranked.sort_by_key(|(_, c)| compressed_size(compressor, c));
+        ranked
+    }
+}
+
+fn compressed_size<R>(compressor: &Compressor<R>, c: &Compression<Action>) -> (usize, usize)
+    where R: Fn(&Action) -> Action,
+{
+    let chars = compressor.render(c).iter().map(String::len).sum();
+    (c.main.len(), chars)
+}
+
+/// Position and heading of the robot, and the cells it has visited on the way.
+#[derive(Clone)]
+struct Route {
+    pos: (Word, Word),
+    dir: Direction,
+    seen: HashSet<(Word, Word)>,
+    /// Segments which can still visit nothing new.
+    revisits: usize,
+}
+
+/// Straight travel after turning to `dir`, where `cost` counts the turns and the move.
+struct Segment {
+    dir: Direction,
+    cells: Vec<(Word, Word)>,
+    cost: usize,
+}
+
+/// Route being enumerated by [`RoutePlanner::routes`].
+struct Walk {
+    /// Times each visited cell has been visited.
+    visits: HashMap<(Word, Word), usize>,
+    covered: usize,
+    /// Cells not yet visited on each line, see [`RoutePlanner::lines`].
+    uncovered: Vec<usize>,
+    lines_left: usize,
+    actions: Vec<Action>,
+    cost: usize,
+    revisits: usize,
+}
+
+/// Runs the robot with the routines rendered by [`Compressor::render`], main routine first, and
+/// returns the dust it reports collecting.
+pub fn part2_dust_collected(routines: &[String], mut data: Vec<Word>) -> Result<Word, Error> {
+    assert_eq!(data[0], 1);
+    data[0] = 2;
+    let mut program = Program::from(intcode::Memory::from(data).with_memory_expansion());
+    let mut regs = Some(Registers::default());
+    let input = format!("{}\nn\n", routines.join("\n"));
+    let mut input = input.chars();
+    let mut output = String::new();
+
+    loop {
+        regs = Some(match program.eval_from_instruction(regs.take().unwrap()).map_err(|e| format!("{:?}", e))? {
+            ExecutionState::HaltedAt(_) => {
+                let last = output.trim().lines().last().unwrap_or_default();
+                return Err(format!("the robot halted without reporting the dust: {:?}", last).into());
+            },
+            ExecutionState::Paused(regs) => unreachable!("Paused? {:?}", regs),
+            ExecutionState::InputIO(io) => {
+                let ch = input.next().ok_or("the robot asked for more than the routines")?;
+                program.handle_input_completion(io, ch as Word).unwrap()
+            },
+            ExecutionState::OutputIO(io, value) => {
+                if value.abs() > 128 {
+                    return Ok(value);
+                }
+                output.push(value as u8 as char);
+                program.handle_output_completion(io)
+            }
+        });
+    }
+}
+
+/// Travels from the given position to the given direction as long as possible returning the final
+/// position and the steps taken. As long as possible means until path ends or arrive at
+/// intersection.
+fn travel_straight(gd: &GameDisplay<Tile>, pos: (Word, Word), dir: Direction) -> Option<((Word, Word), usize)> {
+    (1..)
+        .map(|steps| (dir, steps))
+        .scan(pos, |acc, (d, steps)| {
+            if is_intersection(gd, *acc) {
+                // do not travel over intersections, those are only crossed with frontier(..)
+                None
+            } else {
+                *acc = acc.step(d);
+                match gd.get(acc) {
+                    Some(x) if x.can_visit() => Some((*acc, steps)),
+                    _ => None
+                }
+            }
+        })
+        .last()
+}
+
+fn frontier<'a>(gd: &'a GameDisplay<Tile>, p1: (Word, Word), dir: Direction) -> impl Iterator<Item = (Direction, (Word, Word))> + 'a {
+    FrontierDirections(dir, 0)
+        .map(move |dir| (dir, p1.step(dir)))
+        .filter_map(move |(dir, p2)| gd.get(&p2).map(|t| (dir, p2, t)))
+        .filter_map(|(d, p, t)| if t.can_visit() { Some((d, p)) } else { None })
+}
+
+struct FrontierDirections(Direction, u8);
+
+impl Iterator for FrontierDirections {
+    type Item = Direction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ret = match self.1 {
+            0 => Some(self.0),
+            1 => Some(self.0.turn_left()),
+            2 => Some(self.0.turn_right()),
+            //3 => Some(self.0.reverse()),
+            _ => None,
+        };
+
+        self.1 += 1;
+        ret
+    }
+}
+
+pub struct Instructions<'a>(pub &'a [Action]);
+
+impl<'a> fmt::Display for Instructions<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+
+        let elements = self.combine_consecutive();
+
+        let mut first = true;
+
+        for action in elements {
+            if !first {
+                write!(fmt, ",")?;
+            }
+
+            first = false;
+
+            write!(fmt, "{}", action)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Instructions<'a> {
+    fn combine_consecutive(&self) -> impl Iterator<Item = Action> + 'a {
+        self.0.iter()
+            .map(Option::Some)
+            .chain(std::iter::repeat(None).take(1))
+            .scan(None, |state, action| {
+                match (state.take(), action) {
+                    (Some(Action::Move(n)), Some(Action::Move(m))) => {
+                        *state = Some(Action::Move(n + m));
+                        return Some(None);
+                    },
+                    (Some(x), Some(y)) => {
+                        *state = Some(*y);
+                        return Some(Some(x));
+                    },
+                    (None, Some(Action::Move(n))) => {
+                        *state = Some(Action::Move(*n));
+                        return Some(None);
+                    }
+                    (None, Some(y)) => Some(Some(*y)),
+                    (Some(x), None) => Some(Some(x)),
+                    (None, None) => None,
+                }
+            })
+            .filter_map(|x| x)
+    }
+}
+
+fn is_intersection(gd: &GameDisplay<Tile>, pos: (Word, Word)) -> bool {
+    use Direction::*;
+
+    [Up, Right, Down, Left]
+        .into_iter()
+        .map(|d| pos.step(*d))
+        .filter_map(|p| gd.get(&p))
+        .filter(|t| t.can_visit())
+        .count() == 4
+}
+
+trait Coordinates {
+    fn step(&self, d: Direction) -> Self;
+}
+
+impl Coordinates for (Word, Word) {
+    fn step(&self, d: Direction) -> Self {
+        match d {
+            Direction::Up => (self.0, self.1 - 1),
+            Direction::Right => (self.0 + 1, self.1),
+            Direction::Down => (self.0, self.1 + 1),
+            Direction::Left => (self.0 - 1, self.1),
+        }
+    }
+}
+
+struct ActionsBetweenDirections {
+    from: Direction,
+    to: Direction,
+    last: Direction,
+}
+
+impl From<(Direction, Direction)> for ActionsBetweenDirections {
+    fn from((from, to): (Direction, Direction)) -> Self {
+        ActionsBetweenDirections {
+            from,
+            to,
+            last: from,
+        }
+    }
+}
+
+impl Iterator for ActionsBetweenDirections {
+    type Item = Action;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use Direction::*;
+        if self.last == self.to {
+            return None;
+        }
+        let (next_from, ret) = match (self.from, self.to) {
+            (Up, Right)
+                | (Up, Down)
+                | (Right, Down)
+                | (Right, Left)
+                | (Down, Left)
+                | (Left, Up) => (self.last.turn_right(), Some(Action::TurnRight)),
+            (Up, Left)
+                | (Right, Up)
+                | (Down, Up)
+                | (Down, Right)
+                | (Left, Right)
+                | (Left, Down) => (self.last.turn_left(), Some(Action::TurnLeft)),
+            z => unreachable!("not sure how to get here: {:?}", z),
+        };
+
+        self.last = next_from;
+        //println!("{:?}..={:?}: {:?} and {:?}", self.from, self.to, self.last, ret);
+        ret
+    }
+}
+
+#[test]
+fn test_actions_between_directions() {
+    use Direction::*;
+    let all = [Up, Right, Down, Left];
+    for dir in all.iter().copied() {
+        assert!(ActionsBetweenDirections::from((dir, dir)).next().is_none());
+    }
+
+    let data = [
+        (Up, Right, 1),
+        (Right, Down, 1),
+        (Down, Left, 1),
+        (Left, Up, 1),
+        (Up, Left, 1),
+        (Left, Down, 1),
+        (Down, Right, 1),
+        (Up, Down, 2),
+        (Right, Left, 2),
+        (Down, Up, 2),
+        (Left, Right, 2)
+    ];
+
+    for (from, to, expected) in data.into_iter() {
+        let iter = ActionsBetweenDirections::from((*from, *to));
+        assert_eq!(iter.count(), *expected, "{:?}..={:?}", from, to);
+
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    TurnLeft,
+    TurnRight,
+    Move(usize),
+    Function(char),
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Action::TurnLeft => write!(fmt, "L"),
+            Action::TurnRight => write!(fmt, "R"),
+            Action::Move(steps) => write!(fmt, "{}", steps),
+            Action::Function(n) => write!(fmt, "{}", n),
+        }
+    }
+}
+
+pub fn alignment_parameters(gd: &GameDisplay<Tile>) -> i64 {
+    let mut pos = (0, 0);
+    loop {
+        if let Some(Tile::Scaffolding) = gd.get(&pos) {
+            break;
+        }
+        pos.0 += 1;
+    }
+
+    let mut visited = HashSet::new();
+    let mut pending: VecDeque<(Option<(Word, Word)>, (Word, Word))> = VecDeque::new();
+
+    pending.push_back((None, pos));
+
+    let mut candidates = Vec::new();
+
+    while let Some((prev, pos)) = pending.pop_front() {
+
+        if !visited.insert(pos) {
+            continue;
+        }
+
+        let next = [
+            (0, 1),
+            (1, 0),
+            (0, -1),
+            (-1, 0)
+        ].into_iter()
+            .map(|d| (pos.0 + d.0, pos.1 + d.1))
+            .filter(|p| Some(*p) != prev)
+            .filter_map(|p| gd.get(&p).and_then(|t| if t.can_visit() { Some(p) } else { None }));
+
+        let mut count = 0;
+        for n in next {
+            pending.push_back((Some(pos), n));
+            count += 1;
+        }
+
+        if count > 1 {
+            candidates.push(pos.0 * pos.1);
+        }
+    }
+
+    candidates.into_iter().sum()
+}
+
+#[derive(Clone)]
+pub struct ScaffoldProgram {
+    program: Program<'static>,
+    regs: Option<Registers>,
+}
+
+impl ScaffoldProgram {
+    pub fn new(data: Vec<Word>) -> Self {
+        let program = Program::from(intcode::Memory::from(data).with_memory_expansion());
+
+        Self {
+            program,
+            regs: Some(Registers::default()),
+        }
+    }
+
+    pub fn print_map(mut self) -> GameDisplay<Tile> {
+        let mut gd = GameDisplay::default();
+        let mut pos = (0, 0);
+        loop {
+            self.regs = Some(match self.program.eval_from_instruction(self.regs.take().unwrap()).unwrap() {
+                ExecutionState::HaltedAt(_) => return gd,
+                ExecutionState::Paused(regs) => unreachable!("Paused? {:?}", regs),
+                ExecutionState::InputIO(_io) => unreachable!("No input expected in print_map?"),
+                ExecutionState::OutputIO(io, value) => {
+                    let value = value as u8;
+                    if value == b'\n' {
+                        pos = (0, pos.1 + 1);
+                    } else {
+                        gd.insert(&pos, Tile::try_from(value).unwrap());
+                        pos.0 += 1;
+                    }
+                    self.program.handle_output_completion(io)
+                }
+            });
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Tile {
+    Scaffolding,
+    Empty,
+    Robot(Direction),
+}
+
+impl Default for Tile {
+    fn default() -> Self { Tile::Empty }
+}
+
+impl Tile {
+    fn can_visit(&self) -> bool {
+        match *self {
+            Tile::Empty => false,
+            _ => true,
+        }
+    }
+
+    fn robot_direction(&self) -> Option<Direction> {
+        match *self {
+            Tile::Robot(d) => Some(d),
+            _ => None,
+        }
+    }
+
+    pub fn color(&self) -> intcode::util::image::Rgb {
+        use intcode::util::image::Rgb;
+        match *self {
+            Tile::Scaffolding => Rgb(200, 200, 200),
+            Tile::Empty => Rgb::BLACK,
+            Tile::Robot(_) => Rgb(220, 40, 40),
+        }
+    }
+}
+
+impl TryFrom<u8> for Tile {
+    type Error = char;
+
+    fn try_from(ascii: u8) -> Result<Self, Self::Error> {
+        Ok(match ascii {
+            b'#' => Tile::Scaffolding,
+            b'.' => Tile::Empty,
+            b'^' => Tile::Robot(Direction::Up),
+            b'>' => Tile::Robot(Direction::Right),
+            b'<' => Tile::Robot(Direction::Left),
+            b'v' => Tile::Robot(Direction::Down),
+            x => return Err(x as char),
+        })
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let ch = match *self {
+            Tile::Scaffolding => '#',
+            Tile::Empty => '.',
+            Tile::Robot(Direction::Up) => '^',
+            Tile::Robot(Direction::Right) => '>',
+            Tile::Robot(Direction::Left) => '<',
+            Tile::Robot(Direction::Down) => 'v',
+        };
+
+        write!(fmt, "{}", ch)
+    }
+}
+
+#[cfg(test)]
+fn parse_map(input: &str) -> GameDisplay<Tile> {
+    input.chars()
+        .scan((0, 0), |pos, ch| {
+
+            if ch == '\n' {
+                *pos = (0, pos.1 + 1);
+                Some(None)
+            } else {
+                let old_pos = *pos;
+                pos.0 += 1;
+
+                Some(Some((old_pos, Tile::try_from(ch as u8).unwrap())))
+            }
+        })
+        .flatten()
+        .fold(GameDisplay::default(), |mut gd, (pos, tile)| {
+            gd.insert(&pos, tile);
+            gd
+        })
+}
+
+#[test]
+fn part2_example() {
+    let input = "\
+#######...#####
+#.....#...#...#
+#.....#...#...#
+......#...#...#
+......#...###.#
+......#.....#.#
+^########...#.#
+......#.#...#.#
+......#########
+........#...#..
+....#########..
+....#...#......
+....#...#......
+....#...#......
+....#####......";
+
+    let gd = parse_map(input);
+
+    let intersections = gd.iter()
+        .filter(|(_, t)| t.can_visit())
+        .filter(|(p, _)| is_intersection(&gd, *p))
+        //.inspect(|x| println!("intersection: {:?}", x))
+        ;
+
+    assert_eq!(intersections.clone().count(), 4);
+
+    for (p, _) in intersections {
+        for d in [Direction::Up, Direction::Right, Direction::Down, Direction::Left].into_iter() {
+            // being able to head to all but the direction we came from sounds good
+            assert_eq!(frontier(&gd, p, *d).count(), 3)
+        }
+    }
+
+    assert_eq!(travel_straight(&gd, (1, 6), Direction::Right).unwrap(), ((6, 6), 5));
+
+    let (route, compression) = part2_find_path(&gd);
+
+    assert_eq!(compression.expand(), route);
+
+    // the fewest calls win over the split published with the example, "A,B,C,B,A,C"
+    let routines = movement_functions().render(&compression);
+    assert_eq!(routines, vec!["A,B,C", "R,8,R,8,R,4,R,4", "R,8,L,6,L,2,R,4,R,4", "R,8,R,8,R,8,L,6,L,2"]);
+    assert!(routines.iter().all(|r| r.len() <= 20));
+}
+
+#[test]
+fn routes_coming_back_from_dead_ends() {
+    let gd = parse_map("\
+..#..
+..#..
+>####
+..#..
+..#..");
+
+    // two of the three arms have to be travelled back from
+    let mut planner = RoutePlanner::new(&gd);
+    planner.revisits = 1;
+    assert_eq!(planner.shortest(), None);
+    assert!(planner.routes().is_empty());
+
+    planner.revisits = 2;
+    let shortest = planner.shortest().unwrap();
+    let routes = planner.routes();
+    assert!(routes.contains(&Instructions(&shortest).combine_consecutive().collect()));
+
+    let u_turn = |w: &[Action]| w == [Action::TurnLeft, Action::TurnLeft] || w == [Action::TurnRight, Action::TurnRight];
+    assert!(routes.iter().all(|route| route.windows(2).filter(|w| u_turn(w)).count() == 2));
+}
+
+#[test]
+fn format_instructions() {
+    let input = Instructions(&[Action::TurnLeft, Action::Move(2), Action::Move(4), Action::TurnRight]);
+
+    assert_eq!(format!("{}", input).as_str(), "L,6,R");
+}
@@ -1,766 +1,38 @@
-use std::fmt;
-use std::convert::TryFrom;
-use std::collections::{VecDeque, HashSet, HashMap};
-use std::collections::hash_map::Entry;
-use intcode::{Word, util::{parse_stdin_program_n_lines, GameDisplay}, Program, Registers, ExecutionState};
+use intcode::util::parse_stdin_program_n_lines;
+use intcode::util::image::Frame;
+use aoc2019_day17::{Day17, ScaffoldProgram, Tile, Instructions, alignment_parameters, movement_functions, part2_find_path, part2_dust_collected};
 
 fn main() {
-    let input = parse_stdin_program_n_lines(Some(1));
-    let mut gd = ScaffoldProgram::new(input.clone()).print_map();
-    {
-        println!("part1: {}", alignment_parameters(&gd));
+    // optional argument: path to write the scaffolding as png
+    match std::env::args().nth(1) {
+        Some(path) => explain(path),
+        None => aoc_common::solution::main::<Day17>(),
     }
-
-    let actions = part2_find_path(&mut gd);
-    println!("{:>8}: {}", "raw", Instructions(actions.as_slice()));
-
-    let (main, a, b, c) = Instructions(actions.as_slice()).compress();
-    println!("{:>8}: {}", "MAIN", Instructions(main.as_slice()));
-    println!("{:>8}: {}", "A", Instructions(a.as_slice()));
-    println!("{:>8}: {}", "B", Instructions(b.as_slice()));
-    println!("{:>8}: {}", "C", Instructions(c.as_slice()));
-    println!();
-
-    println!("part2: {}", part2_dust_collected(main.as_slice(), a.as_slice(), b.as_slice(), c.as_slice(), input));
 }
 
-fn part2_find_path(gd: &mut GameDisplay<Tile>) -> Vec<Action> {
-    let robot_initially_at = gd.cells()
-        .iter()
-        .position(|t| if let &Tile::Robot(_) = t { true } else { false })
-        .map(|index| gd.to_coordinates(index))
-        .unwrap();
-
-    let robot_initial_direction = gd.get(&robot_initially_at)
-        .and_then(Tile::robot_direction).unwrap();
-
-    let visitable = gd.cells()
-        .iter()
-        .filter(|t| t.can_visit())
-        .count();
-
-    let mut work = binary_heap_plus::BinaryHeap::new_by(
-        |a: &(_, _, Vec<Action>, _, HashSet<(Word, Word)>, _), b: &(_, _, Vec<Action>, _, HashSet<(Word, Word)>, _)| {
-            use std::cmp::Ordering;
-
-            match a.4.len().cmp(&b.4.len()) {
-                Ordering::Equal => a.2.len().cmp(&b.2.len()).reverse(),
-                x => x
-            }
-        }
-    );
-
+/// Writes the map and prints the compressed movement routines on the way to the answers.
+fn explain(path: String) {
+    let input = parse_stdin_program_n_lines(Some(1));
+    let gd = ScaffoldProgram::new(input.clone()).print_map();
     {
-        let intersections = HashMap::new();
-        let mut seen = HashSet::new();
-        seen.insert(robot_initially_at);
-        work.push((robot_initially_at, robot_initial_direction, Vec::new(), intersections, seen, 0));
-    }
-
-    // current winning solution
-    let mut smallest = None;
-
-    // so do a bfs and do pruning by not exploring the solutions which take longer than the first
-    // completed. this finds the same solutions as the "continue until corners". I was hoping the
-    // input to require coming back but the current frontier solution cannot find such.
-    while let Some((pos, dir, actions_here, intersections, seen, gas)) = work.pop() {
-        if seen.len() == visitable {
-            smallest = match smallest.as_ref() {
-                None => Some(actions_here),
-                Some(other) if other.len() > actions_here.len() => Some(actions_here),
-                Some(_) => continue,
-            };
-            continue;
-        }
-
-        let seen_before = seen.len();
-        //println!("{}", visitable - seen_before);
-
-        for (new_dir, mut new_pos) in frontier(gd, pos, dir) {
-            let (final_pos, steps) = travel_straight(gd, new_pos, new_dir)
-                .expect("frontier point should be travellable");
-
-            let mut intersections = intersections.clone();
-            let mut seen = seen.clone();
-
-            let start_pos = new_pos;
-            seen.insert(start_pos);
-
-            for _ in 0..steps {
-                new_pos = new_pos.step(new_dir);
-                seen.insert(new_pos);
-            }
-
-            assert_eq!(new_pos, final_pos);
-
-            if is_intersection(gd, new_pos) {
-                match intersections.entry((new_pos, (dir, new_dir))) {
-                    Entry::Occupied(mut oc) => {
-                        if *oc.get() > 2 {
-                            // this is no longer hit?
-                            continue;
-                        }
-                        *oc.get_mut() += 1;
-                    }
-                    Entry::Vacant(vcnt) => { vcnt.insert(1); },
-                }
-            }
-
-            let mut actions = actions_here.clone();
-            actions.extend(ActionsBetweenDirections::from((dir, new_dir)));
-            actions.push(Action::Move(steps + 1));
-
-            let gas = if seen_before < seen.len() {
-                gas
-            } else if gas > 0 {
-                gas - 1
-            } else {
-                // this seems to prune most
-                continue;
-            };
-
-            match smallest.as_ref() {
-                Some(x) if x.len() < actions.len() => continue,
-                Some(_) | None => {}
-            }
-
-            work.push((new_pos, new_dir, actions, intersections, seen, gas));
-        }
-    }
-
-    match smallest {
-        Some(instructions) => instructions,
-        None => unreachable!("no solutions"),
-    }
-}
-
-fn part2_dust_collected(main: &[Action], a: &[Action], b: &[Action], c: &[Action], mut data: Vec<Word>) -> Word {
-    assert_eq!(data[0], 1);
-    data[0] = 2;
-    let mut program = Program::from(intcode::Memory::from(data).with_memory_expansion());
-    let mut regs = Some(Registers::default());
-    let input = format!("{}\n{}\n{}\n{}\nn\n", Instructions(main), Instructions(a), Instructions(b), Instructions(c));
-    let mut input = input.chars();
-
-    loop {
-        regs = Some(match program.eval_from_instruction(regs.take().unwrap()).unwrap() {
-            ExecutionState::HaltedAt(_) => todo!("no dust value was received?"),
-            ExecutionState::Paused(regs) => unreachable!("Paused? {:?}", regs),
-            ExecutionState::InputIO(io) => {
-                let ch = input.next().unwrap();
-                print!("{}", ch);
-                program.handle_input_completion(io, ch as Word).unwrap()
-            },
-            ExecutionState::OutputIO(io, value) => {
-                if value.abs() > 128 {
-                    return value;
-                }
-                print!("{}", value as u8 as char);
-                program.handle_output_completion(io)
-            }
-        });
-    }
-}
-
-/// Travels from the given position to the given direction as long as possible returning the final
-/// position and the steps taken. As long as possible means until path ends or arrive at
-/// intersection.
-fn travel_straight(gd: &GameDisplay<Tile>, pos: (Word, Word), dir: Direction) -> Option<((Word, Word), usize)> {
-    (1..)
-        .map(|steps| (dir, steps))
-        .scan(pos, |acc, (d, steps)| {
-            if is_intersection(gd, *acc) {
-                // do not travel over intersections, those are only crossed with frontier(..)
-                None
-            } else {
-                *acc = acc.step(d);
-                match gd.get(acc) {
-                    Some(x) if x.can_visit() => Some((*acc, steps)),
-                    _ => None
-                }
-            }
-        })
-        .last()
-}
-
-fn frontier<'a>(gd: &'a GameDisplay<Tile>, p1: (Word, Word), dir: Direction) -> impl Iterator<Item = (Direction, (Word, Word))> + 'a {
-    FrontierDirections(dir, 0)
-        .map(move |dir| (dir, p1.step(dir)))
-        .filter_map(move |(dir, p2)| gd.get(&p2).map(|t| (dir, p2, t)))
-        .filter_map(|(d, p, t)| if t.can_visit() { Some((d, p)) } else { None })
-}
-
-struct FrontierDirections(Direction, u8);
-
-impl Iterator for FrontierDirections {
-    type Item = Direction;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let ret = match self.1 {
-            0 => Some(self.0),
-            1 => Some(self.0.to_left()),
-            2 => Some(self.0.to_right()),
-            //3 => Some(self.0.reverse()),
-            _ => None,
-        };
-
-        self.1 += 1;
-        ret
-    }
-}
-
-struct Instructions<'a>(&'a [Action]);
-
-impl<'a> fmt::Display for Instructions<'a> {
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-
-        let elements = self.combine_consecutive();
-
-        let mut first = true;
-
-        for action in elements {
-            if !first {
-                write!(fmt, ",")?;
-            }
-
-            first = false;
-
-            match action {
-                Action::TurnLeft => write!(fmt, "L"),
-                Action::TurnRight => write!(fmt, "R"),
-                Action::Move(steps) => write!(fmt, "{}", steps),
-                Action::Function(n) => write!(fmt, "{}", n),
-            }?;
-        }
-
-        Ok(())
-    }
-}
-
-impl<'a> Instructions<'a> {
-    fn combine_consecutive(&self) -> impl Iterator<Item = Action> + 'a {
-        self.0.iter()
-            .map(Option::Some)
-            .chain(std::iter::repeat(None).take(1))
-            .scan(None, |state, action| {
-                match (state.take(), action) {
-                    (Some(Action::Move(n)), Some(Action::Move(m))) => {
-                        *state = Some(Action::Move(n + m));
-                        return Some(None);
-                    },
-                    (Some(x), Some(y)) => {
-                        *state = Some(*y);
-                        return Some(Some(x));
-                    },
-                    (None, Some(Action::Move(n))) => {
-                        *state = Some(Action::Move(*n));
-                        return Some(None);
-                    }
-                    (None, Some(y)) => Some(Some(*y)),
-                    (Some(x), None) => Some(Some(x)),
-                    (None, None) => None,
-                }
-            })
-            .filter_map(|x| x)
-    }
-
-    fn compress(&self) -> (Vec<Action>, Vec<Action>, Vec<Action>, Vec<Action>) {
-        let limit = 20;
-
-        let base = self.combine_consecutive().collect::<Vec<_>>();
-
-        if 2 * base.len() - 1 < limit {
-            (base, Vec::new(), Vec::new(), Vec::new())
-        } else {
-
-            let mut work = VecDeque::new();
-
-            work.push_back(Compression { main: base, a: Vec::new(), b: Vec::new(), c: Vec::new() });
-
-            let mut good = Vec::new();
-
-            while let Some(Compression { main, a, b, c }) = work.pop_front() {
-
-                if !c.is_empty() && 2 * main.len() - 1 < limit {
-                    if let None = main.iter().position(|a| match a { Action::Function(_) => false, _ => true }) {
-                        good.push(Compression { main, a, b, c });
-                    }
-                    continue;
-                } else if !a.is_empty() && !b.is_empty() && !c.is_empty() {
-                    continue;
-                }
-
-                let start = match main.iter().position(|x| match x { Action::Function(_) => false, _ => true }) {
-                    Some(x) => x,
-                    None => continue,
-                };
-
-                let end = main[start..].iter().position(|x| match x { Action::Function(_) => true, _ => false }).map(|x| start + x).unwrap_or(main.len());
-
-                for l in (2..=end - start).into_iter() {
-                    let slice = &main[start..start + l];
-                    let mut find_start = start;
-                    let mut segments = Vec::new();
-
-                    while find_start + l <= main.len() {
-                        let haystack = &main[find_start..find_start + l];
-                        assert_eq!(slice.len(), haystack.len());
-                        if haystack == slice {
-                            segments.push(find_start..find_start + l);
-                            find_start += l;
-                        } else {
-                            find_start += 1;
-                        }
-                    }
-
-                    if segments.len() > 0 {
-                        let act = Action::Function(
-                            match (&a[..], &b[..], &c[..]) {
-                                ([], _, _) => 'A',
-                                (_, [], _) => 'B',
-                                (_, _, []) => 'C',
-                                _ => unreachable!(),
-                            }
-                        );
-
-                        let mut new_main = Vec::new();
-
-                        let mut i = 0;
-                        let mut replacements = 0;
-                        for segment in segments {
-                            while i < segment.start {
-                                new_main.push(main[i]);
-                                i += 1;
-                            }
-                            new_main.push(act);
-                            i += l;
-                            assert_eq!(i, segment.end);
-                            replacements += 1;
-                        }
-
-                        while i < main.len() {
-                            new_main.push(main[i]);
-                            i += 1;
-                        }
-
-                        let repeated = main[start..start + l].to_vec();
-                        assert_eq!(new_main.len() + replacements * (repeated.len() - 1), main.len());
-
-                        let (new_a, new_b, new_c) = match (&a[..], &b[..], &c[..]) {
-                            ([], _, _) => (repeated, Vec::new(), Vec::new()),
-                            (_, [], _) => (a.clone(), repeated, Vec::new()),
-                            (_, _, []) => (a.clone(), b.clone(), repeated),
-                            (_, _, _) => unreachable!(),
-                        };
-
-                        work.push_back(Compression { main: new_main, a: new_a, b: new_b, c: new_c });
-                    }
-                }
-            }
-
-            // this could be moved to work on a option as well...
-            good.sort_by_key(|c| {
-                let total = (c.main.len() + c.a.len() + c.b.len() + c.c.len()) as isize;
-                let avg = total / 4;
-
-                // fit the example output to match...
-                (c.main.len() as isize - avg).pow(2)
-                    + (c.a.len() as isize - avg).pow(2)
-                    + (c.b.len() as isize - avg).pow(2)
-                    + (c.c.len() as isize - avg).pow(2)
-            });
-
-            good.truncate(1);
-
-            let Compression { main, a, b, c } = good.remove(0);
-
-            (main, a, b, c)
-        }
-    }
-}
-
-struct Compression {
-    main: Vec<Action>,
-    a: Vec<Action>,
-    b: Vec<Action>,
-    c: Vec<Action>,
-}
-
-impl fmt::Debug for Compression {
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            fmt,
-            "{{ main: {}, a: {}, b: {}, c: {} }}",
-            Instructions(self.main.as_slice()),
-            Instructions(self.a.as_slice()),
-            Instructions(self.b.as_slice()),
-            Instructions(self.c.as_slice()))
-    }
-}
-
-fn is_intersection(gd: &GameDisplay<Tile>, pos: (Word, Word)) -> bool {
-    use Direction::*;
-
-    [North, West, South, East]
-        .into_iter()
-        .map(|d| pos.step(*d))
-        .filter_map(|p| gd.get(&p))
-        .filter(|t| t.can_visit())
-        .count() == 4
-}
-
-trait Coordinates {
-    fn step(&self, d: Direction) -> Self;
-}
-
-impl Coordinates for (Word, Word) {
-    fn step(&self, d: Direction) -> Self {
-        match d {
-            Direction::North => (self.0, self.1 - 1),
-            Direction::West => (self.0 + 1, self.1),
-            Direction::South => (self.0, self.1 + 1),
-            Direction::East => (self.0 - 1, self.1),
-        }
-    }
-}
-
-struct ActionsBetweenDirections {
-    from: Direction,
-    to: Direction,
-    last: Direction,
-}
-
-impl From<(Direction, Direction)> for ActionsBetweenDirections {
-    fn from((from, to): (Direction, Direction)) -> Self {
-        ActionsBetweenDirections {
-            from,
-            to,
-            last: from,
-        }
-    }
-}
-
-impl Iterator for ActionsBetweenDirections {
-    type Item = Action;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        use Direction::*;
-        if self.last == self.to {
-            return None;
-        }
-        let (next_from, ret) = match (self.from, self.to) {
-            (North, West)
-                | (North, South)
-                | (West, South)
-                | (West, East)
-                | (South, East)
-                | (East, North) => (self.last.to_right(), Some(Action::TurnRight)),
-            (North, East)
-                | (West, North)
-                | (South, North)
-                | (South, West)
-                | (East, West)
-                | (East, South) => (self.last.to_left(), Some(Action::TurnLeft)),
-            z => unreachable!("not sure how to get here: {:?}", z),
-        };
-
-        self.last = next_from;
-        //println!("{:?}..={:?}: {:?} and {:?}", self.from, self.to, self.last, ret);
-        ret
-    }
-}
-
-#[test]
-fn test_actions_between_directions() {
-    use Direction::*;
-    let all = [North, West, South, East];
-    for dir in all.iter().copied() {
-        assert!(ActionsBetweenDirections::from((dir, dir)).next().is_none());
-    }
-
-    let data = [
-        (North, West, 1),
-        (West, South, 1),
-        (South, East, 1),
-        (East, North, 1),
-        (North, East, 1),
-        (East, South, 1),
-        (South, West, 1),
-        (North, South, 2),
-        (West, East, 2),
-        (South, North, 2),
-        (East, West, 2)
-    ];
-
-    for (from, to, expected) in data.into_iter() {
-        let iter = ActionsBetweenDirections::from((*from, *to));
-        assert_eq!(iter.count(), *expected, "{:?}..={:?}", from, to);
-
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum Action {
-    TurnLeft,
-    TurnRight,
-    Move(usize),
-    Function(char),
-}
-
-fn alignment_parameters(gd: &GameDisplay<Tile>) -> i64 {
-    let mut pos = (0, 0);
-    loop {
-        if let Some(Tile::Scaffolding) = gd.get(&pos) {
-            break;
-        }
-        pos.0 += 1;
-    }
-
-    let mut visited = HashSet::new();
-    let mut pending: VecDeque<(Option<(Word, Word)>, (Word, Word))> = VecDeque::new();
-
-    pending.push_back((None, pos));
-
-    let mut candidates = Vec::new();
-
-    while let Some((prev, pos)) = pending.pop_front() {
-
-        if !visited.insert(pos) {
-            continue;
-        }
-
-        let next = [
-            (0, 1),
-            (1, 0),
-            (0, -1),
-            (-1, 0)
-        ].into_iter()
-            .map(|d| (pos.0 + d.0, pos.1 + d.1))
-            .filter(|p| Some(*p) != prev)
-            .filter_map(|p| gd.get(&p).and_then(|t| if t.can_visit() { Some(p) } else { None }));
-
-        let mut count = 0;
-        for n in next {
-            pending.push_back((Some(pos), n));
-            count += 1;
-        }
-
-        if count > 1 {
-            candidates.push(pos.0 * pos.1);
-        }
-    }
-
-    candidates.into_iter().sum()
-}
-
-#[derive(Clone)]
-struct ScaffoldProgram {
-    program: Program<'static>,
-    regs: Option<Registers>,
-}
-
-impl ScaffoldProgram {
-    fn new(data: Vec<Word>) -> Self {
-        let program = Program::from(intcode::Memory::from(data).with_memory_expansion());
-
-        Self {
-            program,
-            regs: Some(Registers::default()),
-        }
-    }
-
-    fn print_map(mut self) -> GameDisplay<Tile> {
-        let mut gd = GameDisplay::default();
-        let mut pos = (0, 0);
-        loop {
-            self.regs = Some(match self.program.eval_from_instruction(self.regs.take().unwrap()).unwrap() {
-                ExecutionState::HaltedAt(_) => return gd,
-                ExecutionState::Paused(regs) => unreachable!("Paused? {:?}", regs),
-                ExecutionState::InputIO(_io) => unreachable!("No input expected in print_map?"),
-                ExecutionState::OutputIO(io, value) => {
-                    let value = value as u8;
-                    if value == b'\n' {
-                        pos = (0, pos.1 + 1);
-                    } else {
-                        gd.insert(&pos, Tile::try_from(value).unwrap());
-                        pos.0 += 1;
-                    }
-                    self.program.handle_output_completion(io)
-                }
-            });
-        }
-    }
-}
-
-#[derive(Debug, PartialEq, Eq, Clone)]
-enum Tile {
-    Scaffolding,
-    Empty,
-    Robot(Direction),
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
-enum Direction {
-    West,
-    North,
-    East,
-    South,
-}
-
-impl Direction {
-    fn to_left(&self) -> Direction {
-        use Direction::*;
-        match *self {
-            North => East,
-            West => North,
-            South => West,
-            East => South,
-        }
-    }
-
-    fn to_right(&self) -> Direction {
-        use Direction::*;
-        match *self {
-            North => West,
-            West => South,
-            South => East,
-            East => North,
-        }
-    }
-
-    fn reverse(&self) -> Direction {
-        use Direction::*;
-        match *self {
-            North => South,
-            South => North,
-            West => East,
-            East => West,
-        }
-    }
-}
-
-
-impl Default for Tile {
-    fn default() -> Self { Tile::Empty }
-}
-
-impl Tile {
-    fn can_visit(&self) -> bool {
-        match *self {
-            Tile::Empty => false,
-            _ => true,
-        }
-    }
-
-    fn robot_direction(&self) -> Option<Direction> {
-        match *self {
-            Tile::Robot(d) => Some(d),
-            _ => None,
-        }
+        println!("part1: {}", alignment_parameters(&gd));
     }
-}
-
-impl TryFrom<u8> for Tile {
-    type Error = char;
 
-    fn try_from(ascii: u8) -> Result<Self, Self::Error> {
-        Ok(match ascii {
-            b'#' => Tile::Scaffolding,
-            b'.' => Tile::Empty,
-            b'^' => Tile::Robot(Direction::North),
-            b'>' => Tile::Robot(Direction::West),
-            b'<' => Tile::Robot(Direction::East),
-            b'v' => Tile::Robot(Direction::South),
-            x => return Err(x as char),
-        })
-    }
-}
+    let file = std::fs::File::create(&path).unwrap();
+    Frame::capture(&gd, &Tile::color).write_png(8, std::io::BufWriter::new(file)).unwrap();
+    println!("wrote map to {}", path);
 
-impl fmt::Display for Tile {
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        let ch = match *self {
-            Tile::Scaffolding => '#',
-            Tile::Empty => '.',
-            Tile::Robot(Direction::North) => '^',
-            Tile::Robot(Direction::West) => '>',
-            Tile::Robot(Direction::East) => '<',
-            Tile::Robot(Direction::South) => 'v',
-        };
+    let (actions, compression) = part2_find_path(&gd);
+    println!("{:>8}: {}", "raw", Instructions(actions.as_slice()));
 
-        write!(fmt, "{}", ch)
+    let routines = movement_functions().render(&compression);
+    for (name, routine) in ["MAIN", "A", "B", "C"].iter().zip(&routines) {
+        println!("{:>8}: {}", name, routine);
     }
-}
-
-#[test]
-fn part2_example() {
-    let input = "\
-#######...#####
-#.....#...#...#
-#.....#...#...#
-......#...#...#
-......#...###.#
-......#.....#.#
-^########...#.#
-......#.#...#.#
-......#########
-........#...#..
-....#########..
-....#...#......
-....#...#......
-....#...#......
-....#####......";
-
-    let mut gd = input.chars()
-        .scan((0, 0), |pos, ch| {
-
-            if ch == '\n' {
-                *pos = (0, pos.1 + 1);
-                Some(None)
-            } else {
-                let old_pos = *pos;
-                pos.0 += 1;
-
-                Some(Some((old_pos, Tile::try_from(ch as u8).unwrap())))
-            }
-        })
-        .filter_map(|x| x)
-        .fold(GameDisplay::default(), |mut gd, (pos, tile)| {
-            gd.insert(&pos, tile);
-            gd
-        });
-
-    let intersections = gd.cells().iter()
-        .enumerate()
-        .filter(|(_, t)| t.can_visit())
-        .map(|(i, tile)| (gd.to_coordinates(i), tile))
-        .filter(|(p, _)| is_intersection(&gd, *p))
-        //.inspect(|x| println!("intersection: {:?}", x))
-        ;
-
-    assert_eq!(intersections.clone().count(), 4);
+    println!();
 
-    for (p, _) in intersections {
-        for d in [Direction::North, Direction::West, Direction::South, Direction::East].into_iter() {
-            // being able to head to all but the direction we came from sounds good
-            assert_eq!(frontier(&gd, p, *d).count(), 3)
-        }
+    match part2_dust_collected(&routines, input) {
+        Ok(dust) => println!("part2: {}", dust),
+        Err(e) => println!("part2 failed: {}", e),
     }
-
-    assert_eq!(travel_straight(&gd, (1, 6), Direction::West).unwrap(), ((6, 6), 5));
-
-    let actions = part2_find_path(&mut gd);
-
-    let (main, a, b, c) = Instructions(actions.as_slice()).compress();
-
-    assert_eq!(format!("{}", Instructions(main.as_slice())).as_str(), "A,B,C,B,A,C");
-    assert_eq!(format!("{}", Instructions(a.as_slice())).as_str(), "R,8,R,8");
-    assert_eq!(format!("{}", Instructions(b.as_slice())).as_str(), "R,4,R,4");
-    assert_eq!(format!("{}", Instructions(c.as_slice())).as_str(), "R,8,L,6,L,2");
-}
-
-#[test]
-fn format_instructions() {
-    let input = Instructions(&[Action::TurnLeft, Action::Move(2), Action::Move(4), Action::TurnRight]);
-
-    assert_eq!(format!("{}", input).as_str(), "L,6,R");
 }
@@ -0,0 +1,602 @@
+use intcode::{util::GameDisplay, Word};
+use intcode::util::poi::{Cell, PoiGraph};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use std::collections::HashMap;
+use smallvec::SmallVec;
+use aoc_common::search::Search;
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Map;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Map::from_str(input).map_err(|InvalidTile(ch)| format!("invalid tile: {:?}", ch).into())
+    }
+
+    fn part_one(map: &Self::Input) -> Result<usize, Error> {
+        Ok(steps_to_collect_all_keys(&mut map.clone(), Part::One))
+    }
+
+    fn part_two(map: &Self::Input) -> Result<usize, Error> {
+        Ok(steps_to_collect_all_keys(&mut map.clone(), Part::Two))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Part {
+    One,
+    Two
+}
+
+pub fn steps_to_collect_all_keys(m: &mut Map, part: Part) -> usize {
+    collect_all_keys(m, part)
+        .expect("failed to find a single path through all keys")
+        .steps
+}
+
+/// Collects the keys with a robot at each entrance of the map. On the second part the single
+/// entrance is split into four, while the maps which already have more entrances are used as they
+/// are.
+pub fn collect_all_keys(m: &mut Map, part: Part) -> Option<Collection> {
+    let entrances = match (part, m.entrances.as_slice()) {
+        (Part::Two, &[initial]) => {
+
+            // expand the map around the portal
+            let mut around = vec![
+                (-1,-1), ( 0,-1), ( 1,-1),
+                (-1, 0), ( 0, 0), ( 1, 0),
+                (-1, 1), ( 0, 1), ( 1, 1),
+            ];
+
+            around.iter_mut().for_each(|p| *p = (p.0 + initial.0, p.1 + initial.1));
+
+            let ret = vec![around[0], around[2], around[6], around[8]];
+
+            for x in around {
+                if ret.contains(&x) {
+                    m.gd.insert(&x, Tile::Empty);
+                    continue;
+                }
+                m.gd.insert(&x, Tile::Wall);
+            }
+
+            ret
+        }
+        (_, entrances) => entrances.to_vec(),
+    };
+
+    KeyCollector::new(&m.gd, &entrances).collect()
+}
+
+/// Steps taken to collect all of the keys, and the order the robots picked them up in as pairs of
+/// the robot and the key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Collection {
+    pub steps: usize,
+    pub order: Vec<(usize, char)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Node {
+    Entrance,
+    Key(usize),
+    /// The door and the key which opens it, if there is one.
+    Door(Option<usize>),
+}
+
+/// Nodes where the robots are.
+type Robots = SmallVec<[usize; 4]>;
+
+/// Memoized search for the fewest steps the robots need to collect all of the keys.
+///
+/// The graph is between the keys, the doors and the entrances, and the keys reachable from each
+/// node with the keys held are walked once and kept in a table. The fewest steps from each state
+/// of the robot positions and the keys held is then found recursively by trying every reachable
+/// key with every robot, remembering the results.
+pub struct KeyCollector {
+    graph: PoiGraph<()>,
+    nodes: Vec<Node>,
+    names: Vec<char>,
+    entrances: Robots,
+    all: KeySet,
+    reachable: HashMap<(usize, KeySet), Vec<(usize, usize)>>,
+    best: HashMap<(Robots, KeySet), Option<Best>>,
+}
+
+/// Fewest steps from a state, and the robot and the key node it moves to first.
+#[derive(Clone, Copy)]
+struct Best {
+    steps: usize,
+    next: Option<(usize, usize)>,
+}
+
+impl KeyCollector {
+    fn new(gd: &GameDisplay<Tile>, entrances: &[(Word, Word)]) -> Self {
+        // every walk stops at the first point of interest so the doors and keys on the way are
+        // handled by the walks over the graph
+        let graph = PoiGraph::<()>::build(gd, |p, t| match t {
+            Tile::Wall => Cell::Wall,
+            Tile::Key(_) | Tile::Door(_) => Cell::Interesting { stop: true },
+            _ if entrances.contains(&p) => Cell::Interesting { stop: true },
+            _ => Cell::Open,
+        }, |_, _, _| {});
+
+        let mut names = graph.nodes()
+            .filter_map(|(_, p)| match gd.get(&p) {
+                Some(&Tile::Key(ch)) => Some(ch),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        names.sort_unstable();
+
+        assert!(names.len() <= KeySet::CAPACITY, "too many keys: {}", names.len());
+
+        let index = |ch: char| names.iter().position(|&key| key == ch);
+
+        let nodes = graph.nodes()
+            .map(|(_, p)| match gd.get(&p) {
+                Some(&Tile::Key(ch)) => Node::Key(index(ch).unwrap()),
+                Some(&Tile::Door(ch)) => Node::Door(ch.to_lowercase().next().and_then(index)),
+                _ => Node::Entrance,
+            })
+            .collect::<Vec<_>>();
+
+        let entrances = entrances.iter()
+            .map(|p| graph.node(p).expect("entrances are nodes"))
+            .collect();
+
+        let all = (0..names.len()).fold(KeySet::default(), |ks, key| ks.with(key));
+
+        KeyCollector {
+            graph,
+            nodes,
+            names,
+            entrances,
+            all,
+            reachable: HashMap::new(),
+            best: HashMap::new(),
+        }
+    }
+
+    /// Collects every key, or `None` if some of them cannot be reached.
+    pub fn collect(&mut self) -> Option<Collection> {
+        let mut robots = self.entrances.clone();
+        let mut keys = KeySet::default();
+
+        let steps = self.fewest_steps(&robots, keys)?;
+        let mut order = Vec::new();
+
+        while let Some(Some(Best { next: Some((robot, to)), .. })) = self.best.get(&(robots.clone(), keys)) {
+            let (robot, to) = (*robot, *to);
+            let key = match self.nodes[to] {
+                Node::Key(key) => key,
+                _ => unreachable!("robots only move to keys"),
+            };
+
+            order.push((robot, self.names[key]));
+            robots[robot] = to;
+            keys = keys.with(key);
+        }
+
+        Some(Collection { steps, order })
+    }
+
+    fn fewest_steps(&mut self, robots: &Robots, keys: KeySet) -> Option<usize> {
+        if keys == self.all {
+            return Some(0);
+        }
+
+        if let Some(best) = self.best.get(&(robots.clone(), keys)) {
+            return best.map(|best| best.steps);
+        }
+
+        let mut best = None;
+
+        for (robot, &at) in robots.iter().enumerate() {
+            for (to, steps) in self.reachable_keys(at, keys) {
+                let key = match self.nodes[to] {
+                    Node::Key(key) => key,
+                    _ => unreachable!("only keys are reachable"),
+                };
+
+                let mut moved = robots.clone();
+                moved[robot] = to;
+
+                let rest = match self.fewest_steps(&moved, keys.with(key)) {
+                    Some(rest) => rest,
+                    None => continue,
+                };
+
+                if best.map(|best: Best| steps + rest < best.steps).unwrap_or(true) {
+                    best = Some(Best { steps: steps + rest, next: Some((robot, to)) });
+                }
+            }
+        }
+
+        self.best.insert((robots.clone(), keys), best);
+        best.map(|best| best.steps)
+    }
+
+    /// The keys not yet held which can be walked to from the node with the keys held, through the
+    /// doors they open, as the key nodes and the steps to them.
+    fn reachable_keys(&mut self, from: usize, keys: KeySet) -> Vec<(usize, usize)> {
+        if let Some(reachable) = self.reachable.get(&(from, keys)) {
+            return reachable.clone();
+        }
+
+        let (graph, nodes) = (&self.graph, &self.nodes);
+
+        let passable = |at: usize| at == from || match nodes[at] {
+            Node::Entrance => true,
+            Node::Key(key) => keys.contains(key),
+            Node::Door(key) => key.map(|key| keys.contains(key)).unwrap_or(false),
+        };
+
+        // the states carry the steps to them for the goal test, but are remembered by the node
+        let mut search = Search::visited_by(|&(at, steps): &(usize, usize)| {
+            let edges = if passable(at) { graph.edges(at) } else { &[] };
+            edges.iter()
+                .map(|edge| ((edge.to, steps + edge.steps), edge.steps))
+                .collect::<Vec<_>>()
+        }, |&(at, _): &(usize, usize)| at);
+
+        let mut reachable = Vec::new();
+
+        // no goal is ever found, so every node reachable is taken from the frontier once with the
+        // fewest steps to it
+        search.dijkstra((from, 0), |&(at, steps)| {
+            // walking over a key picks it up, so the walks end at the new ones
+            if let (false, Node::Key(_)) = (passable(at), nodes[at]) {
+                reachable.push((at, steps));
+            }
+            false
+        });
+
+        self.reachable.insert((from, keys), reachable.clone());
+        reachable
+    }
+}
+
+/// Set of keys by their index, for up to 128 keys.
+#[derive(Default, Clone, Copy, Hash, PartialEq, Eq)]
+struct KeySet(u128);
+
+impl KeySet {
+    const CAPACITY: usize = 128;
+
+    fn with(self, key: usize) -> Self {
+        KeySet(self.0 | 1 << key)
+    }
+
+    fn contains(&self, key: usize) -> bool {
+        self.0 & 1 << key != 0
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..Self::CAPACITY).filter(move |&key| self.contains(key))
+    }
+}
+
+impl fmt::Debug for KeySet {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[test]
+fn keyset_past_64_keys() {
+    let ks = KeySet::default().with(0).with(70).with(127);
+
+    assert!(ks.contains(70));
+    assert!(!ks.contains(64));
+    assert_eq!(ks.len(), 3);
+    assert_eq!(ks.iter().collect::<Vec<_>>(), vec![0, 70, 127]);
+
+    assert_eq!(format!("{:?}", ks), "{0, 70, 127}");
+}
+
+#[derive(Clone)]
+pub struct Map {
+    gd: GameDisplay<Tile>,
+    entrances: Vec<(Word, Word)>,
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.gd.fmt(fmt)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Hash, Eq)]
+enum Tile {
+    Empty,
+    Wall,
+    Portal,
+    Key(char),
+    Door(char),
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let ch = match *self {
+            Tile::Empty => '.',
+            Tile::Wall => '#',
+            Tile::Portal => '@',
+            Tile::Key(ch) => ch,
+            Tile::Door(ch) => ch,
+        };
+
+        write!(fmt, "{}", ch)
+    }
+}
+
+impl Default for Tile {
+    fn default() -> Self {
+        Tile::Empty
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidTile(char);
+
+impl TryFrom<char> for Tile {
+    type Error = InvalidTile;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        Ok(match ch {
+            '.' => Tile::Empty,
+            '#' => Tile::Wall,
+            '@' => Tile::Portal,
+            // past the 26 of the puzzle the keys and doors are any other pairs of letters
+            ch if ch.is_lowercase() => Tile::Key(ch),
+            ch if ch.is_uppercase() && ch.to_lowercase().count() == 1 => Tile::Door(ch),
+            x => return Err(InvalidTile(x)),
+        })
+    }
+}
+
+impl FromStr for Map {
+    type Err = InvalidTile;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mut gd, _) = s
+            .trim()
+            .chars()
+            .scan((0i64, 0i64), |mut acc, ch| match ch {
+                '\n' => {
+                    acc.1 += 1;
+                    acc.0 = 0;
+                    Some((*acc, None))
+                }
+                _ => {
+                    let old = *acc;
+                    acc.0 += 1;
+                    Some((old, Some(ch)))
+                }
+            })
+            .filter_map(|(pos, ch)| match ch {
+                Some(ch) => Some((pos, ch)),
+                None => None,
+            })
+            .map(|(pos, ch)| (pos, Tile::try_from(ch)))
+            .fold(Ok((GameDisplay::default(), HashMap::new())), |gd, (pos, tile)| {
+                let (mut gd, mut poi) = gd.unwrap();
+                let tile = tile?;
+
+                match &tile {
+                    &Tile::Door(_)
+                    | &Tile::Key(_) => {
+                        let old = poi.insert(tile.clone(), pos.clone());
+                        if let Some(x) = old {
+                            panic!("duplicate coordinates for tile {:?}: {:?} and {:?}", tile, pos, x);
+                        }
+                    },
+                    _ => {}
+                }
+
+                gd.insert(&pos, tile);
+                Ok((gd, poi))
+            })?;
+
+        let entrances = gd.iter()
+            .filter(|(_, t)| **t == Tile::Portal)
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>();
+
+        assert!(!entrances.is_empty(), "No portal '@' found on the map");
+
+        for p in &entrances {
+            gd.insert(p, Tile::Empty);
+        }
+
+        Ok(Map {
+            gd,
+            entrances,
+        })
+    }
+}
+
+#[test]
+fn parse_first_map() {
+    let s = "\
+#########
+#b.A.@.a#
+#########";
+
+    let m = Map::from_str(s).unwrap();
+
+    assert_eq!(s.trim().replace("@", "."), format!("{}", m).trim());
+    assert_eq!(vec![(5, 1)], m.entrances);
+    assert_eq!(Some(&Tile::Key('a')), m.gd.get(&(7, 1)));
+    assert_eq!(Some(&Tile::Door('A')), m.gd.get(&(3, 1)));
+    assert_eq!(Some(&Tile::Key('b')), m.gd.get(&(1, 1)));
+
+    assert_eq!(&Tile::Wall, m.gd.get(&(0, 0)).unwrap());
+}
+
+#[test]
+fn full_first_example() {
+    let s = "\
+#########
+#b.A.@.a#
+#########";
+
+    let mut m = Map::from_str(s).unwrap();
+
+    assert_eq!(steps_to_collect_all_keys(&mut m, Part::One), 8);
+}
+
+#[test]
+fn first_multiple_choice() {
+    let s = "\
+########################
+#f.D.E.e.C.b.A.@.a.B.c.#
+######################.#
+#d.....................#
+########################";
+
+    let mut m = Map::from_str(s).unwrap();
+
+    assert_eq!(steps_to_collect_all_keys(&mut m, Part::One), 86);
+}
+
+#[test]
+fn second_multiple_choice() {
+    let s = "\
+########################
+#...............b.C.D.f#
+#.######################
+#.....@.a.B.c.d.A.e.F.g#
+########################";
+
+    let mut m = Map::from_str(s).unwrap();
+
+    assert_eq!(steps_to_collect_all_keys(&mut m, Part::One), 132);
+}
+
+#[test]
+fn collection_order() {
+    let s = "\
+########################
+#f.D.E.e.C.b.A.@.a.B.c.#
+######################.#
+#d.....................#
+########################";
+
+    let mut m = Map::from_str(s).unwrap();
+    let collection = collect_all_keys(&mut m, Part::One).unwrap();
+
+    assert_eq!(collection.steps, 86);
+    assert_eq!(collection.order.iter().map(|&(_, key)| key).collect::<String>(), "abcdef");
+}
+
+#[test]
+fn many_orders_sixteen_keys() {
+    let s = "\
+#################
+#i.G..c...e..H.p#
+########.########
+#j.A..b...f..D.o#
+########@########
+#k.E..a...g..B.n#
+########.########
+#l.F..d...h..C.m#
+#################";
+
+    assert_eq!(steps_to_collect_all_keys(&mut Map::from_str(s).unwrap(), Part::One), 136);
+
+    let s = "\
+########################
+#@..............ac.GI.b#
+###d#e#f################
+###A#B#C################
+###g#h#i################
+########################";
+
+    assert_eq!(steps_to_collect_all_keys(&mut Map::from_str(s).unwrap(), Part::One), 81);
+}
+
+#[test]
+fn split_entrance() {
+    let s = "\
+#######
+#a.#Cd#
+##...##
+##.@.##
+##...##
+#cB#Ab#
+#######";
+
+    let mut m = Map::from_str(s).unwrap();
+    let collection = collect_all_keys(&mut m, Part::Two).unwrap();
+
+    assert_eq!(collection.steps, 8);
+    assert_eq!(collection.order, vec![(0, 'a'), (3, 'b'), (2, 'c'), (1, 'd')]);
+}
+
+#[test]
+fn four_robots() {
+    let examples = [("\
+###############
+#d.ABC.#.....a#
+######@#@######
+###############
+######@#@######
+#b.....#.....c#
+###############", 24), ("\
+#############
+#DcBa.#.GhKl#
+#.###@#@#I###
+#e#d#####j#k#
+###C#@#@###J#
+#fEbA.#.FgHi#
+#############", 32), ("\
+#############
+#g#f.D#..h#l#
+#F###e#E###.#
+#dCba@#@BcIJ#
+#############
+#nK.L@#@G...#
+#M###N#H###.#
+#o#m..#i#jk.#
+#############", 72)];
+
+    for &(s, steps) in &examples {
+        let mut m = Map::from_str(s).unwrap();
+        assert_eq!(m.entrances.len(), 4);
+        assert_eq!(steps_to_collect_all_keys(&mut m, Part::Two), steps);
+    }
+}
+
+#[test]
+fn more_keys_than_letters() {
+    // the latin, greek and cyrillic letters in a corridor, with one of them opening the door to
+    // the last
+    let keys = ('a'..='z').chain('α'..='ω').chain('а'..='я')
+        .filter(|&ch| ch != 'ς')
+        .collect::<Vec<_>>();
+
+    assert!(keys.len() > 64);
+
+    let (lock, last) = (keys[40], keys[keys.len() - 1]);
+    let rest = keys.iter().filter(|&&ch| ch != lock && ch != last).collect::<String>();
+    let s = format!("#{}@{}{}{}#", lock, rest, lock.to_uppercase(), last);
+
+    let mut m = Map::from_str(&s).unwrap();
+    let collection = collect_all_keys(&mut m, Part::One).unwrap();
+
+    assert_eq!(collection.steps, 2 + rest.chars().count() + 2);
+    assert_eq!(collection.order.iter().map(|&(_, key)| key).collect::<String>(), format!("{}{}{}", lock, rest, last));
+}
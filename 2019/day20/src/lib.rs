@@ -0,0 +1,587 @@
+use std::fmt;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use intcode::{Word, util::{GameDisplay, Position}};
+use intcode::util::poi::{Cell, PoiGraph};
+use aoc_common::search::{Found, Search};
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = GameDisplay<ParsedTile>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let mut gd = GameDisplay::default();
+        gd.parse_grid_lines(input, (0, 0)).map_err(|ch| format!("invalid tile: {:?}", ch))?;
+        Ok(gd)
+    }
+
+    fn part_one(gd: &Self::Input) -> Result<usize, Error> {
+        Ok(shortest_path(gd, "AA", "ZZ", Levels::Flat))
+    }
+
+    fn part_two(gd: &Self::Input) -> Result<usize, Error> {
+        Ok(shortest_path(gd, "AA", "ZZ", Levels::Recursive))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParsedTile {
+    Dot,
+    Space,
+    Wall,
+    Key(char),
+}
+
+impl Default for ParsedTile {
+    fn default() -> ParsedTile {
+        ParsedTile::Space
+    }
+}
+
+impl fmt::Display for ParsedTile {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use ParsedTile::*;
+        let ch = match *self {
+            Dot => '.',
+            Space => ' ',
+            Wall => '#',
+            Key(ch) => ch,
+        };
+
+        write!(fmt, "{}", ch)
+    }
+}
+
+impl TryFrom<char> for ParsedTile {
+    type Error = char;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        Ok(match ch {
+            ' ' => ParsedTile::Space,
+            '.' => ParsedTile::Dot,
+            '#' => ParsedTile::Wall,
+            ch @ 'A'..='Z' => ParsedTile::Key(ch),
+            ch => return Err(ch),
+        })
+    }
+}
+
+/// The dot next to a portal, outer ones being at the edges of the maze.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Portal {
+    Outer(Position),
+    Inner(Position),
+}
+
+impl Into<Position> for Portal {
+    fn into(self) -> Position {
+        match self {
+            Portal::Outer(x) | Portal::Inner(x) => x
+        }
+    }
+}
+
+impl Portal {
+    pub fn into_position(self) -> Position {
+        self.into()
+    }
+}
+
+/// How the portals change the levels of the maze.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Levels {
+    /// The portals only teleport, staying on the same level.
+    Flat,
+    /// The inner portals take one level deeper and the outer ones one level back up, with the
+    /// outer portals of the outermost level being walls.
+    Recursive,
+    /// Recursive, but never deeper than the given level. Unlike the unlimited recursion, the
+    /// search ends even when there is no path.
+    Limited(usize),
+}
+
+/// Parts of a route through the maze.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Leg {
+    /// Walking on a level over the cells, including the first and the last.
+    Walk { level: usize, cells: Vec<(Word, Word)> },
+    /// Stepping through the portal with the label, from a level to another.
+    Teleport { label: String, from: usize, to: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub steps: usize,
+    pub legs: Vec<Leg>,
+}
+
+/// The labels of the portals with the dots next to them.
+pub fn find_portal_exits(gd: &GameDisplay<ParsedTile>) -> HashMap<String, Vec<Portal>> {
+
+    let mut min_dot: Option<(Word, Word)> = None;
+    let mut max_dot: Option<(Word, Word)> = None;
+
+    // this seems quite wasteful, but couldn't think of a simpler way
+    for (x, y) in gd.iter().filter_map(|(p, t)| if t == &ParsedTile::Dot { Some(p) } else { None }) {
+        min_dot = min_dot.map(|(mx, my)| (mx.min(x), my.min(y))).or(Some((x, y)));
+        max_dot = max_dot.map(|(mx, my)| (mx.max(x), my.max(y))).or(Some((x, y)));
+    }
+
+    let min_dot = min_dot.unwrap();
+    let max_dot = max_dot.unwrap();
+
+    let letter = |p: Position| match gd.get(&p.into()) {
+        Some(&ParsedTile::Key(ch)) => Some(ch),
+        _ => None,
+    };
+
+    let is_dot = |p: Position| gd.get(&p.into()) == Some(&ParsedTile::Dot);
+
+    let mut ret = HashMap::new();
+
+    let starts = gd.iter()
+        .filter(|(_, t)| matches!(t, ParsedTile::Key(_)))
+        .map(|(p, _)| Position::from(p));
+
+    for p1 in starts {
+        // labels are read left to right or top to bottom, from the first letter of a run of
+        // letters, and the dot is next to either end of the run
+        for &step in &[(1, 0), (0, 1)] {
+            let before = p1 - step;
+
+            if letter(before).is_some() {
+                continue;
+            }
+
+            let label = std::iter::successors(Some(p1), |p| Some(*p + step))
+                .map(letter)
+                .take_while(Option::is_some)
+                .map(Option::unwrap)
+                .collect::<String>();
+
+            let after = p1 + Position::from(step) * label.len() as Word;
+
+            let dot = if is_dot(before) {
+                before
+            } else if is_dot(after) {
+                after
+            } else {
+                continue;
+            };
+
+            let at_bounds = dot.x() == min_dot.0 || dot.x() == max_dot.0
+                || dot.y() == min_dot.1 || dot.y() == max_dot.1;
+
+            let dot = if at_bounds { Portal::Outer(dot) } else { Portal::Inner(dot) };
+
+            ret.entry(label).or_insert_with(Vec::new).push(dot);
+        }
+    }
+
+    ret
+}
+
+/// Maze where walking onto a portal steps out of the other portal with the same label.
+///
+/// The dots next to the portals are the only interesting places, so the searches are over a graph
+/// of the walks between them.
+pub struct PortalMaze<'a> {
+    gd: &'a GameDisplay<ParsedTile>,
+    exits: HashMap<String, Vec<Portal>>,
+    graph: PoiGraph<()>,
+    /// For each node the label, the node on the other side of the portal if there is one, and
+    /// whether the portal is an inner one.
+    teleports: Vec<(String, Option<(usize, bool)>)>,
+}
+
+impl<'a> PortalMaze<'a> {
+    pub fn new(gd: &'a GameDisplay<ParsedTile>) -> Self {
+        let exits = find_portal_exits(gd);
+
+        let portal_dots = exits.values()
+            .flatten()
+            .map(|portal| portal.into_position().into())
+            .collect::<HashSet<(Word, Word)>>();
+
+        let graph = PoiGraph::<()>::build(gd, |p, t| match t {
+            ParsedTile::Dot if portal_dots.contains(&p) => Cell::Interesting { stop: true },
+            ParsedTile::Dot => Cell::Open,
+            _ => Cell::Wall,
+        }, |_, _, _| {});
+
+        let node = |portal: &Portal| graph.node(&portal.into_position().into()).expect("portals are nodes");
+
+        let mut teleports = vec![(String::new(), None); graph.len()];
+
+        for (label, portals) in &exits {
+            for portal in portals {
+                let other = portals.iter()
+                    .find(|other| *other != portal)
+                    .map(|other| (node(other), matches!(portal, Portal::Inner(_))));
+
+                teleports[node(portal)] = (label.clone(), other);
+            }
+        }
+
+        PortalMaze { gd, exits, graph, teleports }
+    }
+
+    /// The dots next to the portals with the label.
+    pub fn portal(&self, label: &str) -> Option<&[Portal]> {
+        self.exits.get(label).map(Vec::as_slice)
+    }
+
+    /// Fewest steps from the portal to another, starting and ending on the outermost level.
+    pub fn shortest(&self, from: &str, to: &str, levels: Levels) -> Option<usize> {
+        self.search(from, to, levels).map(|found| found.cost)
+    }
+
+    /// The shortest route with every cell walked over and the level changes on the way.
+    pub fn route(&self, from: &str, to: &str, levels: Levels) -> Option<Route> {
+        let found = self.search(from, to, levels)?;
+        let mut legs = Vec::new();
+
+        for w in found.path.windows(2) {
+            let ((level, a), (next_level, b)) = (w[0], w[1]);
+
+            // only the flat teleports stay on the same level
+            let partner = self.teleports[a].1.map(|(other, _)| other);
+            let teleported = level != next_level || (levels == Levels::Flat && partner == Some(b));
+
+            if teleported {
+                let label = self.teleports[a].0.clone();
+                legs.push(Leg::Teleport { label, from: level, to: next_level });
+                continue;
+            }
+
+            let cells = self.walk(self.graph.position(a), self.graph.position(b));
+
+            match legs.last_mut() {
+                // walking on from where the previous walk ended
+                Some(Leg::Walk { level: prev, cells: prev_cells }) if *prev == level => {
+                    prev_cells.extend(cells.into_iter().skip(1));
+                }
+                _ => legs.push(Leg::Walk { level, cells }),
+            }
+        }
+
+        Some(Route { steps: found.cost, legs })
+    }
+
+    fn search(&self, from: &str, to: &str, levels: Levels) -> Option<Found<(usize, usize), usize>> {
+        let node = |label: &str| {
+            let portals = self.portal(label)?;
+            assert_eq!(portals.len(), 1, "{} should be a single portal", label);
+            self.graph.node(&portals[0].into_position().into())
+        };
+
+        let (start, end) = (node(from)?, node(to)?);
+
+        // the states are the levels and the nodes
+        let mut search = Search::new(|&(level, n): &(usize, usize)| {
+            let walks = self.graph.edges(n).iter()
+                .map(move |e| ((level, e.to), e.steps));
+
+            let teleport = self.teleports[n].1.and_then(|(other, inner)| {
+                let level = match (levels, inner) {
+                    (Levels::Flat, _) => level,
+                    (Levels::Limited(max), true) if level == max => return None,
+                    (_, true) => level + 1,
+                    (_, false) => level.checked_sub(1)?,
+                };
+                Some(((level, other), 1))
+            });
+
+            walks.chain(teleport)
+        });
+
+        search.dijkstra((0, start), |&state| state == (0, end))
+    }
+
+    /// Cells of a shortest walk between the positions.
+    fn walk(&self, from: (Word, Word), to: (Word, Word)) -> Vec<(Word, Word)> {
+        let mut search = Search::new(|&p: &(Word, Word)| {
+            [(0, -1), (1, 0), (0, 1), (-1, 0)].iter()
+                .map(move |d| (p.0 + d.0, p.1 + d.1))
+                .filter(|q| self.gd.get(q) == Some(&ParsedTile::Dot))
+                .collect::<Vec<_>>()
+        });
+
+        search.bfs(from, |&p| p == to)
+            .expect("the nodes of the graph are walked between")
+            .path
+    }
+}
+
+pub fn shortest_path(gd: &GameDisplay<ParsedTile>, start: &str, end: &str, levels: Levels) -> usize {
+    PortalMaze::new(gd)
+        .shortest(start, end, levels)
+        .unwrap_or_else(|| panic!("should have found a path from {} to {}", start, end))
+}
+
+#[cfg(test)]
+fn read_first_example() -> GameDisplay<ParsedTile> {
+    let first = "\
+_________A_________
+         A_________
+  #######.#########
+  #######.........#
+  #######.#######.#
+  #######.#######.#
+  #######.#######.#
+  #####  B    ###.#
+BC...##  C    ###.#
+  ##.##       ###.#
+  ##...DE  F  ###.#
+  #####    G  ###.#
+  #########.#####.#
+DE..#######...###.#
+  #.#########.###.#
+FG..#########.....#
+  ###########.#####
+             Z_____
+             Z_____".replace("_", " ");
+
+    let reader = std::io::Cursor::new(first.as_bytes());
+
+    let mut output: GameDisplay<ParsedTile> = GameDisplay::default();
+    output.parse_from_reader((0, 0), reader).unwrap();
+
+    assert_eq!(first, format!("{}", output));
+
+    output
+}
+
+#[test]
+fn parse_first_map() {
+    read_first_example();
+}
+
+#[test]
+fn first_map_exits() {
+    let exits = find_portal_exits(&read_first_example());
+    assert_eq!(exits.get("AA"), Some(&vec![Portal::Outer((9, 2).into())]));
+    assert_eq!(exits.get("ZZ"), Some(&vec![Portal::Outer((13, 16).into())]));
+    assert_eq!(exits.get("BC"), Some(&vec![Portal::Inner((9, 6).into()), Portal::Outer((2, 8).into())]));
+}
+
+#[test]
+fn first_example_cost() {
+    assert_eq!(shortest_path(&read_first_example(), "AA", "ZZ", Levels::Flat), 23);
+}
+
+#[cfg(test)]
+fn read_second_example() -> GameDisplay<ParsedTile> {
+    let first = "\
+___________________A_______________
+                   A_______________
+  #################.#############__
+  #.#...#...................#.#.#__
+  #.#.#.###.###.###.#########.#.#__
+  #.#.#.......#...#.....#.#.#...#__
+  #.#########.###.#####.#.#.###.#__
+  #.............#.#.....#.......#__
+  ###.###########.###.#####.#.#.#__
+  #.....#        A   C    #.#.#.#__
+  #######        S   P    #####.#__
+  #.#...#                 #......VT
+  #.#.#.#                 #.#####__
+  #...#.#               YN....#.#__
+  #.###.#                 #####.#__
+DI....#.#                 #.....#__
+  #####.#                 #.###.#__
+ZZ......#               QG....#..AS
+  ###.###                 #######__
+JO..#.#.#                 #.....#__
+  #.#.#.#                 ###.#.#__
+  #...#..DI             BU....#..LF
+  #####.#                 #.#####__
+YN......#               VT..#....QG
+  #.###.#                 #.###.#__
+  #.#...#                 #.....#__
+  ###.###    J L     J    #.#.###__
+  #.....#    O F     P    #.#...#__
+  #.###.#####.#.#####.#####.###.#__
+  #...#.#.#...#.....#.....#.#...#__
+  #.#####.###.###.#.#.#########.#__
+  #...#.#.....#...#.#.#.#.....#.#__
+  #.###.#####.###.###.#.#.#######__
+  #.#.........#...#.............#__
+  #########.###.###.#############__
+           B   J   C             __
+           U   P   P             __".replace("_", " ");
+
+    let reader = std::io::Cursor::new(first.as_bytes());
+
+    let mut output: GameDisplay<ParsedTile> = GameDisplay::default();
+    output.parse_from_reader((0, 0), reader).unwrap();
+
+    assert_eq!(first, format!("{}", output));
+
+    output
+}
+
+#[test]
+fn parse_second_map() {
+    read_second_example();
+}
+
+#[test]
+fn second_example_cost() {
+    assert_eq!(shortest_path(&read_second_example(), "AA", "ZZ", Levels::Flat), 58);
+}
+
+#[cfg(test)]
+fn read_recursive_example() -> GameDisplay<ParsedTile> {
+    let first = "\
+_____________Z L X W       C               __
+             Z P Q B       K               __
+  ###########.#.#.#.#######.###############__
+  #...#.......#.#.......#.#.......#.#.#...#__
+  ###.#.#.#.#.#.#.#.###.#.#.#######.#.#.###__
+  #.#...#.#.#...#.#.#...#...#...#.#.......#__
+  #.###.#######.###.###.#.###.###.#.#######__
+  #...#.......#.#...#...#.............#...#__
+  #.#########.#######.#.#######.#######.###__
+  #...#.#    F       R I       Z    #.#.#.#__
+  #.###.#    D       E C       H    #.#.#.#__
+  #.#...#                           #...#.#__
+  #.###.#                           #.###.#__
+  #.#....OA                       WB..#.#..ZH
+  #.###.#                           #.#.#.#__
+CJ......#                           #.....#__
+  #######                           #######__
+  #.#....CK                         #......IC
+  #.###.#                           #.###.#__
+  #.....#                           #...#.#__
+  ###.###                           #.#.#.#__
+XF....#.#                         RF..#.#.#__
+  #####.#                           #######__
+  #......CJ                       NM..#...#__
+  ###.#.#                           #.###.#__
+RE....#.#                           #......RF
+  ###.###        X   X       L      #.#.#.#__
+  #.....#        F   Q       P      #.#.#.#__
+  ###.###########.###.#######.#########.###__
+  #.....#...#.....#.......#...#.....#.#...#__
+  #####.#.###.#######.#######.###.###.#.#.#__
+  #.......#.......#.#.#.#.#...#...#...#.#.#__
+  #####.###.#####.#.#.#.#.###.###.#.###.###__
+  #.......#.....#.#...#...............#...#__
+  #############.#.#.###.###################__
+               A O F   N                   __
+               A A D   M                   __".replace("_", " ");
+
+    let reader = std::io::Cursor::new(first.as_bytes());
+
+    let mut output: GameDisplay<ParsedTile> = GameDisplay::default();
+    output.parse_from_reader((0, 0), reader).unwrap();
+
+    assert_eq!(first, format!("{}", output));
+
+    output
+}
+
+#[test]
+fn recursive_path() {
+    let output = read_recursive_example();
+
+    let exits = find_portal_exits(&output);
+
+    assert_eq!(exits["OA"], &[Portal::Inner((8, 13).into()), Portal::Outer((17, 34).into())]);
+    assert_eq!(exits["XF"], &[Portal::Outer((2, 21).into()), Portal::Inner((17, 28).into())]);
+
+    assert_eq!(shortest_path(&output, "AA", "ZZ", Levels::Recursive), 396);
+}
+
+#[cfg(test)]
+fn read_long_labels() -> GameDisplay<ParsedTile> {
+    let map = "\
+_____A_________
+_____A_________
+_____A_________
+__###.#####____
+__#...#...LONG_
+__###.#.###____
+LONG..#.#______
+__#####.#______
+_______Z_______
+_______Z_______".replace("_", " ");
+
+    let mut output: GameDisplay<ParsedTile> = GameDisplay::default();
+    output.parse_from_reader((0, 0), std::io::Cursor::new(map.as_bytes())).unwrap();
+    output
+}
+
+#[test]
+fn long_labels() {
+    let gd = read_long_labels();
+    let maze = PortalMaze::new(&gd);
+
+    assert_eq!(maze.portal("AAA"), Some(&[Portal::Outer((5, 3).into())][..]));
+    assert_eq!(maze.portal("LONG"), Some(&[Portal::Outer((9, 4).into()), Portal::Inner((4, 6).into())][..]));
+    assert_eq!(maze.portal("ZZ"), Some(&[Portal::Outer((7, 7).into())][..]));
+
+    let route = maze.route("AAA", "ZZ", Levels::Flat).unwrap();
+
+    assert_eq!(route, Route {
+        steps: 10,
+        legs: vec![
+            Leg::Walk { level: 0, cells: vec![(5, 3), (5, 4), (5, 5), (5, 6), (4, 6)] },
+            Leg::Teleport { label: String::from("LONG"), from: 0, to: 0 },
+            Leg::Walk { level: 0, cells: vec![(9, 4), (8, 4), (7, 4), (7, 5), (7, 6), (7, 7)] },
+        ],
+    });
+
+    // the inner portal leads a level down, where the exit is a wall
+    assert_eq!(maze.shortest("AAA", "ZZ", Levels::Recursive), None);
+    assert_eq!(maze.shortest("AAA", "ZZ", Levels::Limited(0)), None);
+}
+
+#[test]
+fn flat_route_covers_every_step() {
+    let gd = read_second_example();
+    let maze = PortalMaze::new(&gd);
+
+    let route = maze.route("AA", "ZZ", Levels::Flat).unwrap();
+    assert_eq!(route.steps, 58);
+
+    let steps = route.legs.iter()
+        .map(|leg| match leg {
+            Leg::Walk { cells, .. } => cells.len() - 1,
+            Leg::Teleport { .. } => 1,
+        })
+        .sum::<usize>();
+
+    assert_eq!(steps, 58);
+    assert!(route.legs.iter().all(|leg| match leg {
+        Leg::Walk { level, .. } => *level == 0,
+        Leg::Teleport { from, to, .. } => (*from, *to) == (0, 0),
+    }));
+}
+
+#[test]
+fn depth_limited_recursion() {
+    let gd = read_recursive_example();
+    let maze = PortalMaze::new(&gd);
+
+    let route = maze.route("AA", "ZZ", Levels::Recursive).unwrap();
+    assert_eq!(route.steps, 396);
+
+    // the published route goes ten levels deep
+    let deepest = route.legs.iter()
+        .filter_map(|leg| match leg {
+            Leg::Teleport { to, .. } => Some(*to),
+            _ => None,
+        })
+        .max();
+
+    assert_eq!(deepest, Some(10));
+
+    assert_eq!(maze.shortest("AA", "ZZ", Levels::Limited(10)), Some(396));
+    assert!(maze.shortest("AA", "ZZ", Levels::Limited(9)).map(|steps| steps > 396).unwrap_or(true));
+}
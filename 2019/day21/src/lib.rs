@@ -0,0 +1,1086 @@
+#![allow(dead_code)]
+use std::marker::PhantomData;
+use intcode::{Word, ExecutionState, Program, Registers};
+use std::fmt;
+use intcode::util::parse_program_n_lines;
+use aoc_common::evolve::{ChaCha8Rng, Evolution, Observer, Score, Selection};
+use aoc_common::parse::{self, Cursor, ParseError};
+use aoc_common::search::Search;
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Vec<Word>;
+    type PartOne = Word;
+    type PartTwo = Word;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_program_n_lines(input.as_bytes(), Some(1))?)
+    }
+
+    fn part_one(data: &Self::Input) -> Result<Word, Error> {
+        hull_damage::<Part1>(data, WALK)
+    }
+
+    fn part_two(data: &Self::Input) -> Result<Word, Error> {
+        hull_damage::<Part2>(data, RUN)
+    }
+}
+
+/// Jump when there is a hole in the next three tiles and ground to land on.
+pub const WALK: &str = "!(A & B & C) & D";
+
+/// As when walking, but only when after landing the droid can either take a step or jump again.
+pub const RUN: &str = "!(A & B & C) & D & (E | H)";
+
+/// Compiles the formula and runs it on the droid, failing with the ground it fell on.
+pub fn hull_damage<T: Mode + Clone>(data: &[Word], formula: &str) -> Result<Word, Error> {
+    let script = compile::<T>(&formula.parse()?)?.script;
+
+    let mut script_buffer = String::new();
+    let mut output_buffer = String::new();
+
+    test(data, &script, &mut script_buffer, &mut output_buffer).ok_or_else(|| {
+        let ground = fallen_on(&output_buffer);
+        let hull = output_buffer.trim().lines().last().unwrap_or_default();
+        match simulate(&script, &ground) {
+            Outcome::Fell { at, from } => format!("the droid fell with {:?} into the hole at {} from {} on {}", formula, at, from, hull),
+            Outcome::Survived => format!("the droid fell with {:?} on {}", formula, hull),
+        }.into()
+    })
+}
+
+/// Evolves a springscript until the droid makes it over the hull, the same every time for the
+/// same seed, with `progress` observing every generation.
+pub fn random_stage<T, P>(data: &[Word], seed: u64, progress: P) -> Word
+    where Standard: Distribution<Op<T>>,
+          T: Mode + Clone,
+          P: Observer,
+{
+    let mut script_buffer = String::new();
+    let mut output_buffer = String::new();
+
+    // initially thought about enumerating all possible programs but failed to do that with
+    // permutohedron.. next idea: figure out packing mechanism to/from bytes and just generate
+    // random numbers?
+
+    let mut testcases = Vec::new();
+
+    // mixing impl was tested up to 10000 rounds with no avail with length mixing, mutation
+    // 0.15 and mixing of two... no luck. IDEA: try windowed mixing of 4 maybe?
+    //
+    // simply mutating gets the job done in ~200..50000 rounds, almost all best solutions come from
+    // mutation. thanks birkenfeld.
+    Evolution::new(|rng: &mut ChaCha8Rng| rng.gen::<SpringScript<T>>(), SpringScript::mutate)
+        .population(100)
+        .elitism(20)
+        .newcomers(40)
+        .selection(Selection::Tournament(5))
+        // this will cut down the time wasted significantly
+        // 10 is too slow and might not give a result
+        .restart_after(100, 2)
+        .seed(seed)
+        .progress(progress)
+        .run(|program: &SpringScript<T>| {
+            match score(&testcases, data, program, &mut script_buffer, &mut output_buffer) {
+                Ok(x) => Score::Solved(x),
+                Err((testcase, score)) => {
+                    testcases.extend(testcase);
+                    Score::Fitness(score)
+                },
+            }
+        })
+        .expect("evolution runs until found")
+        .answer
+}
+
+#[inline(never)]
+fn debug_print(i: usize, script: &str, output: &str) {
+    println!("--- {}", i);
+    println!("{}", script);
+    println!("{}", output.trim());
+    println!("---");
+}
+
+pub trait Mode {
+    fn command() -> &'static str;
+    /// Number of the sensors from `A` onwards the droid can read.
+    fn sensors() -> usize;
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Part1;
+#[derive(Debug, PartialEq, Clone)]
+pub struct Part2;
+
+fn score<T: Mode>(testcases: &[Vec<bool>], data: &[Word], ops: &[Op<T>], script: &mut String, output: &mut String) -> Result<Word, (Option<Vec<bool>>, usize)> {
+
+    // this idea is from https://github.com/birkenfeld/advent19/blob/master/src/bin/day21.rs didn't
+    // think it was necessary but it is fast
+
+    if let Some(i) = testcases.iter().position(|ground| simulate(ops, ground) != Outcome::Survived) {
+        return Err((None, i));
+    }
+
+    match test(data, ops, script, output) {
+        Some(x) => Ok(x),
+        None => Err((Some(fallen_on(output)), testcases.len())),
+    }
+}
+
+/// The ground the droid program printed last before the droid fell, `true` for the hull.
+fn fallen_on(output: &str) -> Vec<bool> {
+    output.trim()
+        .lines()
+        .last()
+        .unwrap_or_default()
+        .chars()
+        .map(|ch| ch == '#')
+        .collect()
+}
+
+fn test<T: Mode>(data: &[Word], ops: &[Op<T>], script: &mut String, output: &mut String) -> Option<Word> {
+    use std::fmt::Write;
+
+    let mut data = data.to_vec();
+    let mut program = Program::wrap(&mut data)
+        .with_memory_expansion();
+
+    let mut regs = Registers::default();
+
+    script.clear();
+    for op in ops {
+        write!(script, "{}\n", op).unwrap();
+    }
+
+    write!(script, "{}\n", T::command()).unwrap();
+
+    let mut chars = script.chars();
+    output.clear();
+
+    loop {
+        regs = match program.eval_from_instruction(regs).unwrap() {
+            ExecutionState::Paused(_regs) => unreachable!("Pausing not implemented yet?"),
+            ExecutionState::HaltedAt(_regs) => {
+                // maybe use regs as score? or maybe should analyze the executed instructions?
+                return None;
+            },
+            ExecutionState::InputIO(io) => {
+                program.handle_input_completion(io, chars.next().unwrap() as Word).unwrap()
+            },
+            ExecutionState::OutputIO(io, value) => {
+
+                if value > 127 {
+                    return Some(value);
+                }
+
+                output.push(value as u8 as char);
+                program.handle_output_completion(io)
+            },
+        };
+    }
+}
+
+/// Boolean formula over the sensors, the source the springscripts are compiled from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Formula {
+    Sensor(ReadOnlyRegister),
+    Not(Box<Formula>),
+    And(Box<Formula>, Box<Formula>),
+    Or(Box<Formula>, Box<Formula>),
+}
+
+impl Formula {
+    /// Value of the formula with the given sensor readings.
+    pub fn eval<F: Fn(ReadOnlyRegister) -> bool + Copy>(&self, sensors: F) -> bool {
+        match self {
+            Formula::Sensor(r) => sensors(*r),
+            Formula::Not(f) => !f.eval(sensors),
+            Formula::And(a, b) => a.eval(sensors) && b.eval(sensors),
+            Formula::Or(a, b) => a.eval(sensors) || b.eval(sensors),
+        }
+    }
+
+    /// The operands of a chain of the same binary operation, like `A`, `B` and `C` of `A & B & C`.
+    fn operands(&self) -> Vec<&Formula> {
+        match self {
+            Formula::And(a, b) => [a, b].iter()
+                .flat_map(|f| match &***f {
+                    Formula::And(..) => f.operands(),
+                    f => vec![f],
+                })
+                .collect(),
+            Formula::Or(a, b) => [a, b].iter()
+                .flat_map(|f| match &***f {
+                    Formula::Or(..) => f.operands(),
+                    f => vec![f],
+                })
+                .collect(),
+            f => vec![f],
+        }
+    }
+
+    /// The sensors read by the formula in order.
+    pub fn sensors(&self) -> Vec<ReadOnlyRegister> {
+        fn collect(f: &Formula, out: &mut Vec<ReadOnlyRegister>) {
+            match f {
+                Formula::Sensor(r) => out.push(*r),
+                Formula::Not(f) => collect(f, out),
+                Formula::And(a, b) | Formula::Or(a, b) => {
+                    collect(a, out);
+                    collect(b, out);
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        collect(self, &mut out);
+        out.sort();
+        out.dedup();
+        out
+    }
+}
+
+impl std::str::FromStr for Formula {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::line(s, parse_or)
+    }
+}
+
+fn parse_or(c: &mut Cursor) -> Result<Formula, ParseError> {
+    let mut left = parse_and(c)?;
+    loop {
+        c.skip_whitespace();
+        if !c.eat("|") {
+            return Ok(left);
+        }
+        left = Formula::Or(Box::new(left), Box::new(parse_and(c)?));
+    }
+}
+
+fn parse_and(c: &mut Cursor) -> Result<Formula, ParseError> {
+    let mut left = parse_unary(c)?;
+    loop {
+        c.skip_whitespace();
+        if !c.eat("&") {
+            return Ok(left);
+        }
+        left = Formula::And(Box::new(left), Box::new(parse_unary(c)?));
+    }
+}
+
+fn parse_unary(c: &mut Cursor) -> Result<Formula, ParseError> {
+    use ReadOnlyRegister::*;
+
+    c.skip_whitespace();
+    if c.eat("!") {
+        Ok(Formula::Not(Box::new(parse_unary(c)?)))
+    } else if c.eat("(") {
+        let inner = parse_or(c)?;
+        c.skip_whitespace();
+        c.literal(")")?;
+        Ok(inner)
+    } else {
+        let sensors = [("A", A), ("B", B), ("C", C), ("D", D), ("E", E), ("F", F), ("G", G), ("H", H), ("I", I)];
+        c.one_of(&sensors)
+            .map(Formula::Sensor)
+            .map_err(|_| c.error("a sensor from `A` to `I`, `!` or `(`"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompileError {
+    /// The droid cannot read the sensor in the mode.
+    UnreadableSensor(ReadOnlyRegister),
+    /// No script of at most 15 instructions was found for the formula.
+    NotFound,
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompileError::UnreadableSensor(r) => write!(fmt, "sensor {} cannot be read in this mode", r),
+            CompileError::NotFound => write!(fmt, "no script of at most 15 instructions was found"),
+        }
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+/// Script compiled from a formula.
+#[derive(Debug, Clone)]
+pub struct Compiled<T> {
+    pub script: SpringScript<T>,
+    /// True when the search went through all of the shorter scripts, so that there is none.
+    pub minimal: bool,
+}
+
+/// Compiles the formula into a springscript which leaves its value in the jump register, using
+/// only it and the temporary register.
+///
+/// The script is generated from the shape of the formula, choosing the cheapest of the equivalent
+/// forms with the negations moved around and the operands in different orders. For the formulas
+/// reading at most six sensors a breadth first search over the truth tables of the registers then
+/// looks for a shorter script. The search has a budget, and only when it runs through all of the
+/// shorter scripts within it is the script [minimal](Compiled::minimal), which is the case for
+/// the formulas of a few sensors but not for [`RUN`].
+pub fn compile<T: Mode + Clone>(formula: &Formula) -> Result<Compiled<T>, CompileError> {
+    let sensors = formula.sensors();
+
+    if let Some(r) = sensors.iter().find(|r| r.distance() > T::sensors()) {
+        return Err(CompileError::UnreadableSensor(*r));
+    }
+
+    let generated = generate(formula, ReadWriteRegister::Jump, true, [true, true])
+        .map(|(ops, _)| ops)
+        .filter(|ops| ops.len() <= 15);
+
+    let limit = generated.as_ref().map(|ops| ops.len() - 1).unwrap_or(15);
+
+    let (ops, minimal) = if sensors.len() <= 6 {
+        match shortest(formula, &sensors, limit) {
+            Shortest::Found(ops) => (Some(ops), true),
+            Shortest::NoneShorter => (generated, true),
+            Shortest::OutOfBudget => (generated, false),
+        }
+    } else {
+        (generated, false)
+    };
+
+    let ops = ops.ok_or(CompileError::NotFound)?;
+
+    assert!(computes(&ops, formula, &sensors), "compiled script does not compute {:?}", formula);
+
+    let script = SpringScript(ops.into_iter().map(|(instr, src, dst)| Op(instr, src, dst, PhantomData)).collect());
+    Ok(Compiled { script, minimal })
+}
+
+/// Instruction of a script without the mode.
+type Code = (Instruction, ReadableRegister, ReadWriteRegister);
+
+fn other(r: ReadWriteRegister) -> ReadWriteRegister {
+    match r {
+        ReadWriteRegister::Temporary => ReadWriteRegister::Jump,
+        ReadWriteRegister::Jump => ReadWriteRegister::Temporary,
+    }
+}
+
+fn index(r: ReadWriteRegister) -> usize {
+    match r {
+        ReadWriteRegister::Temporary => 0,
+        ReadWriteRegister::Jump => 1,
+    }
+}
+
+/// Cheapest code leaving the formula in `r`, using the other register only with `scratch`.
+/// `fresh` tells which of the registers still hold their initial false, and the one after the
+/// code is returned with it.
+fn generate(f: &Formula, r: ReadWriteRegister, scratch: bool, fresh: [bool; 2]) -> Option<(Vec<Code>, [bool; 2])> {
+    use Formula::*;
+    use Instruction::{And as AndI, Not as NotI, Or as OrI};
+    use ReadableRegister::{RO, RW};
+
+    let mut after = fresh;
+    after[index(r)] = false;
+
+    match f {
+        Sensor(s) if fresh[index(r)] => Some((vec![(OrI, RO(*s), r)], after)),
+        Sensor(s) => Some((vec![(NotI, RO(*s), r), (NotI, RW(r), r)], after)),
+        Not(inner) => match &**inner {
+            Sensor(s) => Some((vec![(NotI, RO(*s), r)], after)),
+            Not(g) => generate(g, r, scratch, fresh),
+            And(a, b) | Or(a, b) => {
+                let negated = generate(inner, r, scratch, fresh).map(|(mut ops, fresh)| {
+                    ops.push((NotI, RW(r), r));
+                    (ops, fresh)
+                });
+
+                // pushing the negation to the operands
+                let (a, b) = (Box::new(Not(a.clone())), Box::new(Not(b.clone())));
+                let flipped = match &**inner {
+                    And(..) => Or(a, b),
+                    _ => And(a, b),
+                };
+
+                cheapest(negated, generate(&flipped, r, scratch, fresh))
+            }
+        },
+        And(..) | Or(..) => {
+            let (instr, operands) = match f {
+                And(..) => (AndI, f.operands()),
+                _ => (OrI, f.operands()),
+            };
+
+            let mut best = None;
+
+            for head in 0..operands.len() {
+                let candidate = generate(operands[head], r, scratch, fresh).and_then(|(mut ops, mut fresh)| {
+                    for (_, operand) in operands.iter().enumerate().filter(|&(i, _)| i != head) {
+                        match operand {
+                            Sensor(s) => ops.push((instr, RO(*s), r)),
+                            _ if scratch => {
+                                let (more, after) = generate(operand, other(r), false, fresh)?;
+                                ops.extend(more);
+                                ops.push((instr, RW(other(r)), r));
+                                fresh = after;
+                            }
+                            _ => return None,
+                        }
+                    }
+                    Some((ops, fresh))
+                });
+
+                best = cheapest(best, candidate);
+            }
+
+            best
+        }
+    }
+}
+
+fn cheapest(a: Option<(Vec<Code>, [bool; 2])>, b: Option<(Vec<Code>, [bool; 2])>) -> Option<(Vec<Code>, [bool; 2])> {
+    match (a, b) {
+        (Some(a), Some(b)) if b.0.len() < a.0.len() => Some(b),
+        (Some(a), _) => Some(a),
+        (None, b) => b,
+    }
+}
+
+/// How the search for a shorter script ended.
+enum Shortest {
+    /// The shortest script, as the search goes through the shorter ones first.
+    Found(Vec<Code>),
+    /// There is no script within the limit.
+    NoneShorter,
+    /// Some of the scripts within the limit were left unexplored.
+    OutOfBudget,
+}
+
+/// Searches for the shortest script of at most `limit` instructions leaving the formula in the
+/// jump register.
+fn shortest(formula: &Formula, sensors: &[ReadOnlyRegister], limit: usize) -> Shortest {
+    use std::cell::Cell;
+
+    // truth tables over the readings of the sensors, a bit for every combination of them
+    let rows = 1 << sensors.len();
+    let mask = if rows == 64 { !0 } else { (1u64 << rows) - 1 };
+    let table = |f: &dyn Fn(usize) -> bool| (0..rows).filter(|&row| f(row)).fold(0u64, |t, row| t | 1 << row);
+    let reading = |row: usize, r: ReadOnlyRegister| row & (1 << sensors.iter().position(|s| *s == r).unwrap()) != 0;
+
+    let target = table(&|row| formula.eval(|r| reading(row, r)));
+
+    let readable = [ReadableRegister::RW(ReadWriteRegister::Temporary), ReadableRegister::RW(ReadWriteRegister::Jump)]
+        .iter()
+        .copied()
+        .chain(sensors.iter().map(|&r| ReadableRegister::RO(r)))
+        .map(|reg| match reg {
+            ReadableRegister::RO(r) => (reg, table(&|row| reading(row, r))),
+            _ => (reg, 0),
+        })
+        .collect::<Vec<_>>();
+
+    let budget = Cell::new(10_000usize);
+    let exhausted = Cell::new(false);
+
+    // the states are the tables of the temporary and jump registers, with the instruction which
+    // got there kept along for building the script
+    let mut search = Search::visited_by(|&(t, j, len, _): &(u64, u64, usize, Option<Code>)| {
+        let mut next = Vec::new();
+
+        if len == limit {
+            return next;
+        }
+
+        if budget.get() == 0 {
+            exhausted.set(true);
+            return next;
+        }
+
+        for &instr in &[Instruction::And, Instruction::Or, Instruction::Not] {
+            for &(reg, sensor) in &readable {
+                let left = match reg {
+                    ReadableRegister::RW(ReadWriteRegister::Temporary) => t,
+                    ReadableRegister::RW(ReadWriteRegister::Jump) => j,
+                    ReadableRegister::RO(_) => sensor,
+                };
+
+                let apply = |right: u64| match instr {
+                    Instruction::And => left & right,
+                    Instruction::Or => left | right,
+                    Instruction::Not => !left & mask,
+                };
+
+                next.push((apply(t), j, len + 1, Some((instr, reg, ReadWriteRegister::Temporary))));
+                next.push((t, apply(j), len + 1, Some((instr, reg, ReadWriteRegister::Jump))));
+            }
+        }
+
+        budget.set(budget.get().saturating_sub(1));
+        next
+    }, |&(t, j, _, _): &(u64, u64, usize, Option<Code>)| (t, j));
+
+    match search.bfs((0, 0, 0, None), |&(_, j, _, _)| j == target) {
+        Some(found) => Shortest::Found(found.path.into_iter().filter_map(|(_, _, _, op)| op).collect()),
+        None if exhausted.get() => Shortest::OutOfBudget,
+        None => Shortest::NoneShorter,
+    }
+}
+
+/// Checks the script against the formula with every combination of the sensor readings.
+fn computes(ops: &[Code], formula: &Formula, sensors: &[ReadOnlyRegister]) -> bool {
+    (0..1usize << sensors.len()).all(|row| {
+        let reading = |r: ReadOnlyRegister| row & (1 << sensors.iter().position(|s| *s == r).unwrap()) != 0;
+        let mut registers = [false, false];
+
+        for &(instr, src, dst) in ops {
+            let left = match src {
+                ReadableRegister::RW(r) => registers[index(r)],
+                ReadableRegister::RO(r) => reading(r),
+            };
+
+            let right = registers[index(dst)];
+
+            registers[index(dst)] = match instr {
+                Instruction::And => left & right,
+                Instruction::Or => left | right,
+                Instruction::Not => !left,
+            };
+        }
+
+        registers[index(ReadWriteRegister::Jump)] == formula.eval(reading)
+    })
+}
+
+/// The first of the testcases the droid falls on with the script.
+pub fn failing_testcase<'a, T: Mode>(ops: &[Op<T>], testcases: &'a [Vec<bool>]) -> Option<&'a [bool]> {
+    testcases.iter()
+        .find(|ground| simulate(ops, ground) != Outcome::Survived)
+        .map(Vec::as_slice)
+}
+
+/// Whether the droid jumps with the script when the sensors read the given ground.
+pub fn jumps<T, F: Fn(ReadOnlyRegister) -> bool>(ops: &[Op<T>], sensors: F) -> bool {
+    let mut j = false;
+    let mut t = false;
+
+    for Op(instr, left, right, _) in ops.iter() {
+        let left = match left {
+            ReadableRegister::RW(ReadWriteRegister::Jump) => j,
+            ReadableRegister::RW(ReadWriteRegister::Temporary) => t,
+            ReadableRegister::RO(r) => sensors(*r),
+        };
+
+        let target = match right {
+            ReadWriteRegister::Jump => &mut j,
+            ReadWriteRegister::Temporary => &mut t,
+        };
+
+        let right = *target;
+
+        *target = match instr {
+            Instruction::And => left & right,
+            Instruction::Or => left | right,
+            Instruction::Not => !left,
+        };
+    }
+
+    j
+}
+
+/// How the droid did on the hull.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Made it past the end of the hull.
+    Survived,
+    /// Fell into the hole at `at`, after walking or jumping from `from`.
+    Fell { at: usize, from: usize },
+}
+
+/// Walks or runs the droid over the ground, where `false` is a hole, like the droid program does
+/// but without it. The droid starts at the first tile, jumps land four tiles ahead, and the
+/// sensors see ground past the end of the hull. The mode only limits the sensors read, which the
+/// instructions of the mode already do.
+pub fn simulate<T: Mode>(ops: &[Op<T>], ground: &[bool]) -> Outcome {
+    let mut x = 0;
+
+    while x < ground.len() {
+        let sensors = |r: ReadOnlyRegister| {
+            debug_assert!(r.distance() <= T::sensors(), "sensor {} cannot be read in {}", r, T::command());
+            ground.get(x + r.distance()).copied().unwrap_or(true)
+        };
+
+        let next = if jumps(ops, sensors) { x + 4 } else { x + 1 };
+
+        if !ground.get(next).copied().unwrap_or(true) {
+            return Outcome::Fell { at: next, from: x };
+        }
+
+        x = next;
+    }
+
+    Outcome::Survived
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum ReadWriteRegister {
+    Temporary,
+    Jump,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum ReadOnlyRegister {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+}
+
+impl ReadOnlyRegister {
+    fn distance(&self) -> usize {
+        match *self {
+            Self::A => 1,
+            Self::B => 2,
+            Self::C => 3,
+            Self::D => 4,
+            Self::E => 5,
+            Self::F => 6,
+            Self::G => 7,
+            Self::H => 8,
+            Self::I => 9,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum ReadableRegister {
+    RW(ReadWriteRegister),
+    RO(ReadOnlyRegister),
+}
+
+impl Mode for Part1 {
+    fn command() -> &'static str {
+        "WALK"
+    }
+
+    fn sensors() -> usize {
+        4
+    }
+}
+
+impl Mode for Part2 {
+    fn command() -> &'static str {
+        "RUN"
+    }
+
+    fn sensors() -> usize {
+        9
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpringScript<P>(Vec<Op<P>>);
+
+impl<P> SpringScript<P>
+    where Standard: Distribution<Op<P>>
+{
+    /// Changes a single instruction or the length of the script.
+    pub fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        if rng.gen_bool(0.5) {
+            self.mutate_one(rng);
+        } else {
+            self.mutate_size(rng);
+        }
+    }
+
+    fn mutate_one<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let target = rng.gen_range(0, self.len());
+        let next = rng.gen();
+
+        if rng.gen_bool(0.33) {
+            self[target].0 = next.0;
+        } else if rng.gen_bool(0.5) {
+            self[target].1 = next.1;
+        } else {
+            self[target].2 = next.2;
+        }
+    }
+
+    fn mutate_size<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        if self.len() > 1 && self.len() < 15 {
+            if rng.gen_bool(0.5) {
+                self.insert_random(rng);
+            } else {
+                self.pop_random(rng);
+            }
+        } else if self.len() == 15 {
+            self.pop_random(rng);
+        } else {
+            self.insert_random(rng);
+        }
+    }
+
+    fn insert_random<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let pos = rng.gen_range(0, self.len());
+        self.insert(pos, rng.gen());
+    }
+
+    fn pop_random<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let pos = rng.gen_range(0, self.len());
+        self.remove(pos);
+    }
+}
+
+impl<P> From<Vec<Op<P>>> for SpringScript<P> {
+    fn from(v: Vec<Op<P>>) -> Self {
+        SpringScript(v)
+    }
+}
+
+impl<P> std::ops::Deref for SpringScript<P> {
+    type Target = Vec<Op<P>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<P> std::ops::DerefMut for SpringScript<P> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<P> fmt::Display for SpringScript<P> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for op in &self.0 {
+            write!(fmt, "{}\n", op)?;
+        }
+        Ok(())
+    }
+}
+
+impl<P> Distribution<SpringScript<P>> for Standard
+    where Standard: Distribution<Op<P>>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> SpringScript<P> {
+        let len = 1 + rng.gen_range(0, 15);
+
+        let mut v = Vec::with_capacity(len);
+
+        for _ in 0..len {
+            v.push(rng.gen());
+        }
+
+        SpringScript(v)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Op<P>(Instruction, ReadableRegister, ReadWriteRegister, PhantomData<P>);
+
+use rand::Rng;
+use rand::distributions::{Distribution, Standard};
+
+impl Distribution<Op<Part1>> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Op<Part1> {
+        let seed = rng.gen_range(0u8, 3*6*2);
+
+        Op::try_from(seed).unwrap()
+    }
+}
+
+impl Distribution<Op<Part2>> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Op<Part2> {
+        let seed = rng.gen_range(0u8, 3*11*2);
+
+        Op::try_from(seed).unwrap()
+    }
+}
+
+use std::convert::TryFrom;
+
+impl TryFrom<u8> for Op<Part1> {
+    type Error = u8;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        if val >= 3*6*2 {
+            Err(val)
+        } else {
+            let instr = val % 3;
+            let val = val / 3;
+            let ro = val % 6;
+            let val = val / 6;
+            let rw = val % 2;
+
+            let instr = Instruction::try_from(instr).unwrap();
+            let ro = ReadableRegister::try_from(ro).unwrap();
+
+            let rw = match rw {
+                0 => ReadWriteRegister::Temporary,
+                1 => ReadWriteRegister::Jump,
+                x => unreachable!("out of range for rw: {}", x),
+            };
+
+            Ok(Op(instr, ro, rw, PhantomData))
+        }
+    }
+}
+
+impl TryFrom<u8> for Op<Part2> {
+    type Error = u8;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        // so this is 66 ... 15 * 66 < u128...
+        if val >= 3 * 11 * 2 {
+            Err(val)
+        } else {
+            let instr = val % 3;
+            let val = val / 3;
+            let ro = val % 11;
+            let val = val / 11;
+            let rw = val % 2;
+
+            let instr = Instruction::try_from(instr).unwrap();
+            let ro = ReadableRegister::try_from(ro).unwrap();
+
+            let rw = match rw {
+                0 => ReadWriteRegister::Temporary,
+                1 => ReadWriteRegister::Jump,
+                x => unreachable!("out of range for rw: {}", x),
+            };
+
+            Ok(Op(instr, ro, rw, PhantomData))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Instruction {
+    And,
+    Or,
+    Not,
+}
+
+impl TryFrom<u8> for Instruction {
+    type Error = u8;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        Ok(match val {
+            0 => Self::And,
+            1 => Self::Or,
+            2 => Self::Not,
+            x => return Err(x),
+        })
+    }
+}
+
+impl fmt::Display for ReadWriteRegister {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use ReadWriteRegister::*;
+        let s = match *self {
+            Temporary => "T",
+            Jump => "J",
+        };
+
+        write!(fmt, "{}", s)
+    }
+}
+
+impl fmt::Display for ReadOnlyRegister {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use ReadOnlyRegister::*;
+        let s = match *self {
+            A => "A",
+            B => "B",
+            C => "C",
+            D => "D",
+            E => "E",
+            F => "F",
+            G => "G",
+            H => "H",
+            I => "I",
+        };
+
+        write!(fmt, "{}", s)
+    }
+}
+
+impl TryFrom<u8> for ReadOnlyRegister {
+    type Error = u8;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        Ok(match val {
+            0 => Self::A,
+            1 => Self::B,
+            2 => Self::C,
+            3 => Self::D,
+            4 => Self::E,
+            5 => Self::F,
+            6 => Self::G,
+            7 => Self::H,
+            8 => Self::I,
+            x => return Err(x),
+        })
+    }
+}
+
+impl fmt::Display for ReadableRegister {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use ReadableRegister::*;
+        match *self {
+            RW(ref d) => write!(fmt, "{}", d),
+            RO(ref d) => write!(fmt, "{}", d),
+        }
+    }
+}
+
+impl TryFrom<u8> for ReadableRegister {
+    type Error = u8;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        Ok(match val {
+            0 => Self::RW(ReadWriteRegister::Temporary),
+            1 => Self::RW(ReadWriteRegister::Jump),
+            x => Self::RO(ReadOnlyRegister::try_from(x - 2)?),
+        })
+    }
+}
+
+impl<T> fmt::Display for Op<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{} {} {}", self.0, self.1, self.2)
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use Instruction::*;
+        let s = match *self {
+            And => "AND",
+            Or => "OR",
+            Not => "NOT",
+        };
+
+        write!(fmt, "{}", s)
+    }
+}
+
+#[cfg(test)]
+fn hulls(hulls: &[&str]) -> Vec<Vec<bool>> {
+    hulls.iter().map(|hull| hull.chars().map(|ch| ch == '#').collect()).collect()
+}
+
+#[test]
+fn parse_formulas() {
+    use ReadOnlyRegister::*;
+
+    let sensor = |r| Box::new(Formula::Sensor(r));
+
+    // and binds tighter than or
+    assert_eq!("!A | B & C".parse::<Formula>().unwrap(), Formula::Or(
+        Box::new(Formula::Not(sensor(A))),
+        Box::new(Formula::And(sensor(B), sensor(C)))));
+
+    let f = RUN.parse::<Formula>().unwrap();
+    assert_eq!(f.sensors(), vec![A, B, C, D, E, H]);
+    assert!(f.eval(|r| r != A && r != H));
+    assert!(!f.eval(|r| r != A && r != E && r != H));
+
+    let e = "!(A & J)".parse::<Formula>().unwrap_err();
+    assert_eq!((e.line, e.column), (1, 7));
+    assert_eq!(e.expected, "a sensor from `A` to `I`, `!` or `(`");
+
+    assert!("(A | B".parse::<Formula>().is_err());
+}
+
+#[test]
+fn compiles_short_scripts() {
+    let script = |f: &str| compile::<Part2>(&f.parse().unwrap()).unwrap().script.to_string();
+
+    assert_eq!(script("A"), "OR A J\n");
+    assert_eq!(script("!A"), "NOT A J\n");
+    // no two instructions compute it, which the search goes through
+    assert_eq!(compile::<Part2>(&"!A & !B".parse().unwrap()).unwrap().script.len(), 3);
+
+    assert_eq!(script(WALK), "OR A J\nAND B J\nAND C J\nNOT J J\nAND D J\n");
+    assert_eq!(script(RUN), "OR A J\nAND B J\nAND C J\nNOT J J\nAND D J\nOR E T\nOR H T\nAND T J\n");
+
+    let minimal = |f: &str| compile::<Part2>(&f.parse().unwrap()).unwrap().minimal;
+    assert!(minimal("!A & !B"));
+    assert!(minimal(WALK));
+    // the search runs out of its budget before going through the scripts of seven instructions
+    assert!(!minimal(RUN));
+
+    assert_eq!(compile::<Part1>(&RUN.parse().unwrap()).unwrap_err(), CompileError::UnreadableSensor(ReadOnlyRegister::E));
+}
+
+#[test]
+fn compiled_scripts_against_recorded_testcases() {
+    // the ground the droid fell on with the first tries
+    let walking = hulls(&["#####.#..########"]);
+    let running = hulls(&["#####.#..########", "#####.#.##..#####", "#####...##...####"]);
+
+    let walk = compile::<Part1>(&WALK.parse().unwrap()).unwrap().script;
+    assert_eq!(failing_testcase(&walk, &walking), None);
+
+    let naive = compile::<Part1>(&"D".parse().unwrap()).unwrap().script;
+    assert_eq!(failing_testcase(&naive, &walking), Some(&walking[0][..]));
+
+    let run = compile::<Part2>(&RUN.parse().unwrap()).unwrap().script;
+    assert_eq!(failing_testcase(&run, &running), None);
+
+    let walk_when_running = compile::<Part2>(&WALK.parse().unwrap()).unwrap().script;
+    assert_eq!(failing_testcase(&walk_when_running, &running), Some(&running[1][..]));
+}
+
+#[test]
+fn simulated_falls() {
+    use ReadOnlyRegister::*;
+
+    let hull = &hulls(&["#####.#..########"])[0];
+
+    let naive = compile::<Part1>(&"D".parse().unwrap()).unwrap().script;
+    assert_eq!(simulate(&naive, hull), Outcome::Fell { at: 5, from: 4 });
+
+    let never = compile::<Part1>(&"A & !A".parse().unwrap()).unwrap().script;
+    assert_eq!(simulate(&never, hull), Outcome::Fell { at: 5, from: 4 });
+
+    let walk = compile::<Part1>(&WALK.parse().unwrap()).unwrap().script;
+    assert_eq!(simulate(&walk, hull), Outcome::Survived);
+    assert!(jumps(&walk, |r| r != C));
+    assert!(!jumps(&walk, |r| r != D));
+
+    let walk_when_running = compile::<Part2>(&WALK.parse().unwrap()).unwrap().script;
+    let hull = &hulls(&["#####.#.##..#####"])[0];
+    assert_eq!(simulate(&walk_when_running, hull), Outcome::Fell { at: 7, from: 6 });
+}
+
+#[cfg(test)]
+fn full_input() -> Vec<Word> {
+    use aoc_common::input::{InputStore, DEFAULT_SET};
+    let input = InputStore::from_env().read(2019, 21, DEFAULT_SET).unwrap_or_else(|e| panic!("{}", e));
+    parse_program_n_lines(input.as_bytes(), Some(1)).unwrap()
+}
+
+#[test]
+fn random_stage_is_seeded() {
+    let data = full_input();
+
+    let run = |seed| {
+        let mut last = None;
+        let answer = random_stage::<Part1, _>(&data, seed, |p: &aoc_common::evolve::Progress| last = Some(p.generation));
+        (answer, last.unwrap())
+    };
+
+    let first = run(0);
+    assert_eq!(first.0, 19350258);
+    assert_eq!(run(0), first);
+}
@@ -0,0 +1,318 @@
+use std::str::FromStr;
+use aoc_common::math::Affine;
+use aoc_common::parse::{self, Cursor, ParseError};
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Vec<Op>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_ops(input)?)
+    }
+
+    fn part_one(ops: &Self::Input) -> Result<i64, Error> {
+        Ok(stage1(ops))
+    }
+
+    fn part_two(ops: &Self::Input) -> Result<i64, Error> {
+        Ok(stage2(ops))
+    }
+}
+
+pub fn parse_ops(input: &str) -> Result<Vec<Op>, ParseError> {
+    parse::lines(input, parse_op)
+}
+
+pub fn stage1(ops: &[Op]) -> i64 {
+    if true {
+        let m = 10_007;
+        ops.iter().fold(Affine::identity(m), |acc, next| acc.then(&next.as_affine(m))).apply(2019) as i64
+    } else if true {
+        let mut tracker = CardInDeckTracker::new(10_007, 2019);
+
+        for op in ops {
+            op.exec(&mut tracker);
+        }
+
+        tracker.pos
+    } else {
+
+        let mut deck = (0i16..10_007).into_iter().collect::<Vec<_>>();
+        let mut target = deck.clone();
+
+        for op in ops {
+            op.shuffle(&mut deck, &mut target);
+            std::mem::swap(&mut deck, &mut target);
+        }
+
+        deck.iter().position(|&card| card == 2019).unwrap() as i64
+    }
+}
+
+pub fn stage2(ops: &[Op]) -> i64 {
+    let m = 119315717514047;
+    let card = 2020;
+    let times = 101741582076661;
+
+    let shuffle = ops.iter().fold(Affine::identity(m), |acc, next| acc.then(&next.as_affine(m)));
+
+    // the card which ends up in the position is found by undoing all of the shuffles. well this
+    // was horrible: I couldn't had done this without reddit answers, I was all out of math for
+    // this one.
+    let inverse = shuffle.pow(times).inverse().expect("the deck size is prime");
+    inverse.apply(card) as i64
+}
+
+trait CardDeck {
+    fn deal_with_increment(&mut self, increment: i64);
+    fn cut(&mut self, cut: i64);
+    fn deal_into_new_stack(&mut self);
+}
+
+#[cfg(test)]
+struct FullCardDeckTracker {
+    deck: Vec<CardInDeckTracker>,
+}
+
+#[cfg(test)]
+impl FullCardDeckTracker {
+    fn new(cards: u64) -> Self {
+        FullCardDeckTracker {
+            deck: (0..cards).into_iter().map(|i| CardInDeckTracker::new(cards, i)).collect(),
+        }
+    }
+
+    fn cards(&self) -> Vec<u64> {
+        let mut ret = vec![0; self.deck.len()];
+
+        for tracker in &self.deck {
+            ret[tracker.pos as usize] = tracker.tracked;
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+impl CardDeck for FullCardDeckTracker {
+    fn deal_with_increment(&mut self, increment: i64) {
+        self.deck.iter_mut().for_each(|v| v.deal_with_increment(increment));
+    }
+    fn cut(&mut self, cut: i64) {
+        self.deck.iter_mut().for_each(|v| v.cut(cut));
+    }
+    fn deal_into_new_stack(&mut self) {
+        self.deck.iter_mut().for_each(|v| v.deal_into_new_stack());
+    }
+}
+
+struct CardInDeckTracker {
+    len: i64,
+    #[cfg(test)]
+    tracked: u64,
+    pos: i64,
+}
+
+impl CardInDeckTracker {
+    // created in factory order of 0..cards
+    fn new(cards: u64, tracked: u64) -> Self {
+        assert!(cards > tracked);
+        Self {
+            len: cards as i64,
+            #[cfg(test)]
+            tracked,
+            pos: tracked as i64,
+        }
+    }
+}
+
+impl CardDeck for CardInDeckTracker {
+    fn deal_with_increment(&mut self, increment: i64) {
+        use num::integer::mod_floor;
+        assert!(increment > 0);
+        self.pos = mod_floor(self.pos * increment, self.len);
+    }
+
+    fn cut(&mut self, cut: i64) {
+        use num::integer::mod_floor;
+        self.pos = mod_floor(self.pos - cut, self.len);
+    }
+
+    fn deal_into_new_stack(&mut self) {
+        use num::integer::mod_floor;
+        self.pos = mod_floor(-1 * self.pos - 1, self.len);
+    }
+}
+
+pub enum Op {
+    DealWithIncrement(i16),
+    Cut(i16),
+    DealIntoNewStack,
+}
+
+impl FromStr for Op {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::line(s, parse_op)
+    }
+}
+
+fn parse_op(c: &mut Cursor) -> Result<Op, ParseError> {
+    if c.eat("deal into new stack") {
+        Ok(Op::DealIntoNewStack)
+    } else if c.eat("deal with increment ") {
+        Ok(Op::DealWithIncrement(c.number()?))
+    } else if c.eat("cut ") {
+        Ok(Op::Cut(c.number()?))
+    } else {
+        Err(c.error("one of `deal into new stack`, `deal with increment` or `cut`"))
+    }
+}
+
+impl Op {
+
+    /// The position where the card at a position moves to in a deck of `m` cards.
+    fn as_affine(&self, m: u64) -> Affine {
+        match *self {
+            Op::DealIntoNewStack => Affine::new(-1, -1, m),
+            Op::DealWithIncrement(incr) => Affine::new(incr as i64, 0, m),
+            Op::Cut(cut) => Affine::new(1, -cut as i64, m),
+        }
+    }
+
+    fn exec<D: CardDeck>(&self, deck: &mut D) {
+        match *self {
+            Op::DealIntoNewStack => deck.deal_into_new_stack(),
+            Op::DealWithIncrement(incr) => deck.deal_with_increment(incr as i64),
+            Op::Cut(cut) => deck.cut(cut as i64),
+        }
+    }
+
+    fn shuffle(&self, deck: &mut Vec<i16>, target: &mut Vec<i16>) {
+        target.clear();
+        match *self {
+            Op::DealIntoNewStack => {
+                deck.reverse();
+                std::mem::swap(deck, target);
+            },
+            Op::DealWithIncrement(incr) => {
+                if incr <= 0 {
+                    unreachable!();
+                } else {
+                    target.clear();
+                    target.resize(deck.len(), 0);
+
+                    let mut puts = 0;
+                    let mut ptr = 0;
+
+                    while puts < deck.len() {
+                        target[ptr] = deck[puts];
+                        puts += 1;
+                        ptr = (ptr + incr as usize) % deck.len();
+                    }
+                }
+            },
+            Op::Cut(amt) => {
+                target.clear();
+                let amt = if amt < 0 {
+                    deck.len() - (-amt) as usize
+                } else if amt > 0 {
+                    amt as usize
+                } else {
+                    unreachable!()
+                };
+                target.extend(&deck[amt..]);
+                target.extend(&deck[..amt]);
+            }
+        }
+    }
+}
+
+#[test]
+fn reverse() {
+    let mut v = Vec::new();
+    v.push(0);
+    v.push(1);
+    v.push(2);
+
+    let mut target = Vec::new();
+    Op::DealIntoNewStack.shuffle(&mut v, &mut target);
+
+    assert_eq!(
+        target.iter().copied().collect::<Vec<_>>(),
+        vec![2, 1, 0]);
+
+    let mut multi = FullCardDeckTracker::new(3);
+    multi.deal_into_new_stack();
+
+    assert_eq!(
+        multi.cards(),
+        &[2, 1, 0]);
+
+    Op::DealIntoNewStack.shuffle(&mut target, &mut v);
+
+    multi.deal_into_new_stack();
+
+    assert_eq!(
+        v.iter().copied().collect::<Vec<_>>(),
+        &[0, 1, 2]);
+}
+
+#[test]
+fn cut() {
+    let mut v = (0i16..10).into_iter().collect::<Vec<i16>>();
+    let mut target = Vec::new();
+    Op::Cut(3).shuffle(&mut v, &mut target);
+
+    assert_eq!(
+        target.iter().copied().collect::<Vec<_>>(),
+        &[3, 4, 5, 6, 7, 8, 9, 0, 1, 2]);
+
+    let mut multi = FullCardDeckTracker::new(10);
+    multi.cut(3);
+
+    assert_eq!(
+        multi.cards(),
+        &[3, 4, 5, 6, 7, 8, 9, 0, 1, 2]);
+}
+
+#[test]
+fn cut_negative() {
+    let mut v = (0i16..10).into_iter().collect::<Vec<i16>>();
+    let mut target = Vec::new();
+    Op::Cut(-4).shuffle(&mut v, &mut target);
+
+    assert_eq!(
+        target.iter().copied().collect::<Vec<_>>(),
+        &[6, 7, 8, 9, 0, 1, 2, 3, 4, 5]);
+
+    let mut multi = FullCardDeckTracker::new(10);
+    multi.cut(-4);
+
+    assert_eq!(
+        multi.cards(),
+        &[6, 7, 8, 9, 0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn deal_with_incr_pos() {
+    let mut v = (0i16..10).into_iter().collect::<Vec<i16>>();
+    let mut target = Vec::new();
+    Op::DealWithIncrement(3).shuffle(&mut v, &mut target);
+
+    assert_eq!(
+        target.iter().copied().collect::<Vec<_>>(),
+        &[0, 7, 4, 1, 8, 5, 2, 9, 6, 3]);
+
+    let mut multi = FullCardDeckTracker::new(10);
+    multi.deal_with_increment(3);
+
+    assert_eq!(
+        multi.cards(),
+        &[0, 7, 4, 1, 8, 5, 2, 9, 6, 3]);
+}
@@ -0,0 +1,1032 @@
+use std::rc::Rc;
+use std::fmt;
+use std::fmt::Write;
+use std::convert::TryFrom;
+use std::collections::HashMap;
+use intcode::{Word, InvalidProgram, util::{GameDisplay, Position, Direction}, Program, Registers, ExecutionState};
+use intcode::util::parse_program_n_lines;
+use aoc_common::solution::{Error, Solution, Unsolved};
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    // there is no second part on the last day
+    type Input = Vec<Word>;
+    type PartOne = String;
+    type PartTwo = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_program_n_lines(input.as_bytes(), Some(1))?)
+    }
+
+    fn part_one(data: &Self::Input) -> Result<String, Error> {
+        Game::new(data).play().map_err(|e| format!("unexpected {:?}", e).into())
+    }
+
+    fn part_two(_: &Self::Input) -> Result<String, Error> {
+        Err(Unsolved.into())
+    }
+}
+
+#[derive(Debug)]
+pub enum GameFailure {
+    InvalidProgram(InvalidProgram),
+    InvalidCardinalDirection(String),
+    UnexpectedHalt(String),
+    InfiniteLoop(String),
+    Stuck(String),
+    CheckpointNotFound,
+    NoCombination,
+    /// More items were found than the subsets of them can be searched for.
+    TooManyItems(usize),
+    /// The droid walked off the pressure-sensitive floor to the room instead of being weighed.
+    NotEjected(String),
+}
+
+impl From<InvalidProgram> for GameFailure {
+    fn from(ip: InvalidProgram) -> Self {
+        Self::InvalidProgram(ip)
+    }
+}
+
+/// Why the pressure-sensitive floor ejected the droid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EjectionReason {
+    /// The other droids are heavier, the droid carries too little.
+    Light,
+    /// The other droids are lighter, the droid carries too much.
+    Heavy,
+}
+
+/// What happened on the pressure-sensitive floor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Weighing<T> {
+    Ejected(EjectionReason),
+    Passed(T),
+}
+
+/// Why an item cannot be carried, learned by taking it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Danger {
+    /// The game ended with the message.
+    Halts(String),
+    /// The game started repeating the line forever.
+    Loops(String),
+    /// The droid could not move anymore.
+    Stuck,
+}
+
+#[derive(Clone, Debug)]
+struct Ejection {
+    reason: EjectionReason,
+    ejected_from: Box<Room>,
+}
+
+#[derive(Debug, Clone)]
+struct Room {
+    name: Rc<String>,
+    desc: String,
+    doors: Vec<CardinalDirection>,
+    items: Vec<Rc<String>>,
+    ejection: Option<Ejection>,
+}
+
+impl Room {
+}
+
+const CHECKPOINT: &str = "In the next room, a pressure-sensitive floor will verify your identity.";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Tile {
+    VisitedRoom,
+    UnvisitedRoom,
+    Wall,
+    Space,
+    Drone,
+}
+
+impl Default for Tile {
+    fn default() -> Self { Tile::Space }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            Tile::VisitedRoom => '.',
+            Tile::UnvisitedRoom => '?',
+            Tile::Wall => '#',
+            Tile::Space => ' ',
+            Tile::Drone => 'd',
+        };
+
+        write!(fmt, "{}", s)
+    }
+}
+
+/// The rooms, doors and items found by exploring the ship.
+#[derive(Default)]
+pub struct Ship {
+    map: Map,
+    start: Option<Rc<String>>,
+    /// The items carried with the rooms they were found in.
+    items: Vec<(Rc<String>, Rc<String>)>,
+    dangerous: Vec<(Rc<String>, Danger)>,
+    /// The room before the pressure-sensitive floor and the door to the floor.
+    checkpoint: Option<(Rc<String>, CardinalDirection)>,
+}
+
+impl Ship {
+    /// Names of the rooms in the order they were found.
+    pub fn rooms(&self) -> impl Iterator<Item = &str> + '_ {
+        self.map.index_names.iter().map(|name| name.as_str())
+    }
+
+    /// The items safe to carry with the rooms they were found in.
+    pub fn items(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.items.iter().map(|(item, room)| (item.as_str(), room.as_str()))
+    }
+
+    /// The items left where they were with what happened when they were taken.
+    pub fn dangerous(&self) -> impl Iterator<Item = (&str, &Danger)> + '_ {
+        self.dangerous.iter().map(|(item, danger)| (item.as_str(), danger))
+    }
+
+    /// The room before the pressure-sensitive floor.
+    pub fn checkpoint(&self) -> Option<&str> {
+        self.checkpoint.as_ref().map(|(room, _)| room.as_str())
+    }
+}
+
+/// Searches the subsets of `count` items in Gray code order, so that mostly a single item is taken
+/// or dropped between the weighings. The subsets of a too light subset and the supersets of a too
+/// heavy one are skipped without weighing. Returns the first subset as a mask which passes, with
+/// what `weigh` returned for it.
+pub fn search_subsets<T, E, F>(count: usize, mut weigh: F) -> Result<Option<(u32, T)>, E>
+    where F: FnMut(u32) -> Result<Weighing<T>, E>
+{
+    assert!(count < 32, "too many items for the masks: {}", count);
+
+    let mut too_light: Vec<u32> = Vec::new();
+    let mut too_heavy: Vec<u32> = Vec::new();
+
+    for i in 0..1u32 << count {
+        let mask = i ^ (i >> 1);
+
+        if too_light.iter().any(|&light| mask & !light == 0) || too_heavy.iter().any(|&heavy| heavy & !mask == 0) {
+            continue;
+        }
+
+        match weigh(mask)? {
+            Weighing::Passed(x) => return Ok(Some((mask, x))),
+            Weighing::Ejected(EjectionReason::Light) => too_light.push(mask),
+            Weighing::Ejected(EjectionReason::Heavy) => too_heavy.push(mask),
+        }
+    }
+
+    Ok(None)
+}
+
+/// How the droid got past the pressure-sensitive floor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Passage {
+    /// Password for the main airlock.
+    pub password: String,
+    /// The items the droid carried over the floor.
+    pub items: Vec<String>,
+    /// Times the droid stepped on the floor.
+    pub weighings: usize,
+}
+
+/// State of the droid to return to after trying out something dangerous.
+struct Save {
+    prog: Program<'static>,
+    regs: Registers,
+}
+
+pub struct Game {
+    prog: Program<'static>,
+    regs: Option<Registers>,
+    pub read_buffer: String,
+    write_buffer: Option<String>,
+}
+
+impl Game {
+    pub fn new(data: &[Word]) -> Self {
+        Game {
+            prog: Program::from(data.to_vec()).with_memory_expansion(),
+            regs: Some(Default::default()),
+            read_buffer: String::new(),
+            write_buffer: Some(String::new()),
+        }
+    }
+
+    pub fn reset_from(&mut self, data: &[Word]) {
+        self.prog.reset_from(data);
+        self.regs = Some(Default::default());
+        self.read_buffer.clear();
+        if let Some(b) = self.write_buffer.as_mut() {
+            b.clear();
+        } else {
+            unreachable!("write buffer was lost?");
+        }
+    }
+
+    /// Explores the ship and passes the checkpoint, returning the password for the main airlock.
+    pub fn play(&mut self) -> Result<String, GameFailure> {
+        let ship = self.explore()?;
+        Ok(self.pass_checkpoint(&ship)?.password)
+    }
+
+    /// Walks through every door but the one to the pressure-sensitive floor, picking up the items
+    /// which can be carried, and comes back to the start.
+    pub fn explore(&mut self) -> Result<Ship, GameFailure> {
+        let mut ship = Ship::default();
+
+        let room = self.expect_room()?;
+        ship.map.learn(None, &room.name, &room.doors);
+        ship.start = Some(Rc::clone(&room.name));
+
+        self.explore_from(&room, None, &mut ship)?;
+
+        Ok(ship)
+    }
+
+    fn explore_from(&mut self, room: &Room, came_by: Option<CardinalDirection>, ship: &mut Ship) -> Result<(), GameFailure> {
+        let way_back = came_by.map(|dir| dir.reverse());
+
+        for item in &room.items {
+            match self.try_take(item, way_back.unwrap_or(room.doors[0]))? {
+                None => ship.items.push((Rc::clone(item), Rc::clone(&room.name))),
+                Some(danger) => ship.dangerous.push((Rc::clone(item), danger)),
+            }
+        }
+
+        let checkpoint = room.desc == CHECKPOINT;
+
+        for &dir in &room.doors {
+            if Some(dir) == way_back {
+                continue;
+            }
+
+            if checkpoint {
+                // the floor is stepped on only after exploring everything else
+                ship.checkpoint = Some((Rc::clone(&room.name), dir));
+                continue;
+            }
+
+            let next = self.move_to(dir)?;
+
+            // the rooms already found are through another door, only the door is learned
+            if ship.map.learn(Some((&room.name, dir)), &next.name, &next.doors) {
+                self.explore_from(&next, Some(dir), ship)?;
+            }
+
+            let back = self.move_to(dir.reverse())?;
+            assert_eq!(back.name, room.name);
+        }
+
+        Ok(())
+    }
+
+    /// Takes the item unless it is dangerous, which is found out by taking it and trying to move
+    /// through `door`, returning to the state before when it was.
+    fn try_take(&mut self, item: &str, door: CardinalDirection) -> Result<Option<Danger>, GameFailure> {
+        let save = self.save();
+
+        let danger = match self.take_item(item) {
+            Ok(()) => match self.move_to(door) {
+                Ok(_) => None,
+                Err(GameFailure::Stuck(_)) => Some(Danger::Stuck),
+                Err(e) => return Err(e),
+            },
+            Err(GameFailure::UnexpectedHalt(message)) => Some(Danger::Halts(message)),
+            Err(GameFailure::InfiniteLoop(line)) => Some(Danger::Loops(line)),
+            Err(e) => return Err(e),
+        };
+
+        self.restore(save);
+
+        if danger.is_none() {
+            self.take_item(item)?;
+        }
+
+        Ok(danger)
+    }
+
+    /// Goes to the checkpoint and tries the items found until the droid weighs the same as the
+    /// droids of the ship, returning the password given then.
+    pub fn pass_checkpoint(&mut self, ship: &Ship) -> Result<Passage, GameFailure> {
+        let (checkpoint, floor) = ship.checkpoint.clone().ok_or(GameFailure::CheckpointNotFound)?;
+        let start = ship.start.as_ref().expect("exploring starts from a room");
+
+        let items = ship.items.iter().map(|(item, _)| item.as_str()).collect::<Vec<_>>();
+
+        // the subsets are searched as the bits of a u32
+        if items.len() >= 32 {
+            return Err(GameFailure::TooManyItems(items.len()));
+        }
+
+        for dir in ship.map.path_to(start, &checkpoint) {
+            self.move_to(dir)?;
+        }
+
+        // all of the items were picked up while exploring
+        let mut held = (1u32 << items.len()) - 1;
+        let mut weighings = 0;
+
+        let found = search_subsets(items.len(), |mask| {
+            for (i, item) in items.iter().enumerate() {
+                let bit = 1 << i;
+                if mask & bit != 0 && held & bit == 0 {
+                    self.take_item(item)?;
+                } else if mask & bit == 0 && held & bit != 0 {
+                    self.drop_item(item)?;
+                }
+            }
+
+            held = mask;
+            weighings += 1;
+
+            self.weigh(floor)
+        })?;
+
+        let (mask, password) = found.ok_or(GameFailure::NoCombination)?;
+
+        let items = items.iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) != 0)
+            .map(|(_, item)| item.to_string())
+            .collect();
+
+        Ok(Passage { password, items, weighings })
+    }
+
+    /// Steps on the pressure-sensitive floor, which either ends the game with the password or
+    /// ejects the droid back to the checkpoint.
+    fn weigh(&mut self, floor: CardinalDirection) -> Result<Weighing<String>, GameFailure> {
+        self.eval_input_by(|s| writeln!(s, "{}", floor).unwrap())?;
+
+        match self.expect_room() {
+            Ok(Room { ejection: Some(ejection), .. }) => Ok(Weighing::Ejected(ejection.reason)),
+            Ok(room) => Err(GameFailure::NotEjected(room.name.to_string())),
+            Err(GameFailure::UnexpectedHalt(message)) => {
+                // "... You should be able to get in by typing 1234 on the keypad at the main airlock."
+                let password = message.split_whitespace()
+                    .skip_while(|&word| word != "typing")
+                    .nth(1)
+                    .map(String::from);
+
+                password.map(Weighing::Passed).ok_or(GameFailure::UnexpectedHalt(message))
+            },
+            Err(e) => Err(e),
+        }
+    }
+
+    fn move_to(&mut self, dir: CardinalDirection) -> Result<Room, GameFailure> {
+        self.eval_input_by(|s| writeln!(s, "{}", dir).unwrap())?;
+        self.expect_room()
+    }
+
+    fn save(&self) -> Save {
+        Save {
+            prog: self.prog.clone(),
+            regs: self.regs.clone().expect("registers are always returned"),
+        }
+    }
+
+    fn restore(&mut self, save: Save) {
+        self.prog = save.prog;
+        self.regs = Some(save.regs);
+        self.read_buffer.clear();
+    }
+
+    fn drop_item(&mut self, item: &str) -> Result<(), GameFailure> {
+        self.eval_input_by(|s| write!(s, "drop {}\n", item).unwrap())?;
+        self.read_until_prompt()?;
+
+        let buffer = self.read_buffer.trim();
+
+        let mut lines = buffer.lines();
+        let confirmation = lines.next().unwrap();
+
+        assert_eq!(confirmation.len(), "You drop the ".len() + item.len() + 1);
+        assert!(confirmation.starts_with("You drop the "));
+        assert!(confirmation.ends_with("."));
+
+        Ok(())
+    }
+
+    fn read_until_prompt(&mut self) -> Result<(), GameFailure> {
+        self.read_buffer.clear();
+        loop {
+            let was_nl = match self.eval_until_output()? {
+                MaybeAscii::Ascii(x) => {
+                    self.read_buffer.push(x as char);
+                    x == b'\n'
+                },
+                x => unreachable!("Unexpected {:?}", x),
+            };
+
+            if self.read_buffer.ends_with("Command?\n") {
+                return Ok(());
+            }
+
+            if was_nl {
+                // this is exponential but ... should get it quite fast
+                let mut all_lines = HashMap::new();
+
+                for s in self.read_buffer.lines().filter(|s| !s.is_empty()) {
+                    let count = all_lines.entry(s).or_insert(0);
+                    *count += 1;
+                    if *count > 2 {
+                        // need to allow two repetitions for ejection from security check
+                        return Err(GameFailure::InfiniteLoop(s.into()));
+                    }
+                }
+            }
+        }
+    }
+
+    fn expect_room(&mut self) -> Result<Room, GameFailure> {
+        self.read_until_prompt()?;
+
+        parse_room(&self.read_buffer)
+    }
+
+    fn take_item(&mut self, item: &str) -> Result<(), GameFailure> {
+        self.eval_input_by(|s| write!(s, "take {}\n", item).unwrap())?;
+
+        self.read_until_prompt()?;
+
+        let buffer = self.read_buffer.trim();
+
+        let mut lines = buffer.lines().filter(|s| !s.is_empty());
+
+        let confirmation = lines.next().unwrap();
+        if confirmation.len() != "You take the ".len() + item.len() + 1 {
+            panic!("{}", confirmation);
+        }
+
+        assert!(confirmation.starts_with("You take the "));
+        assert!(confirmation.ends_with("."));
+
+        assert_eq!(lines.next(), Some("Command?"));
+        Ok(())
+    }
+
+    fn eval_input_by<F: FnOnce(&mut String)>(&mut self, formatter: F) -> Result<(), GameFailure> {
+        let mut buffer = self.write_buffer.take().unwrap();
+        buffer.clear();
+
+        formatter(&mut buffer);
+
+        let res = self.eval_input(&buffer);
+
+        self.write_buffer = Some(buffer);
+
+        res
+    }
+
+    fn eval_until_output(&mut self) -> Result<MaybeAscii, GameFailure> {
+        loop {
+            let output;
+            self.regs = Some(match self.prog.eval_from_instruction(self.regs.take().unwrap())? {
+                ExecutionState::Paused(_regs) => unreachable!("Pausing not implemented yet?"),
+                ExecutionState::HaltedAt(_regs) => return Err(GameFailure::UnexpectedHalt(self.read_buffer.trim().replace("\n\n", " "))),
+                ExecutionState::InputIO(_io) => unreachable!("Input was unexpected"),
+                ExecutionState::OutputIO(io, value) => {
+                    output = Some(value);
+                    self.prog.handle_output_completion(io)
+                },
+            });
+
+            if let Some(value) = output {
+                return Ok(value.into());
+            }
+        }
+    }
+
+    fn eval_input(&mut self, input: &str) -> Result<(), GameFailure> {
+        let mut consumed = 0;
+        let mut chars = input.chars().peekable();
+        loop {
+            self.regs = Some(match self.prog.eval_from_instruction(self.regs.take().unwrap())? {
+                ExecutionState::Paused(_regs) => unreachable!("Pausing not implemented yet?"),
+                ExecutionState::HaltedAt(_regs) => return Err(GameFailure::UnexpectedHalt(self.read_buffer.trim().replace("\n\n", " "))),
+                ExecutionState::InputIO(io) => {
+                    consumed += 1;
+                    self.prog.handle_input_completion(io, chars.next().unwrap() as Word)?
+                },
+                ExecutionState::OutputIO(_io, value) => unreachable!("Output was unexpected: {} with {} chars consumed of {:?}", value, consumed, &input[consumed..]),
+            });
+
+            if !chars.peek().is_some() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn parse_room(buffer: &str) -> Result<Room, GameFailure> {
+    let buffer = buffer.trim();
+
+    let mut lines = buffer.lines().filter(|s| !s.is_empty()).peekable();
+
+    let name = lines.next().unwrap();
+
+    if name.ends_with(" You can't move!!") {
+        return Err(GameFailure::Stuck(String::from(&name[..name.len() - " You can't move!!".len() - 1])));
+    }
+
+    let name = name.split("==")
+        .skip(1)
+        .next()
+        .map(str::trim)
+        .unwrap_or_else(|| panic!("unexpected response?\n{}", buffer));
+
+    let desc = lines.next().unwrap();
+
+    match lines.next().unwrap() {
+        "Doors here lead:" => {},
+        x => panic!("expected doors, found {:?}", x),
+    }
+
+    let mut doors = Vec::new();
+
+    loop {
+        if !lines.peek().unwrap().starts_with("- ") {
+            break;
+        }
+
+        let next = lines.next().unwrap();
+
+        let start = next.char_indices().skip(2).next().unwrap().0;
+
+        let dir = CardinalDirection::try_from(&next[start..]).map_err(|_| GameFailure::InvalidCardinalDirection(String::from(next)))?;
+        doors.push(dir);
+    }
+
+    let mut items = Vec::new();
+
+    if lines.peek().map(|s| s.ends_with("you are ejected back to the checkpoint.")).unwrap() {
+        let reason_line = lines.next().unwrap();
+        // "Droids on this ship are heavier than the detected value!"
+        let reason = if reason_line.contains("heavier") {
+            EjectionReason::Light
+        } else {
+            assert!(reason_line.contains("lighter"), "unexpected reason: {:?}", reason_line);
+            EjectionReason::Heavy
+        };
+
+        let name = Rc::new(String::from(name));
+        let desc = String::from(desc);
+
+        let rest = &buffer[(buffer.find(reason_line).unwrap() + reason_line.len())..];
+
+        let mut final_room = parse_room(rest)?;
+        final_room.ejection = Some(Ejection {
+            reason,
+            ejected_from: Box::new(Room {
+                name,
+                desc,
+                doors,
+                items: Vec::new(),
+                ejection: None,
+            })
+        });
+
+        return Ok(final_room);
+    }
+
+    if lines.peek() != Some(&"Command?") {
+        let items_header = lines.next().unwrap();
+
+        assert_eq!(items_header, "Items here:");
+
+        loop {
+            if !lines.peek().unwrap().starts_with("- ") {
+                break;
+            }
+
+            let item = Rc::new(String::from(&lines.next().unwrap()[2..]));
+            items.push(item);
+        }
+    }
+
+    assert_eq!(lines.next(), Some("Command?"));
+
+    Ok(Room {
+        name: Rc::new(String::from(name)),
+        desc: String::from(desc),
+        doors,
+        items,
+        ejection: None,
+    })
+}
+
+#[derive(Default)]
+struct Map {
+    room_indices: HashMap<Rc<String>, usize>,
+    index_names: Vec<Rc<String>>,
+    doors: HashMap<usize, Vec<Door>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Door {
+    Visited(usize),
+    Unvisited,
+    Missing,
+}
+
+impl Door {
+    fn upgrade(&mut self, room: usize) -> bool {
+        match self {
+            Door::Unvisited => { *self = Door::Visited(room); true },
+            Door::Visited(x) if *x == room => { false },
+            Door::Visited(y) => panic!("Cannot change known door {} to {}", y, room),
+            Door::Missing => panic!("Cannot learn missing door as {}", room),
+        }
+    }
+
+    fn accessible(&mut self) {
+        match self {
+            Door::Missing => *self = Door::Unvisited,
+            _ => {}
+        }
+    }
+}
+
+impl Map {
+    fn learn(&mut self, came_from: Option<(&Rc<String>, CardinalDirection)>, room_name: &Rc<String>, doors: &[CardinalDirection]) -> bool {
+        let index = self.room_indices.get(room_name).copied();
+
+        let (new_room, index) = match index {
+            Some(x) => (false, x),
+            None => {
+                let index = self.room_indices.len();
+                self.room_indices.insert(Rc::clone(room_name), index);
+                self.index_names.push(Rc::clone(room_name));
+                (true, index)
+            }
+        };
+
+        let current_doors = &mut self.doors.entry(index).or_insert_with(|| vec![Door::Missing; 4]);
+
+        for dir in doors {
+            let slot = &mut current_doors[dir.as_index()];
+            slot.accessible();
+        }
+
+        if let Some((prev, came_from)) = came_from {
+            let prev_index = self.room_indices[prev];
+            {
+                let slot = &mut current_doors[came_from.reverse().as_index()];
+                if slot.upgrade(prev_index) {
+                    //println!("  learned from {:?} {} gets us to {:?}", prev.as_str(), came_from, room_name.as_str());
+                }
+            }
+            {
+                let doors = self.doors.get_mut(&prev_index).unwrap();
+                let slot = &mut doors[came_from.as_index()];
+                if slot.upgrade(index) {
+                    //println!("  learned from {:?} {} gets us to {:?}", room_name.as_str(), came_from.reverse(), prev.as_str());
+                }
+            }
+        }
+
+        new_room
+    }
+
+    fn path_to(&self, from: &Rc<String>, to: &Rc<String>) -> Vec<CardinalDirection> {
+        // from == to when exploring a room for the first time
+        if from == to {
+            vec![]
+        } else {
+            use std::collections::BinaryHeap;
+            use std::collections::hash_map::Entry;
+            use std::cmp;
+            // monkeyd from day15 again
+            let mut ret = Vec::new();
+            let mut dist: HashMap<usize, usize> = HashMap::new();
+            let mut prev: HashMap<usize, (usize, CardinalDirection)> = HashMap::new();
+
+            #[derive(PartialEq, Debug, Clone, Copy, Eq, Ord)]
+            struct Work {
+                steps_here: usize,
+                room_index: usize,
+                came_from: Option<CardinalDirection>,
+            }
+
+            impl PartialOrd for Work {
+                fn partial_cmp(&self, rhs: &Work) -> Option<cmp::Ordering> {
+                    Some(
+                        self.steps_here.cmp(&rhs.steps_here).reverse()
+                            .then_with(|| self.room_index.cmp(&rhs.room_index))
+                            .then_with(|| self.came_from.map(CardinalDirection::as_index).cmp(&rhs.came_from.map(CardinalDirection::as_index)))
+                    )
+                }
+            }
+
+            {
+                let mut work: BinaryHeap<Work> = BinaryHeap::new();
+                //println!("path_to: {} -> {}", from, to);
+
+                let from = self.to_index(from);
+                let to = self.to_index(to);
+
+                work.push(Work { steps_here: 0, room_index: from, came_from: None });
+
+                while let Some(Work { steps_here, room_index, came_from: _came_from }) = work.pop() {
+
+                    //println!("  path_to:  {:>3}, {:?} ({}), {:?}", steps_here, self.index_names[room_index].as_str(), room_index, came_from);
+
+                    match dist.entry(room_index) {
+                        Entry::Vacant(vcnt) => {
+                            vcnt.insert(steps_here);
+                        },
+                        Entry::Occupied(mut o) => {
+                            if *o.get() >= steps_here {
+                                *o.get_mut() = steps_here;
+                            } else {
+                                //println!("already visited {:?} with lower dist {} than {} from {:?}", room_index, o.get(), steps_here, prev[&room_index]);
+                                continue;
+                            }
+                        }
+                    }
+
+                    if room_index == to {
+                        let mut backwards = room_index;
+
+                        while backwards != from {
+                            let previous = prev.remove(&backwards).unwrap();
+                            ret.push(previous.1);
+                            backwards = previous.0;
+                        }
+                        ret.reverse();
+
+                        // could put the door we need to go to into work, but that requires another type
+                        // which does not compare on other elements...
+                        return ret;
+                    }
+
+                    let doors = self.doors[&room_index].iter().enumerate()
+                        .filter_map(|(i, door)| match door {
+                            Door::Visited(x) => Some((i, x)),
+                            _ => None,
+                        })
+                        .map(|(i, x)| (CardinalDirection::try_from(i).unwrap(), x));
+
+                    for (dir, &next_room) in doors {
+                        //println!("    path_to: {} => {:?} ({})", dir, self.index_names[next_room].as_str(), next_room);
+                        let steps_here = steps_here + 1;
+
+                        if steps_here < *dist.get(&next_room).unwrap_or(&usize::max_value()) {
+                            dist.insert(next_room, steps_here);
+                            prev.insert(next_room, (room_index, dir));
+                            work.push(Work { steps_here, room_index: next_room, came_from: Some(dir) });
+                        }
+                    }
+                }
+            }
+
+            unreachable!("did not find path from {}...{}", from, to);
+        }
+    }
+
+    fn to_index(&self, room: &Rc<String>) -> usize {
+        self.room_indices.get(room).copied().unwrap()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MaybeAscii {
+    Ascii(u8),
+    Other(Word),
+}
+
+impl From<Word> for MaybeAscii {
+    fn from(w: Word) -> MaybeAscii {
+        match w {
+            x @ 0..=127 => MaybeAscii::Ascii(x as u8),
+            y => MaybeAscii::Other(y),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+enum CardinalDirection {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl CardinalDirection {
+    fn as_direction(self) -> Direction {
+        self.into()
+    }
+
+    fn as_index(self) -> usize {
+        use CardinalDirection::*;
+
+        match self {
+            North => 0,
+            East => 1,
+            South => 2,
+            West => 3,
+        }
+    }
+
+    fn reverse(&self) -> CardinalDirection {
+        use CardinalDirection::*;
+        match *self {
+            North => South,
+            West => East,
+            South => North,
+            East => West,
+        }
+    }
+}
+
+impl Into<Position> for CardinalDirection {
+    fn into(self) -> Position {
+        let dir: Direction = self.into();
+        dir.into()
+    }
+}
+
+impl TryFrom<usize> for CardinalDirection {
+    type Error = usize;
+    fn try_from(i: usize) -> Result<Self, Self::Error> {
+        use CardinalDirection::*;
+        Ok(match i {
+            0 => North,
+            1 => East,
+            2 => South,
+            3 => West,
+            x => return Err(x),
+        })
+    }
+}
+
+impl Into<Direction> for CardinalDirection {
+    fn into(self) -> Direction {
+        use CardinalDirection::*;
+        use Direction::*;
+        match self {
+            North => Up,
+            East => Right,
+            South => Down,
+            West => Left,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for CardinalDirection {
+    type Error = &'a str;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Ok(match s {
+            "north" => CardinalDirection::North,
+            "east"  => CardinalDirection::East,
+            "south" => CardinalDirection::South,
+            "west"  => CardinalDirection::West,
+            _ => return Err(s),
+        })
+    }
+}
+
+impl fmt::Display for CardinalDirection {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            CardinalDirection::North => "north",
+            CardinalDirection::East => "east",
+            CardinalDirection::South => "south",
+            CardinalDirection::West => "west",
+        };
+
+        write!(fmt, "{}", s)
+    }
+}
+
+use std::collections::VecDeque;
+
+struct StdIO {
+    stdin: std::io::Stdin,
+    buffer: VecDeque<u8>,
+    line: String,
+}
+
+impl StdIO {
+    #[allow(dead_code)]
+    fn new() -> Self {
+        StdIO {
+            stdin: std::io::stdin(),
+            buffer: VecDeque::new(),
+            line: String::new(),
+        }
+    }
+}
+
+impl intcode::IO for StdIO {
+    fn input(&mut self) -> Result<Word, intcode::ProgramError> {
+        use std::io::BufRead;
+
+        while self.buffer.is_empty() {
+            self.line.clear();
+            match self.stdin.lock().read_line(&mut self.line) {
+                Ok(0) => panic!("stdin eof"),
+                Ok(_) => {
+                    let trimmed = self.line.trim().as_bytes();
+
+                    if trimmed.is_empty() {
+                        continue;
+                    }
+                    self.buffer.extend(trimmed);
+                    self.buffer.push_back(b'\n');
+                }
+                Err(e) => panic!("stdin read failed: {}", e),
+            }
+        }
+
+        Ok(self.buffer.pop_front().unwrap() as Word)
+    }
+
+    fn output(&mut self, value: Word) -> Result<(), intcode::ProgramError> {
+        print!("{}", value as u8 as char);
+        Ok(())
+    }
+}
+
+#[test]
+fn parse_rooms() {
+    let room = parse_room("\n\n\n== Holodeck ==\nSomeone seems to have left it on the Giant Grid setting.\n\nDoors here lead:\n- north\n- east\n- west\n\nItems here:\n- giant electromagnet\n\nCommand?\n").unwrap();
+
+    assert_eq!(room.name.as_str(), "Holodeck");
+    assert_eq!(room.doors, vec![CardinalDirection::North, CardinalDirection::East, CardinalDirection::West]);
+    assert_eq!(room.items, vec![Rc::new(String::from("giant electromagnet"))]);
+    assert!(room.ejection.is_none());
+
+    match parse_room("\nThe giant electromagnet is stuck to you.  You can't move!!\n\nCommand?\n") {
+        Err(GameFailure::Stuck(_)) => {},
+        x => panic!("unexpected {:?}", x),
+    }
+}
+
+#[test]
+fn parse_ejection() {
+    let room = parse_room("\n\n\n== Pressure-Sensitive Floor ==\nAnalyzing...\n\nDoors here lead:\n- south\n\nA loud, robotic voice says \"Alert! Droids on this ship are heavier than the detected value!\" and you are ejected back to the checkpoint.\n\n\n\n== Security Checkpoint ==\nIn the next room, a pressure-sensitive floor will verify your identity.\n\nDoors here lead:\n- north\n- south\n\nCommand?\n").unwrap();
+
+    assert_eq!(room.name.as_str(), "Security Checkpoint");
+    assert_eq!(room.desc, CHECKPOINT);
+
+    let ejection = room.ejection.unwrap();
+    assert_eq!(ejection.reason, EjectionReason::Light);
+    assert_eq!(ejection.ejected_from.name.as_str(), "Pressure-Sensitive Floor");
+}
+
+#[test]
+fn subsets_pruned_by_weight() {
+    let weights = [1, 2, 4, 8, 16, 32, 64, 128];
+
+    let weigh = |target: u32| {
+        let mut weighings = 0;
+        let found = search_subsets::<_, (), _>(weights.len(), |mask| {
+            weighings += 1;
+            let weight = (0..weights.len()).filter(|i| mask & (1 << i) != 0).map(|i| weights[i]).sum::<u32>();
+            Ok(if weight < target {
+                Weighing::Ejected(EjectionReason::Light)
+            } else if weight > target {
+                Weighing::Ejected(EjectionReason::Heavy)
+            } else {
+                Weighing::Passed(weight)
+            })
+        }).unwrap();
+        (found, weighings)
+    };
+
+    let (found, weighings) = weigh(2 + 16 + 64);
+    assert_eq!(found, Some((0b0101_0010, 82)));
+    assert!(weighings < 1 << weights.len(), "{} weighings", weighings);
+
+    let (found, weighings) = weigh(256);
+    assert_eq!(found, None);
+    assert!(weighings < 1 << weights.len(), "{} weighings", weighings);
+}
+
+#[test]
+fn too_many_items_for_the_checkpoint() {
+    let items = (0..32).map(|i| (Rc::new(format!("item {}", i)), Rc::new(String::from("Hallway")))).collect();
+    let ship = Ship {
+        start: Some(Rc::new(String::from("Hallway"))),
+        items,
+        checkpoint: Some((Rc::new(String::from("Security Checkpoint")), CardinalDirection::North)),
+        ..Ship::default()
+    };
+
+    // fails before the droid program runs at all
+    match Game::new(&[99]).pass_checkpoint(&ship) {
+        Err(GameFailure::TooManyItems(32)) => {},
+        x => panic!("unexpected {:?}", x),
+    }
+}
@@ -1,11 +1,27 @@
 use crate::Word;
 
+pub mod image;
+pub mod ocr;
+pub mod poi;
+pub mod compress;
+
 #[derive(Debug)]
 pub enum ParsingError {
     Io(std::io::Error, usize),
     Int(std::num::ParseIntError, usize, String),
 }
 
+impl std::fmt::Display for ParsingError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParsingError::Io(e, line) => write!(fmt, "Failed to read near line {}: {}", line, e),
+            ParsingError::Int(e, line, raw) => write!(fmt, "Bad input at line {}: \"{}\" ({})", line, raw.trim_end(), e),
+        }
+    }
+}
+
+impl std::error::Error for ParsingError {}
+
 pub fn parse_program<R: std::io::BufRead>(r: R) -> Result<Vec<Word>, ParsingError> {
     parse_program_n_lines(r, None)
 }
@@ -70,16 +86,18 @@ pub fn parse_stdin_program_n_lines(n: Option<usize>) -> Vec<Word> {
     }
 }
 
-/// Testing utility: parses "input" from current working directory as a program with
-/// `parse_program`, unwrapping on error.
-pub fn with_parsed_program<V, F>(f: F) -> V
+/// Testing utility: parses the default input of the 2019 `day` from the input store as a program
+/// with `parse_program`, panicking with the expected path if the input is missing.
+pub fn with_parsed_program<V, F>(day: u8, f: F) -> V
     where F: FnOnce(&[Word]) -> V
 {
-    use std::io::BufReader;
+    use aoc_common::input::{InputStore, DEFAULT_SET};
 
-    let file = std::fs::File::open("input").expect("Could not open day02 input?");
+    let input = InputStore::from_env()
+        .read(2019, day, DEFAULT_SET)
+        .unwrap_or_else(|e| panic!("2019 day {}: {}", day, e));
 
-    let data = parse_program(BufReader::new(file)).unwrap();
+    let data = parse_program(input.as_bytes()).unwrap();
 
     f(&data)
 }
@@ -91,7 +109,7 @@ mod gamedisplay {
     use std::io::BufRead;
 
     /// Does not really belong to `intcode` but useful for maps and displays.
-    #[derive(Default)]
+    #[derive(Default, Clone)]
     pub struct GameDisplay<T> {
         cells: Vec<T>,
         // coordinates of the left top corner or bottom?
@@ -179,15 +197,28 @@ mod gamedisplay {
                 .and_then(|index| self.cells.get(index))
         }
 
+        /// World coordinates of the top left cell.
+        pub fn smallest_coordinates(&self) -> (Word, Word) {
+            self.smallest_coordinates
+        }
+
         pub fn cells(&self) -> &[T] {
             self.cells.as_slice()
         }
 
+        /// World coordinates of the cell at `index` of `cells()`, the inverse of `to_index`.
         pub fn to_coordinates(&self, index: usize) -> (Word, Word) {
             let x = index % self.width;
             let y = index / self.width;
             assert!(y < self.height);
-            (x as Word, y as Word)
+            (self.smallest_coordinates.0 + x as Word, self.smallest_coordinates.1 + y as Word)
+        }
+
+        /// The cells with their world coordinates, row by row starting from the top left.
+        pub fn iter(&self) -> impl Iterator<Item = ((Word, Word), &T)> + Clone + '_ {
+            self.cells.iter()
+                .enumerate()
+                .map(move |(index, t)| (self.to_coordinates(index), t))
         }
 
         pub fn len(&self) -> usize {
@@ -198,9 +229,6 @@ mod gamedisplay {
     impl<T: Default + Clone> GameDisplay<T> {
 
         pub fn insert(&mut self, p: &(Word, Word), t: T) {
-            // protect against infinite loops
-            let mut gas = 3usize;
-
             if self.cells.is_empty() {
                 self.smallest_coordinates = *p;
                 self.cells.push(t);
@@ -209,26 +237,8 @@ mod gamedisplay {
                 return;
             }
 
-            loop {
-                if let Some(next_gas) = gas.checked_sub(1) {
-                    gas = next_gas;
-                } else {
-                    panic!("ran out of gas while trying to insert {:?}, w={}, h={}, smallest={:?}", p, self.width, self.height, self.smallest_coordinates);
-                }
-
-                // this could be Result<index, OutsideCoordinates::Before(Word, Word)> where err would be "how much outside"
-                if let Some(index) = self.to_index(p) {
-                    self.cells[index] = t;
-                    return;
-                }
-
-                let g = Growth::from_setup(
-                    (self.width, self.height),
-                    self.smallest_coordinates,
-                    *p);
-
-                let g = g.unwrap();
-
+            // each growth covers the point in one direction, so this runs at most twice
+            while let Some(g) = Growth::from_setup((self.width, self.height), self.smallest_coordinates, *p) {
                 let mut size = (self.width, self.height);
 
                 g.grow(&mut self.cells, &mut size, &mut self.smallest_coordinates);
@@ -236,6 +246,9 @@ mod gamedisplay {
                 self.width = size.0;
                 self.height = size.1;
             }
+
+            let index = self.to_index(p).expect("point should be contained after growing");
+            self.cells[index] = t;
         }
     }
 
@@ -596,62 +609,103 @@ mod gamedisplay {
         gd.insert(&( 0, 2), a);
 
     }
-}
-
-pub use gamedisplay::GameDisplay;
+    #[cfg(test)]
+    mod properties {
+        use super::GameDisplay;
+        use crate::Word;
+        use proptest::prelude::*;
+        use std::collections::HashMap;
+
+        /// Inserts of non-default values, mostly near the origin but with some far jumps.
+        fn inserts() -> impl Strategy<Value = Vec<((Word, Word), u8)>> {
+            let coordinate = prop_oneof![4 => -20..20 as Word, 1 => -500..500 as Word];
+            prop::collection::vec(((coordinate.clone(), coordinate), 1..=255u8), 0..40)
+        }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum Direction {
-    // position grows
-    Up,
-    // position grows
-    Right,
-    Down,
-    Left,
-}
+        fn build(inserts: &[((Word, Word), u8)]) -> (GameDisplay<u8>, HashMap<(Word, Word), u8>) {
+            let mut gd = GameDisplay::default();
+            let mut model = HashMap::new();
 
-impl Direction {
-    pub fn turn_left(&self) -> Self {
-        use Direction::*;
+            for &(p, v) in inserts {
+                gd.insert(&p, v);
+                model.insert(p, v);
+            }
 
-        match *self {
-            Up => Left,
-            Right => Up,
-            Down => Right,
-            Left => Down,
+            (gd, model)
         }
-    }
 
-    pub fn turn_right(&self) -> Self {
-        use Direction::*;
+        proptest! {
+            #[test]
+            fn agrees_with_hashmap(inserts in inserts()) {
+                let (gd, model) = build(&inserts);
+
+                for (p, v) in &model {
+                    prop_assert_eq!(gd.get(p), Some(v));
+                }
+
+                let mut seen = 0;
+                for (p, v) in gd.iter() {
+                    match model.get(&p) {
+                        Some(expected) => {
+                            prop_assert_eq!(v, expected);
+                            seen += 1;
+                        }
+                        None => prop_assert_eq!(*v, 0),
+                    }
+                }
+                prop_assert_eq!(seen, model.len());
+                prop_assert_eq!(gd.iter().count(), gd.len());
+            }
+
+            #[test]
+            fn grows_to_bounding_box(inserts in inserts()) {
+                let (gd, model) = build(&inserts);
+
+                if model.is_empty() {
+                    prop_assert_eq!(gd.len(), 0);
+                } else {
+                    let min_x = model.keys().map(|p| p.0).min().unwrap();
+                    let max_x = model.keys().map(|p| p.0).max().unwrap();
+                    let min_y = model.keys().map(|p| p.1).min().unwrap();
+                    let max_y = model.keys().map(|p| p.1).max().unwrap();
+
+                    prop_assert_eq!(gd.smallest_coordinates(), (min_x, min_y));
+                    prop_assert_eq!(gd.width(), (max_x - min_x + 1) as usize);
+                    prop_assert_eq!(gd.height(), (max_y - min_y + 1) as usize);
+                    prop_assert_eq!(gd.len(), gd.width() * gd.height());
+                }
+            }
+
+            #[test]
+            fn index_and_coordinates_round_trip(inserts in inserts(), probe in (-600..600 as Word, -600..600 as Word)) {
+                let (gd, _) = build(&inserts);
 
-        match *self {
-            Up => Right,
-            Right => Down,
-            Down => Left,
-            Left => Up,
+                for index in 0..gd.len() {
+                    prop_assert_eq!(gd.to_index(&gd.to_coordinates(index)), Some(index));
+                }
+
+                if let Some(index) = gd.to_index(&probe) {
+                    prop_assert_eq!(gd.to_coordinates(index), probe);
+                }
+            }
         }
     }
 }
 
-impl Into<Position> for &Direction {
-    fn into(self) -> Position {
-        use Direction::*;
+pub use gamedisplay::GameDisplay;
 
-        let tuple = match *self {
-            Up => (0, -1),
-            Right => (1, 0),
-            Down => (0, 1),
-            Left => (-1, 0),
-        };
+pub use aoc_common::geometry::Direction;
 
-        tuple.into()
+impl From<&Direction> for Position {
+    fn from(dir: &Direction) -> Position {
+        let p = aoc_common::geometry::Point2::from(dir);
+        Position(p.x(), p.y())
     }
 }
 
-impl Into<Position> for Direction {
-    fn into(self) -> Position {
-        (&self).into()
+impl From<Direction> for Position {
+    fn from(dir: Direction) -> Position {
+        (&dir).into()
     }
 }
 
@@ -0,0 +1,249 @@
+//! Compressing a sequence of tokens into a main routine calling subroutines, like the movement
+//! functions of the vacuum robot of day 17.
+//!
+//! The ASCII programs take each routine as a line of comma separated tokens with a limited length,
+//! and the main routine can only call the subroutines which are named with letters from `A`.
+
+use std::fmt;
+
+/// Main routine and subroutines which together expand back to the compressed tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compression<T> {
+    /// Indices of the subroutines called in order.
+    pub main: Vec<usize>,
+    /// Subroutines in the order of their first call.
+    pub functions: Vec<Vec<T>>,
+}
+
+impl<T: Clone> Compression<T> {
+    /// The tokens the main routine expands to.
+    pub fn expand(&self) -> Vec<T> {
+        self.main
+            .iter()
+            .flat_map(|&f| self.functions[f].iter().cloned())
+            .collect()
+    }
+}
+
+/// Finds compressions of the tokens into at most `functions` subroutines so that each routine
+/// fits the `budget` of characters when rendered with `render` and separated by commas.
+pub struct Compressor<R> {
+    pub functions: usize,
+    pub budget: usize,
+    pub render: R,
+}
+
+/// Most subroutines there are names for, from `A` to `Z`.
+pub const MAX_FUNCTIONS: usize = 26;
+
+/// Name of the subroutine at the index as called from the main routine.
+pub fn function_name(index: usize) -> char {
+    assert!(index < MAX_FUNCTIONS, "no name for subroutine {}", index);
+    (b'A' + index as u8) as char
+}
+
+impl<R> Compressor<R> {
+    /// First compression found, trying the shortest subroutines first.
+    pub fn compress<T, D>(&self, tokens: &[T]) -> Option<Compression<T>>
+    where
+        T: PartialEq + Clone,
+        R: Fn(&T) -> D,
+        D: fmt::Display,
+    {
+        let mut first = None;
+        self.search(tokens, &mut |c| {
+            first = Some(c);
+            false
+        });
+        first
+    }
+
+    /// All of the compressions, which differ in how the tokens are split between the subroutines.
+    pub fn compressions<T, D>(&self, tokens: &[T]) -> Vec<Compression<T>>
+    where
+        T: PartialEq + Clone,
+        R: Fn(&T) -> D,
+        D: fmt::Display,
+    {
+        let mut all = Vec::new();
+        self.search(tokens, &mut |c| {
+            all.push(c);
+            true
+        });
+        all
+    }
+
+    /// The routines as lines, the main routine first and then every subroutine, with empty
+    /// lines for the unused ones.
+    pub fn render<T, D>(&self, compression: &Compression<T>) -> Vec<String>
+    where
+        R: Fn(&T) -> D,
+        D: fmt::Display,
+    {
+        let main = compression.main.iter().map(|&f| function_name(f).to_string());
+        let functions = (0..self.functions).map(|f| {
+            compression
+                .functions
+                .get(f)
+                .map(|tokens| self.line(tokens.iter().map(|t| (self.render)(t).to_string())))
+                .unwrap_or_default()
+        });
+
+        std::iter::once(self.line(main)).chain(functions).collect()
+    }
+
+    fn line(&self, tokens: impl Iterator<Item = String>) -> String {
+        tokens.collect::<Vec<_>>().join(",")
+    }
+
+    fn search<T, D>(&self, tokens: &[T], found: &mut dyn FnMut(Compression<T>) -> bool)
+    where
+        T: PartialEq + Clone,
+        R: Fn(&T) -> D,
+        D: fmt::Display,
+    {
+        assert!(
+            self.functions <= MAX_FUNCTIONS,
+            "at most {} subroutines can be named, not {}",
+            MAX_FUNCTIONS,
+            self.functions
+        );
+
+        let widths = tokens
+            .iter()
+            .map(|t| (self.render)(t).to_string().len())
+            .collect::<Vec<_>>();
+
+        let mut state = Compression {
+            main: Vec::new(),
+            functions: Vec::new(),
+        };
+
+        self.split(tokens, &widths, 0, &mut state, found);
+    }
+
+    /// Covers the tokens from `at` onwards, returning false once `found` wants no more.
+    fn split<T: PartialEq + Clone>(
+        &self,
+        tokens: &[T],
+        widths: &[usize],
+        at: usize,
+        state: &mut Compression<T>,
+        found: &mut dyn FnMut(Compression<T>) -> bool,
+    ) -> bool {
+        if at == tokens.len() {
+            return found(state.clone());
+        }
+
+        // every call takes a letter and a comma
+        if 2 * (state.main.len() + 1) - 1 > self.budget {
+            return true;
+        }
+
+        for f in 0..state.functions.len() {
+            if tokens[at..].starts_with(&state.functions[f]) {
+                let len = state.functions[f].len();
+                state.main.push(f);
+                let more = self.split(tokens, widths, at + len, state, found);
+                state.main.pop();
+                if !more {
+                    return false;
+                }
+            }
+        }
+
+        if state.functions.len() < self.functions {
+            // subroutines are defined at their first call, which keeps them in order
+            let mut width = 0;
+            for end in at + 1..=tokens.len() {
+                width += widths[end - 1] + if end - 1 > at { 1 } else { 0 };
+                if width > self.budget {
+                    break;
+                }
+
+                state.main.push(state.functions.len());
+                state.functions.push(tokens[at..end].to_vec());
+                let more = self.split(tokens, widths, end, state, found);
+                state.functions.pop();
+                state.main.pop();
+                if !more {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+fn example_route() -> Vec<String> {
+    // the route of the published example of day 17, split into the turns and the moves
+    "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2"
+        .split(',')
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn compresses_the_published_example() {
+    let route = example_route();
+    let compressor = Compressor {
+        functions: 3,
+        budget: 20,
+        render: |s: &String| s.clone(),
+    };
+
+    let all = compressor.compressions(&route);
+    assert!(!all.is_empty());
+    assert!(all.iter().all(|c| c.expand() == route));
+    assert!(all.iter().all(|c| compressor
+        .render(c)
+        .iter()
+        .all(|line| line.len() <= 20)));
+
+    let expected = vec!["A,B,C,B,A,C", "R,8,R,8", "R,4,R,4", "R,8,L,6,L,2"];
+    assert!(all.iter().any(|c| compressor.render(c) == expected));
+
+    let first = compressor.compress(&route).unwrap();
+    assert_eq!(first, all[0]);
+}
+
+#[test]
+fn budgets_and_function_counts() {
+    let route = example_route();
+
+    // the main routine alone would be too long for a single call per token
+    let compressor = Compressor {
+        functions: 1,
+        budget: 20,
+        render: |s: &String| s.clone(),
+    };
+    assert_eq!(compressor.compress(&route), None);
+
+    // multi digit tokens count for every digit, and the main routine has room for four calls
+    let compressor = Compressor {
+        functions: 3,
+        budget: 7,
+        render: |n: &u32| *n,
+    };
+    let c = compressor.compress(&[10, 10, 3, 10, 10, 3]).unwrap();
+    assert_eq!(compressor.render(&c), vec!["A,A,B,C", "10", "3", "10,10,3"]);
+
+    // a single subroutine fits as well
+    assert!(compressor
+        .compressions(&[10, 10, 3, 10, 10, 3])
+        .iter()
+        .any(|c| c.main == vec![0, 0] && c.functions.len() == 1));
+}
+
+#[test]
+#[should_panic(expected = "at most 26 subroutines can be named, not 27")]
+fn too_many_functions_to_name() {
+    let compressor = Compressor {
+        functions: MAX_FUNCTIONS + 1,
+        budget: 20,
+        render: |n: &u32| *n,
+    };
+    compressor.compress(&[1, 2, 3]);
+}
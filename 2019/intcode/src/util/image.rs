@@ -0,0 +1,510 @@
+//! Exporting `GameDisplay` snapshots as images instead of terminal output.
+//!
+//! Single frames can be written as PPM or PNG, and a `FrameRecorder` can collect successive
+//! snapshots to be written either as numbered PNG files or as a single animated PNG. The PNG
+//! encoder is a minimal one with only fixed huffman codes, so the files are larger than they need
+//! to be but there is no need for extra dependencies.
+
+use std::convert::TryFrom;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::Word;
+use super::GameDisplay;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+}
+
+/// Maps the cell values of a `GameDisplay` to colors.
+pub trait Palette<T> {
+    fn color(&self, cell: &T) -> Rgb;
+}
+
+impl<T, F: Fn(&T) -> Rgb> Palette<T> for F {
+    fn color(&self, cell: &T) -> Rgb {
+        (self)(cell)
+    }
+}
+
+/// Single snapshot of a `GameDisplay` with one pixel per cell, remembering the world coordinates
+/// of the top left cell so that frames of differently sized displays can be aligned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    origin: (Word, Word),
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Frame {
+    pub fn capture<T, P: Palette<T> + ?Sized>(gd: &GameDisplay<T>, palette: &P) -> Frame {
+        Frame {
+            origin: gd.smallest_coordinates(),
+            width: gd.width(),
+            height: gd.height(),
+            pixels: gd.cells().iter().map(|cell| palette.color(cell)).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[Rgb] {
+        self.pixels.as_slice()
+    }
+
+    /// Returns a copy of this frame placed on a `width` x `height` canvas whose top left corner is
+    /// at `origin`, filling the uncovered pixels with `background`.
+    fn reframe(&self, origin: (Word, Word), width: usize, height: usize, background: Rgb) -> Frame {
+        let mut pixels = vec![background; width * height];
+
+        let dx = (self.origin.0 - origin.0) as usize;
+        let dy = (self.origin.1 - origin.1) as usize;
+
+        assert!(dx + self.width <= width && dy + self.height <= height, "frame does not fit the canvas");
+
+        for (y, row) in self.pixels.chunks(self.width.max(1)).enumerate() {
+            let start = (dy + y) * width + dx;
+            pixels[start..start + row.len()].copy_from_slice(row);
+        }
+
+        Frame { origin, width, height, pixels }
+    }
+
+    /// Raw RGB scanlines with each cell repeated `scale` times horizontally and vertically.
+    fn scaled_rows(&self, scale: usize) -> impl Iterator<Item = Vec<u8>> + '_ {
+        let scale = scale.max(1);
+        self.pixels
+            .chunks(self.width.max(1))
+            .map(move |row| {
+                row.iter()
+                    .flat_map(|px| std::iter::repeat_n(px, scale))
+                    .flat_map(|&Rgb(r, g, b)| vec![r, g, b])
+                    .collect::<Vec<u8>>()
+            })
+            .flat_map(move |row| std::iter::repeat_n(row, scale))
+    }
+
+    /// Writes the frame as a binary (P6) PPM file.
+    pub fn write_ppm<W: Write>(&self, scale: usize, mut out: W) -> io::Result<()> {
+        let scale = scale.max(1);
+        write!(out, "P6\n{} {}\n255\n", self.width * scale, self.height * scale)?;
+        for row in self.scaled_rows(scale) {
+            out.write_all(&row)?;
+        }
+        out.flush()
+    }
+
+    /// Writes the frame as a truecolor PNG file.
+    pub fn write_png<W: Write>(&self, scale: usize, mut out: W) -> io::Result<()> {
+        let scale = scale.max(1);
+        let (w, h) = ((self.width * scale) as u32, (self.height * scale) as u32);
+
+        out.write_all(png::SIGNATURE)?;
+        png::write_chunk(&mut out, b"IHDR", &png::header(w, h))?;
+        png::write_chunk(&mut out, b"IDAT", &png::image_data(self.scaled_rows(scale)))?;
+        png::write_chunk(&mut out, b"IEND", &[])?;
+        out.flush()
+    }
+}
+
+/// Collects frames from successive snapshots of a `GameDisplay` for writing an animation.
+pub struct FrameRecorder<P> {
+    palette: P,
+    background: Rgb,
+    delay: Duration,
+    frames: Vec<Frame>,
+}
+
+impl<P> FrameRecorder<P> {
+    /// `background` is used for the areas which are not covered by a frame, for example when the
+    /// display has grown since the frame was recorded.
+    pub fn new(palette: P, background: Rgb) -> Self {
+        FrameRecorder {
+            palette,
+            background,
+            delay: Duration::from_millis(100),
+            frames: Vec::new(),
+        }
+    }
+
+    /// Delay between frames in the animated PNG, defaults to 100ms.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn record<T>(&mut self, gd: &GameDisplay<T>)
+        where P: Palette<T>
+    {
+        let frame = Frame::capture(gd, &self.palette);
+        self.frames.push(frame);
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Recorded frames placed on a common canvas covering all of the recorded frames.
+    pub fn frames(&self) -> Vec<Frame> {
+        let mut iter = self.frames.iter();
+
+        let first = match iter.next() {
+            Some(first) => first,
+            None => return Vec::new(),
+        };
+
+        let bounds = |f: &Frame| (f.origin, (f.origin.0 + f.width as Word, f.origin.1 + f.height as Word));
+
+        let (min, max) = iter.fold(bounds(first), |(min, max), f| {
+            let (fmin, fmax) = bounds(f);
+            ((min.0.min(fmin.0), min.1.min(fmin.1)), (max.0.max(fmax.0), max.1.max(fmax.1)))
+        });
+
+        let (width, height) = ((max.0 - min.0) as usize, (max.1 - min.1) as usize);
+
+        self.frames.iter()
+            .map(|f| f.reframe(min, width, height, self.background))
+            .collect()
+    }
+
+    /// Writes the frames as `{prefix}{index:05}.png` files into `dir`, returning the created paths.
+    pub fn write_numbered(&self, dir: &Path, prefix: &str, scale: usize) -> io::Result<Vec<PathBuf>> {
+        let mut written = Vec::with_capacity(self.frames.len());
+        for (i, frame) in self.frames().into_iter().enumerate() {
+            let path = dir.join(format!("{}{:05}.png", prefix, i));
+            let file = std::fs::File::create(&path)?;
+            frame.write_png(scale, io::BufWriter::new(file))?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+
+    /// Writes all of the frames as a single looping animated PNG.
+    pub fn write_apng<W: Write>(&self, scale: usize, mut out: W) -> io::Result<()> {
+        let frames = self.frames();
+        let first = match frames.first() {
+            Some(first) => first,
+            None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "no frames recorded")),
+        };
+
+        let scale = scale.max(1);
+        let (w, h) = ((first.width * scale) as u32, (first.height * scale) as u32);
+        let delay_ms = u16::try_from(self.delay.as_millis()).unwrap_or(u16::MAX);
+
+        out.write_all(png::SIGNATURE)?;
+        png::write_chunk(&mut out, b"IHDR", &png::header(w, h))?;
+
+        let mut actl = Vec::with_capacity(8);
+        actl.extend(&(frames.len() as u32).to_be_bytes());
+        // zero plays means looping forever
+        actl.extend(&0u32.to_be_bytes());
+        png::write_chunk(&mut out, b"acTL", &actl)?;
+
+        let mut sequence = 0u32;
+
+        for (i, frame) in frames.iter().enumerate() {
+            let mut fctl = Vec::with_capacity(26);
+            fctl.extend(&sequence.to_be_bytes());
+            fctl.extend(&w.to_be_bytes());
+            fctl.extend(&h.to_be_bytes());
+            // x and y offsets
+            fctl.extend(&[0u8; 8]);
+            fctl.extend(&delay_ms.to_be_bytes());
+            fctl.extend(&1000u16.to_be_bytes());
+            // dispose_op = none, blend_op = source
+            fctl.extend(&[0u8, 0u8]);
+            png::write_chunk(&mut out, b"fcTL", &fctl)?;
+            sequence += 1;
+
+            let data = png::image_data(frame.scaled_rows(scale));
+
+            if i == 0 {
+                png::write_chunk(&mut out, b"IDAT", &data)?;
+            } else {
+                let mut fdat = Vec::with_capacity(4 + data.len());
+                fdat.extend(&sequence.to_be_bytes());
+                fdat.extend(data);
+                png::write_chunk(&mut out, b"fdAT", &fdat)?;
+                sequence += 1;
+            }
+        }
+
+        png::write_chunk(&mut out, b"IEND", &[])?;
+        out.flush()
+    }
+}
+
+mod png {
+    use std::io::{self, Write};
+
+    pub(super) const SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+    pub(super) fn header(width: u32, height: u32) -> Vec<u8> {
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend(&width.to_be_bytes());
+        ihdr.extend(&height.to_be_bytes());
+        // bit depth 8, color type 2 (truecolor), compression, filter and interlace methods 0
+        ihdr.extend(&[8, 2, 0, 0, 0]);
+        ihdr
+    }
+
+    pub(super) fn write_chunk<W: Write>(out: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+        out.write_all(&(data.len() as u32).to_be_bytes())?;
+        out.write_all(kind)?;
+        out.write_all(data)?;
+        let crc = crc32(kind.iter().chain(data.iter()).copied());
+        out.write_all(&crc.to_be_bytes())
+    }
+
+    /// zlib stream of the scanlines, each prefixed with filter type 0.
+    pub(super) fn image_data<I: Iterator<Item = Vec<u8>>>(rows: I) -> Vec<u8> {
+        let mut raw = Vec::new();
+        for row in rows {
+            raw.push(0);
+            raw.extend(row);
+        }
+
+        // CMF: deflate with 32k window, FLG: no dict, fastest, check bits
+        let mut out = vec![0x78, 0x01];
+        deflate(&raw, &mut out);
+        out.extend(&adler32(&raw).to_be_bytes());
+        out
+    }
+
+    const LENGTH_BASE: [u16; 29] = [
+        3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115,
+        131, 163, 195, 227, 258,
+    ];
+    const LENGTH_EXTRA: [u8; 29] = [
+        0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+    ];
+    const DISTANCE_BASE: [u16; 30] = [
+        1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+        2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+    ];
+    const DISTANCE_EXTRA: [u8; 30] = [
+        0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12,
+        13, 13,
+    ];
+
+    #[derive(Default)]
+    struct BitWriter {
+        out: Vec<u8>,
+        acc: u32,
+        bits: u8,
+    }
+
+    impl BitWriter {
+        /// Writes the `count` lowest bits of `value`, least significant first.
+        fn bits(&mut self, value: u32, count: u8) {
+            for i in 0..count {
+                self.acc |= ((value >> i) & 1) << self.bits;
+                self.bits += 1;
+                if self.bits == 8 {
+                    self.out.push(self.acc as u8);
+                    self.acc = 0;
+                    self.bits = 0;
+                }
+            }
+        }
+
+        /// Huffman codes are written most significant bit first.
+        fn code(&mut self, code: u32, count: u8) {
+            for i in (0..count).rev() {
+                self.bits((code >> i) & 1, 1);
+            }
+        }
+
+        fn symbol(&mut self, symbol: u16) {
+            match symbol {
+                0..=143 => self.code(0x30 + symbol as u32, 8),
+                144..=255 => self.code(0x190 + (symbol - 144) as u32, 9),
+                256..=279 => self.code((symbol - 256) as u32, 7),
+                _ => self.code(0xc0 + (symbol - 280) as u32, 8),
+            }
+        }
+
+        fn finish(mut self) -> Vec<u8> {
+            if self.bits > 0 {
+                self.out.push(self.acc as u8);
+            }
+            self.out
+        }
+    }
+
+    /// Single fixed huffman block with greedy matching against the latest occurrence of each
+    /// three byte prefix, which is enough for the long runs of same colored cells.
+    fn deflate(raw: &[u8], out: &mut Vec<u8>) {
+        use std::collections::HashMap;
+
+        let mut w = BitWriter::default();
+        // BFINAL = 1, BTYPE = 01 (fixed huffman codes)
+        w.bits(1, 1);
+        w.bits(1, 2);
+
+        let mut latest: HashMap<&[u8], usize> = HashMap::new();
+        let mut i = 0;
+
+        while i < raw.len() {
+            let candidate = raw.get(i..i + 3)
+                .and_then(|prefix| latest.insert(prefix, i))
+                .filter(|&at| i - at <= 32768);
+
+            let matched = candidate.map(|at| {
+                raw[i..].iter()
+                    .zip(&raw[at..])
+                    .take(258)
+                    .take_while(|(a, b)| a == b)
+                    .count()
+            });
+
+            match (candidate, matched) {
+                (Some(at), Some(len)) if len >= 3 => {
+                    let code = LENGTH_BASE.iter().rposition(|&base| base as usize <= len).unwrap();
+                    w.symbol(257 + code as u16);
+                    w.bits((len - LENGTH_BASE[code] as usize) as u32, LENGTH_EXTRA[code]);
+
+                    let distance = i - at;
+                    let code = DISTANCE_BASE.iter().rposition(|&base| base as usize <= distance).unwrap();
+                    w.code(code as u32, 5);
+                    w.bits((distance - DISTANCE_BASE[code] as usize) as u32, DISTANCE_EXTRA[code]);
+
+                    for j in i + 1..i + len {
+                        if let Some(prefix) = raw.get(j..j + 3) {
+                            latest.insert(prefix, j);
+                        }
+                    }
+                    i += len;
+                },
+                _ => {
+                    w.symbol(raw[i] as u16);
+                    i += 1;
+                }
+            }
+        }
+
+        w.symbol(256);
+        out.extend(w.finish());
+    }
+
+    pub(super) fn crc32<I: Iterator<Item = u8>>(bytes: I) -> u32 {
+        let mut crc = 0xffff_ffffu32;
+        for b in bytes {
+            crc ^= b as u32;
+            for _ in 0..8 {
+                let mask = (crc & 1).wrapping_neg();
+                crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+            }
+        }
+        !crc
+    }
+
+    pub(super) fn adler32(bytes: &[u8]) -> u32 {
+        let (mut a, mut b) = (1u32, 0u32);
+        for chunk in bytes.chunks(5552) {
+            for &x in chunk {
+                a += x as u32;
+                b += a;
+            }
+            a %= 65521;
+            b %= 65521;
+        }
+        (b << 16) | a
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND".iter().copied()), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn deflate_runs() {
+        let raw = (0..1000u32).map(|i| if i % 300 < 150 { 7 } else { (i % 7) as u8 }).collect::<Vec<_>>();
+        let mut out = Vec::new();
+        deflate(&raw, &mut out);
+        assert!(out.len() < raw.len() / 4, "{} bytes compressed to {}", raw.len(), out.len());
+    }
+}
+
+#[cfg(test)]
+fn two_by_two() -> GameDisplay<bool> {
+    let mut gd = GameDisplay::default();
+    gd.insert(&(0, 0), true);
+    gd.insert(&(1, 1), true);
+    gd
+}
+
+#[cfg(test)]
+fn black_and_white(lit: &bool) -> Rgb {
+    if *lit { Rgb::WHITE } else { Rgb::BLACK }
+}
+
+#[test]
+fn ppm_scales_cells() {
+    let frame = Frame::capture(&two_by_two(), &black_and_white);
+
+    let mut out = Vec::new();
+    frame.write_ppm(2, &mut out).unwrap();
+
+    let header = b"P6\n4 4\n255\n";
+    assert_eq!(&out[..header.len()], header);
+
+    let pixels = &out[header.len()..];
+    assert_eq!(pixels.len(), 4 * 4 * 3);
+    // first row: two white pixels followed by two black
+    assert_eq!(&pixels[..12], &[255, 255, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0]);
+    // last row: two black followed by two white
+    assert_eq!(&pixels[36..], &[0, 0, 0, 0, 0, 0, 255, 255, 255, 255, 255, 255]);
+}
+
+#[test]
+fn png_chunks() {
+    let frame = Frame::capture(&two_by_two(), &black_and_white);
+
+    let mut out = Vec::new();
+    frame.write_png(1, &mut out).unwrap();
+
+    assert_eq!(&out[..8], png::SIGNATURE);
+    assert_eq!(&out[12..16], b"IHDR");
+    assert_eq!(&out[16..24], &[0, 0, 0, 2, 0, 0, 0, 2]);
+    assert_eq!(&out[out.len() - 12..], &[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]);
+}
+
+#[test]
+fn recorder_aligns_growing_display() {
+    let mut gd = GameDisplay::default();
+    let mut recorder = FrameRecorder::new(black_and_white, Rgb(1, 2, 3));
+
+    gd.insert(&(0, 0), true);
+    recorder.record(&gd);
+    gd.insert(&(-1, 0), false);
+    recorder.record(&gd);
+
+    let frames = recorder.frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].pixels(), &[Rgb(1, 2, 3), Rgb::WHITE]);
+    assert_eq!(frames[1].pixels(), &[Rgb::BLACK, Rgb::WHITE]);
+
+    let mut out = Vec::new();
+    recorder.write_apng(1, &mut out).unwrap();
+    let chunks = out.windows(4).filter(|w| w == b"fcTL").count();
+    assert_eq!(chunks, 2);
+    assert_eq!(out.windows(4).filter(|w| w == b"fdAT").count(), 1);
+}
+
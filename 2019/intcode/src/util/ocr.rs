@@ -0,0 +1,208 @@
+//! Reading the capital letters drawn as 4x6 pixel glyphs by some of the puzzles.
+//!
+//! Glyphs are separated from each other by columns with no lit pixels, and each run of lit columns
+//! is looked up from the known alphabet. Blank rows above and below the text are ignored.
+
+use std::fmt;
+use super::GameDisplay;
+
+const HEIGHT: usize = 6;
+
+/// The letters seen so far in the puzzles; not all of the alphabet has been seen.
+const ALPHABET: &[(char, [&str; HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// Number of cells is not `width * height`.
+    BadDimensions { len: usize, width: usize, height: usize },
+    /// There were no lit pixels at all.
+    Empty,
+    /// Text was not exactly as high as the glyphs.
+    BadHeight(usize),
+    /// Glyph at `index` (starting from the left) was not in the alphabet; `column` is the first
+    /// column of the glyph in the input.
+    UnrecognizedGlyph { index: usize, column: usize, glyph: String },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::BadDimensions { len, width, height } =>
+                write!(fmt, "{} cells cannot be an image of {}x{}", len, width, height),
+            OcrError::Empty => write!(fmt, "no lit pixels"),
+            OcrError::BadHeight(h) => write!(fmt, "text is {} pixels high, expected {}", h, HEIGHT),
+            OcrError::UnrecognizedGlyph { index, column, glyph } =>
+                write!(fmt, "unrecognized glyph #{} at column {}:\n{}", index, column, glyph),
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Decodes the text out of a raw image such as the one in day08, where each byte is a pixel.
+pub fn read_image<F>(image: &[u8], width: usize, height: usize, lit: F) -> Result<String, OcrError>
+    where F: Fn(&u8) -> bool
+{
+    read_cells(image, width, height, lit)
+}
+
+/// Decodes the text out of a `GameDisplay`, such as the painted hull in day11.
+pub fn read_display<T, F>(gd: &GameDisplay<T>, lit: F) -> Result<String, OcrError>
+    where F: Fn(&T) -> bool
+{
+    read_cells(gd.cells(), gd.width(), gd.height(), lit)
+}
+
+pub fn read_cells<T, F>(cells: &[T], width: usize, height: usize, lit: F) -> Result<String, OcrError>
+    where F: Fn(&T) -> bool
+{
+    if cells.len() != width * height {
+        return Err(OcrError::BadDimensions { len: cells.len(), width, height });
+    }
+
+    let is_lit = |x: usize, y: usize| lit(&cells[y * width + x]);
+
+    let lit_rows = (0..height)
+        .filter(|&y| (0..width).any(|x| is_lit(x, y)))
+        .collect::<Vec<_>>();
+
+    let (top, bottom) = match (lit_rows.first(), lit_rows.last()) {
+        (Some(&top), Some(&bottom)) => (top, bottom),
+        _ => return Err(OcrError::Empty),
+    };
+
+    if bottom - top + 1 != HEIGHT {
+        return Err(OcrError::BadHeight(bottom - top + 1));
+    }
+
+    let column_lit = |x: usize| (top..=bottom).any(|y| is_lit(x, y));
+
+    let mut text = String::new();
+    let mut x = 0;
+
+    while x < width {
+        if !column_lit(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && column_lit(x) {
+            x += 1;
+        }
+
+        let glyph = (top..=bottom)
+            .map(|y| (start..x).map(|x| if is_lit(x, y) { '#' } else { '.' }).collect::<String>())
+            .collect::<Vec<_>>();
+
+        let found = ALPHABET.iter()
+            .find(|(_, rows)| rows.iter().zip(glyph.iter()).all(|(a, b)| a == b))
+            .map(|(ch, _)| *ch);
+
+        match found {
+            Some(ch) => text.push(ch),
+            None => return Err(OcrError::UnrecognizedGlyph {
+                index: text.chars().count(),
+                column: start,
+                glyph: glyph.join("\n"),
+            }),
+        }
+    }
+
+    Ok(text)
+}
+
+#[cfg(test)]
+fn from_picture(picture: &str) -> (Vec<u8>, usize, usize) {
+    let width = picture.lines().map(str::len).max().unwrap_or(0);
+    let height = picture.lines().count();
+    let mut image = Vec::with_capacity(width * height);
+    for line in picture.lines() {
+        image.extend(line.bytes());
+        image.extend(std::iter::repeat(b'.').take(width - line.len()));
+    }
+    (image, width, height)
+}
+
+#[test]
+fn whole_alphabet() {
+    let mut rows = vec![String::new(); HEIGHT];
+    for (_, glyph) in ALPHABET {
+        for (row, line) in rows.iter_mut().zip(glyph.iter()) {
+            row.push_str(line);
+            row.push('.');
+        }
+    }
+
+    let (image, w, h) = from_picture(&rows.join("\n"));
+
+    let expected = ALPHABET.iter().map(|(ch, _)| *ch).collect::<String>();
+    assert_eq!(read_image(&image, w, h, |&b| b == b'#').unwrap(), expected);
+}
+
+#[test]
+fn blank_margins_are_ignored() {
+    let picture = "
+.........
+..#..#...
+..#..#...
+..####...
+..#..#...
+..#..#...
+..#..#...
+.........";
+
+    let (image, w, h) = from_picture(picture.trim_start());
+    assert_eq!(read_image(&image, w, h, |&b| b == b'#').unwrap(), "H");
+}
+
+#[test]
+fn unrecognized_glyph() {
+    let picture = "\
+####.#..#
+#....#..#
+###..#..#
+#....####
+#.......#
+####....#";
+
+    let (image, w, h) = from_picture(picture);
+    assert_eq!(
+        read_image(&image, w, h, |&b| b == b'#'),
+        Err(OcrError::UnrecognizedGlyph {
+            index: 1,
+            column: 5,
+            glyph: String::from("#..#\n#..#\n#..#\n####\n...#\n...#"),
+        }));
+}
+
+#[test]
+fn display_reading() {
+    let mut gd = GameDisplay::default();
+    for (y, line) in ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"].iter().enumerate() {
+        for (x, ch) in line.chars().enumerate() {
+            gd.insert(&(x as crate::Word - 10, y as crate::Word + 3), ch == '#');
+        }
+    }
+    assert_eq!(read_display(&gd, |&lit| lit).unwrap(), "K");
+}
+
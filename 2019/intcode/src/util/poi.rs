@@ -0,0 +1,221 @@
+//! Weighted graphs between the points of interest of a [`GameDisplay`] map.
+//!
+//! Mazes like the ones of days 18 and 20 have thousands of open cells but only tens of cells which
+//! matter, like keys, doors or portals. Walking from each of the interesting cells over the open
+//! cells gives the distances to the other interesting cells reachable from it, so that the actual
+//! searches can run over the small graph instead of the cells.
+
+use std::collections::{HashMap, VecDeque};
+use crate::Word;
+use super::GameDisplay;
+
+/// How a cell is seen while walking the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Wall,
+    Open,
+    /// Node of the graph; `stop` ends the walks arriving at the cell, otherwise the walks continue
+    /// through it to the points of interest behind it.
+    Interesting { stop: bool },
+}
+
+/// Shortest walk from a node to another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge<M> {
+    pub to: usize,
+    pub steps: usize,
+    /// Whatever was gathered from the cells between the nodes, like the doors crossed.
+    pub crossed: M,
+}
+
+#[derive(Debug, Clone)]
+pub struct PoiGraph<M> {
+    positions: Vec<(Word, Word)>,
+    index: HashMap<(Word, Word), usize>,
+    edges: Vec<Vec<Edge<M>>>,
+}
+
+impl<M: Clone + Default> PoiGraph<M> {
+    /// Builds the graph by walking breadth first from each of the interesting cells, orthogonally
+    /// over the cells which are not walls. `cross` is called with the metadata of the walk for each
+    /// cell walked through, excluding the start and the end.
+    pub fn build<T, C, X>(gd: &GameDisplay<T>, mut classify: C, mut cross: X) -> Self
+        where C: FnMut((Word, Word), &T) -> Cell,
+              X: FnMut(&mut M, (Word, Word), &T),
+    {
+        let cells = gd.iter()
+            .map(|(p, t)| classify(p, t))
+            .collect::<Vec<_>>();
+
+        let positions = cells.iter()
+            .enumerate()
+            .filter(|(_, c)| matches!(c, Cell::Interesting { .. }))
+            .map(|(i, _)| gd.to_coordinates(i))
+            .collect::<Vec<_>>();
+
+        let index = positions.iter()
+            .enumerate()
+            .map(|(i, p)| (*p, i))
+            .collect::<HashMap<_, _>>();
+
+        let mut edges = Vec::with_capacity(positions.len());
+        let mut seen = vec![false; cells.len()];
+        let mut queue = VecDeque::new();
+
+        for &start in &positions {
+            seen.iter_mut().for_each(|s| *s = false);
+            queue.clear();
+
+            let mut out = Vec::new();
+            seen[gd.to_index(&start).unwrap()] = true;
+            queue.push_back((start, 0, M::default()));
+
+            while let Some((p, steps, crossed)) = queue.pop_front() {
+                for next in neighbours(p) {
+                    let i = match gd.to_index(&next) {
+                        Some(i) if !seen[i] => i,
+                        _ => continue,
+                    };
+
+                    seen[i] = true;
+
+                    let walk_on = match cells[i] {
+                        Cell::Wall => continue,
+                        Cell::Open => true,
+                        Cell::Interesting { stop } => {
+                            out.push(Edge { to: index[&next], steps: steps + 1, crossed: crossed.clone() });
+                            !stop
+                        }
+                    };
+
+                    if walk_on {
+                        let mut crossed = crossed.clone();
+                        cross(&mut crossed, next, &gd.cells()[i]);
+                        queue.push_back((next, steps + 1, crossed));
+                    }
+                }
+            }
+
+            edges.push(out);
+        }
+
+        PoiGraph { positions, index, edges }
+    }
+}
+
+impl<M> PoiGraph<M> {
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Node at the position, if it was interesting.
+    pub fn node(&self, p: &(Word, Word)) -> Option<usize> {
+        self.index.get(p).copied()
+    }
+
+    pub fn position(&self, node: usize) -> (Word, Word) {
+        self.positions[node]
+    }
+
+    /// Edges to the nodes reachable from `node`, nearest first.
+    pub fn edges(&self, node: usize) -> &[Edge<M>] {
+        &self.edges[node]
+    }
+
+    /// The nodes with their positions.
+    pub fn nodes(&self) -> impl Iterator<Item = (usize, (Word, Word))> + '_ {
+        self.positions.iter().copied().enumerate()
+    }
+}
+
+fn neighbours(p: (Word, Word)) -> impl Iterator<Item = (Word, Word)> {
+    [(0, -1), (1, 0), (0, 1), (-1, 0)].iter()
+        .map(move |d| (p.0 + d.0, p.1 + d.1))
+}
+
+/// True for the open cells with more than two open neighbours, which are the points where walks
+/// branch, for using as points of interest in addition to the others.
+pub fn is_intersection<T, F>(gd: &GameDisplay<T>, p: (Word, Word), mut open: F) -> bool
+    where F: FnMut(&T) -> bool,
+{
+    let is_open = |p: &(Word, Word), open: &mut F| gd.get(p).map(&mut *open).unwrap_or(false);
+    is_open(&p, &mut open) && neighbours(p).filter(|n| is_open(n, &mut open)).count() > 2
+}
+
+#[cfg(test)]
+fn parse_example(s: &str) -> GameDisplay<char> {
+    let mut gd = GameDisplay::default();
+    for (y, line) in s.lines().enumerate() {
+        for (x, ch) in line.chars().enumerate() {
+            gd.insert(&(x as Word, y as Word), ch);
+        }
+    }
+    gd
+}
+
+#[test]
+fn corridor_with_door() {
+    let gd = parse_example("\
+#########
+#b.A.@.a#
+#########");
+
+    let graph = PoiGraph::<String>::build(&gd, |_, &ch| match ch {
+        '#' => Cell::Wall,
+        'a'..='z' | '@' => Cell::Interesting { stop: false },
+        _ => Cell::Open,
+    }, |crossed, _, &ch| if ch.is_ascii_uppercase() { crossed.push(ch) });
+
+    assert_eq!(graph.len(), 3);
+
+    let start = graph.node(&(5, 1)).unwrap();
+    let a = graph.node(&(7, 1)).unwrap();
+    let b = graph.node(&(1, 1)).unwrap();
+
+    assert_eq!(graph.edges(start), &[
+        Edge { to: a, steps: 2, crossed: String::new() },
+        Edge { to: b, steps: 4, crossed: String::from("A") },
+    ]);
+
+    // walking through the start continues to b
+    assert_eq!(graph.edges(a).last(), Some(&Edge { to: b, steps: 6, crossed: String::from("A") }));
+}
+
+#[test]
+fn stopping_at_intersections() {
+    let gd = parse_example("\
+#######
+#a...b#
+###.###
+###c###
+#######");
+
+    let open = |ch: &char| *ch != '#';
+    let fork = (3, 1);
+    assert!(is_intersection(&gd, fork, open));
+    assert!(!is_intersection(&gd, (2, 1), open));
+
+    let graph = PoiGraph::<()>::build(&gd, |p, ch| match ch {
+        '#' => Cell::Wall,
+        'a'..='z' => Cell::Interesting { stop: true },
+        _ if is_intersection(&gd, p, open) => Cell::Interesting { stop: true },
+        _ => Cell::Open,
+    }, |_, _, _| {});
+
+    assert_eq!(graph.len(), 4);
+    let fork = graph.node(&fork).unwrap();
+
+    let mut steps = graph.edges(fork).iter()
+        .map(|e| (graph.position(e.to), e.steps))
+        .collect::<Vec<_>>();
+    steps.sort();
+    assert_eq!(steps, vec![((1, 1), 2), ((3, 3), 2), ((5, 1), 2)]);
+
+    // the other walks stop at the fork
+    let a = graph.node(&(1, 1)).unwrap();
+    assert_eq!(graph.edges(a).iter().map(|e| e.to).collect::<Vec<_>>(), vec![fork]);
+}
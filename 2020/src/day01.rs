@@ -0,0 +1,90 @@
+use aoc_common::solution::{Error, Solution};
+use itertools::Itertools;
+use std::collections::BTreeSet;
+use std::io::BufRead;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = BTreeSet<u16>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse(input.as_bytes())
+    }
+
+    fn part_one(input: &Self::Input) -> Result<u32, Error> {
+        part_one(input).ok_or_else(|| "no two entries sum to 2020".into())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<u32, Error> {
+        part_two(input).ok_or_else(|| "no three entries sum to 2020".into())
+    }
+}
+
+// original best idea at the time was a bit different, but then I thought it might be a good
+// idea to focus on learning more about itertools this time aorund.
+//
+// part_one originally was a clone vec of input, sort it, binary search for the needed.
+// thought that maybe it's easier to just use BTreeSet but perhaps sorting it would be faster,
+// but I was expecting part_two to require sorted input as well.
+
+pub fn part_one(input: &BTreeSet<u16>) -> Option<u32> {
+    input
+        .iter()
+        .filter_map(|&small| {
+            if let Some(gt) = 2020u16.checked_sub(small) {
+                if input.contains(&gt) {
+                    Some((small, gt))
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        })
+        .map(|(a, b)| a as u32 * b as u32)
+        .next()
+}
+
+// originally just nested for-loops
+
+pub fn part_two(input: &BTreeSet<u16>) -> Option<u32> {
+    input
+        .iter()
+        .cartesian_product(input.iter())
+        .cartesian_product(input.iter())
+        .filter_map(|((a, b), c)| {
+            if a + b + c == 2020 {
+                Some((*a, *b, *c))
+            } else {
+                None
+            }
+        })
+        .map(|(a, b, c)| a as u32 * b as u32 * c as u32)
+        .next()
+}
+
+pub fn parse<R: BufRead>(mut input: R) -> Result<BTreeSet<u16>, Box<dyn std::error::Error + 'static>> {
+    let mut buf = String::new();
+    let mut ret = BTreeSet::new();
+    loop {
+        buf.clear();
+        let read = input.read_line(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        let num = buf.trim().parse::<u16>()?;
+        ret.insert(num);
+    }
+
+    Ok(ret)
+}
+
+#[test]
+fn example() {
+    let input = parse(&b"1721\n979\n366\n299\n675\n1456\n"[..]).unwrap();
+    assert_eq!(part_one(&input), Some(514579));
+    assert_eq!(part_two(&input), Some(241861950));
+}
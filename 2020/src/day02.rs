@@ -0,0 +1,108 @@
+use aoc_common::solution::{Error, Solution};
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<(Policy, String)>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse(input.as_bytes())
+    }
+
+    fn part_one(input: &Self::Input) -> Result<usize, Error> {
+        Ok(part_one(input))
+    }
+
+    fn part_two(input: &Self::Input) -> Result<usize, Error> {
+        Ok(part_two(input))
+    }
+}
+
+/// Passwords with the policy they should follow.
+pub fn parse<R: BufRead>(
+    mut input: R,
+) -> Result<Vec<(Policy, String)>, Box<dyn std::error::Error + 'static>> {
+    let re = regex::Regex::new(r"^(\d+)-(\d+) (\S): (\S+)$").unwrap();
+    let mut buf = String::new();
+    let mut ret = Vec::new();
+
+    loop {
+        buf.clear();
+        let read = input.read_line(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+
+        let buf = buf.trim();
+
+        if buf.is_empty() {
+            continue;
+        }
+
+        for cap in re.captures_iter(buf) {
+            let start = cap[1].parse::<u8>().expect("matched with re already");
+            let end = cap[2].parse::<u8>().expect("matched with re already");
+            let ch = cap[3].chars().next().unwrap();
+
+            ret.push((Policy(start..=end, ch), cap[4].to_owned()));
+        }
+    }
+
+    Ok(ret)
+}
+
+pub fn part_one(passwords: &[(Policy, String)]) -> usize {
+    passwords
+        .iter()
+        .filter(|(p, pw)| p.verify_contains(pw))
+        .count()
+}
+
+pub fn part_two(passwords: &[(Policy, String)]) -> usize {
+    passwords
+        .iter()
+        .filter(|(p, pw)| p.verify_at_indices(pw))
+        .count()
+}
+
+pub struct Policy(RangeInclusive<u8>, char);
+
+impl Policy {
+    fn verify_contains(&self, s: &str) -> bool {
+        let found = s.chars().filter(|&x| x == self.1).count();
+
+        assert!(found < 256);
+
+        self.0.contains(&(found as u8))
+    }
+
+    fn verify_at_indices(&self, s: &str) -> bool {
+        assert!(s.len() < 255); // because of +1
+        let found = s
+            .char_indices()
+            .filter(|&(_, ch)| ch == self.1)
+            // tripped on this quite confusingly: was using RangeInclusive::contains
+            .filter(|&(index, _)| {
+                let index = (index as u8) + 1;
+                &index == self.0.start() || &index == self.0.end()
+            })
+            .count();
+
+        // exactly one match at the set of indices
+        found == 1
+    }
+}
+
+#[test]
+fn part2_examples() {
+    assert!(Policy(1..=3, 'a').verify_at_indices("abcde"));
+    assert!(!Policy(1..=3, 'b').verify_at_indices("cdefg"));
+    assert!(!Policy(2..=9, 'c').verify_at_indices("ccccccccc"));
+    assert!(Policy(2..=9, 'c').verify_at_indices("bbbbbbbbc"));
+    assert!(!Policy(2..=9, 'c').verify_at_indices("bcbbbbbbc"));
+    assert!(Policy(2..=9, 'c').verify_at_indices("bcbbbbbbb"));
+}
@@ -0,0 +1,118 @@
+use aoc_common::solution::{Error, Solution};
+use std::io::BufRead;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    // the trees on all of the slopes are counted in a single pass over the map
+    type Input = Vec<usize>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        count_trees(input.as_bytes(), SLOPES)
+    }
+
+    fn part_one(counts: &Self::Input) -> Result<usize, Error> {
+        Ok(counts[1])
+    }
+
+    fn part_two(counts: &Self::Input) -> Result<usize, Error> {
+        Ok(counts.iter().product())
+    }
+}
+
+/// The slopes checked in the second part; the first part is only interested in `(3, 1)`.
+pub const SLOPES: &[(u8, u8)] = &[(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+/// Counts the trees on each of the slopes in a single pass over the map.
+pub fn count_trees<R: BufRead>(
+    mut input: R,
+    slopes: &[(u8, u8)],
+) -> Result<Vec<usize>, Box<dyn std::error::Error + 'static>> {
+    let mut buf = String::new();
+    let mut width = None;
+
+    let mut counters = slopes.iter().map(TreeCounter::from).collect::<Vec<_>>();
+
+    loop {
+        buf.clear();
+        let read = input.read_line(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+
+        let buf = buf.trim();
+
+        if let Some(width) = width.as_ref().copied() {
+            assert_eq!(buf.len() as i64, width);
+        } else {
+            width = Some(buf.len() as i64);
+        }
+
+        counters.iter_mut().for_each(|ctr| ctr.process(buf))
+    }
+
+    Ok(counters
+        .into_iter()
+        .map(|ctr| ctr.into_count())
+        .collect::<Vec<_>>())
+}
+
+struct TreeCounter {
+    slope: (u8, u8),
+    x: u8,
+    skip: u8,
+    trees: usize,
+}
+
+impl<'a> From<&'a (u8, u8)> for TreeCounter {
+    fn from(slope: &'a (u8, u8)) -> Self {
+        TreeCounter {
+            slope: (slope.0, slope.1),
+            x: 0,
+            // having to start this as 1 as in "skip 1" feels unnatural
+            skip: 1,
+            trees: 0,
+        }
+    }
+}
+
+impl TreeCounter {
+    fn process(&mut self, map: &str) {
+        self.skip -= 1;
+        if self.skip > 0 {
+            return;
+        }
+        let tree = map.as_bytes()[self.x as usize] == b'#';
+
+        if tree {
+            self.trees += 1;
+        }
+
+        self.x = (self.x + self.slope.0) % map.len() as u8;
+        self.skip = self.slope.1;
+    }
+
+    fn into_count(self) -> usize {
+        self.trees
+    }
+}
+
+#[test]
+fn example() {
+    let map = b"..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#
+";
+    let counts = count_trees(&map[..], SLOPES).unwrap();
+    assert_eq!(counts, vec![2, 7, 3, 4, 2]);
+}
@@ -0,0 +1,243 @@
+use aoc_common::io::Records;
+use aoc_common::solution::{Error, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{
+    collections::{HashMap, HashSet},
+    io::BufRead,
+};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    // the passports are validated in a single pass, counting both the ones with the required
+    // fields and the valid ones
+    type Input = (usize, usize, usize);
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        process(input.as_bytes())
+    }
+
+    fn part_one(counts: &Self::Input) -> Result<usize, Error> {
+        Ok(counts.1)
+    }
+
+    fn part_two(counts: &Self::Input) -> Result<usize, Error> {
+        Ok(counts.2)
+    }
+}
+
+/// Returns the number of records, the ones with all required fields and the valid ones.
+pub fn process<R: BufRead>(
+    input: R,
+) -> Result<(usize, usize, usize), Box<dyn std::error::Error + 'static>> {
+    let required = [
+        ("byr", validate_birth_year as fn(&str) -> bool),
+        ("iyr", validate_issue_year),
+        ("eyr", validate_exp_year),
+        ("hgt", validate_height),
+        ("hcl", validate_hair_color),
+        ("ecl", validate_eye_color),
+        ("pid", validate_passport_id),
+    ]
+    .iter()
+    .copied()
+    .collect::<HashMap<&str, fn(&str) -> bool>>();
+
+    assert_eq!(required.len(), 7);
+
+    let mut records = 0;
+    let mut part_one = 0;
+    let mut part_two = 0;
+
+    let mut passports = Records::new(input);
+
+    while let Some(record_buffer) = passports.read_next()? {
+        let (has_all, valid) = inspect_record(&record_buffer, &required);
+
+        if has_all {
+            part_one += 1;
+        }
+
+        if valid {
+            part_two += 1;
+        }
+
+        records += 1;
+    }
+
+    Ok((records, part_one, part_two))
+}
+
+fn inspect_record(record_buffer: &str, required: &HashMap<&str, fn(&str) -> bool>) -> (bool, bool) {
+    let found = record_buffer.split_whitespace().map(|field| {
+        let mut split = field.splitn(2, ':');
+        let key = split.next().unwrap();
+        let value = split.next().unwrap();
+        (key, value)
+    });
+
+    let mut valid = true;
+    let mut found_keys = HashSet::new();
+
+    for (key, value) in found {
+        if key == "cid" {
+            // optional
+            continue;
+        }
+
+        if !required[key](value) {
+            valid = false;
+        }
+
+        assert!(found_keys.insert(key));
+    }
+
+    let has_all = found_keys.len() == required.len();
+
+    (has_all, has_all && valid)
+}
+
+fn validate_birth_year(s: &str) -> bool {
+    if let Ok(y) = s.parse::<usize>() {
+        (1920..=2002).contains(&y)
+    } else {
+        false
+    }
+}
+
+fn validate_issue_year(s: &str) -> bool {
+    if let Ok(y) = s.parse::<usize>() {
+        (2010..=2020).contains(&y)
+    } else {
+        false
+    }
+}
+
+#[test]
+fn issue_year() {
+    assert!(validate_issue_year("2010"));
+    assert!(validate_issue_year("2015"));
+    assert!(validate_issue_year("2020"));
+    assert!(!validate_issue_year("2009"));
+    assert!(!validate_issue_year("2021"));
+    assert!(!validate_issue_year("2021abc"));
+}
+
+fn validate_exp_year(s: &str) -> bool {
+    if let Ok(y) = s.parse::<usize>() {
+        (2020..=2030).contains(&y)
+    } else {
+        false
+    }
+}
+
+fn validate_height(s: &str) -> bool {
+    lazy_static! {
+        static ref RE: Regex = Regex::new("^([0-9]+)(in|cm)$").unwrap();
+    }
+    if let Some(cap) = RE.captures_iter(s).next() {
+        let amount = cap[1].parse::<usize>();
+        let unit = &cap[2];
+
+        return match (unit, amount) {
+            ("cm", Ok(ref h)) if (150..=193).contains(h) => true,
+            ("in", Ok(ref h)) if (59..=76).contains(h) => true,
+            _ => false,
+        };
+    }
+
+    false
+}
+
+#[test]
+fn height() {
+    assert!(validate_height("59in"));
+    assert!(validate_height("60in"));
+    assert!(validate_height("70in"));
+    assert!(validate_height("150cm"));
+    assert!(validate_height("193cm"));
+    assert!(!validate_height("190in"));
+    assert!(!validate_height("190"));
+}
+
+fn validate_hair_color(s: &str) -> bool {
+    lazy_static! {
+        static ref RE: Regex = Regex::new("^#[0-9a-f]{6}$").unwrap();
+    }
+    RE.is_match(s)
+}
+
+fn validate_eye_color(s: &str) -> bool {
+    lazy_static! {
+        static ref RE: Regex = Regex::new("^(amb|blu|brn|gry|grn|hzl|oth)$").unwrap();
+    }
+    RE.is_match(s)
+}
+
+fn validate_passport_id(s: &str) -> bool {
+    lazy_static! {
+        static ref RE: Regex = Regex::new("^[0-9]{9}$").unwrap();
+    }
+    RE.is_match(s)
+}
+
+#[test]
+fn part_one_example() {
+    let (total, count, _) = process(std::io::BufReader::new(std::io::Cursor::new(
+        b"ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in",
+    )))
+    .unwrap();
+
+    assert_eq!(2, count);
+    assert_eq!(4, total);
+}
+
+#[test]
+fn part_two_examples() {
+    let (total, _, count) = process(std::io::BufReader::new(std::io::Cursor::new(
+        b"eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007
+
+pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719",
+    )))
+    .unwrap();
+    assert_eq!(4, count);
+    assert_eq!(8, total);
+}
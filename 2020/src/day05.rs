@@ -0,0 +1,225 @@
+use aoc_common::solution::{Error, Solution};
+use std::io::BufRead;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<u16>;
+    type PartOne = u16;
+    type PartTwo = u16;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        seat_ids(input.as_bytes())
+    }
+
+    fn part_one(ids: &Self::Input) -> Result<u16, Error> {
+        part_one(ids).ok_or_else(|| "no boarding passes".into())
+    }
+
+    fn part_two(ids: &Self::Input) -> Result<u16, Error> {
+        part_two(ids).ok_or_else(|| "no free seat in between".into())
+    }
+}
+
+/// Decodes all of the boarding passes.
+pub fn seat_ids<R: BufRead>(mut input: R) -> Result<Vec<u16>, Box<dyn std::error::Error + 'static>> {
+    let mut buffer = String::new();
+    let mut all = vec![];
+
+    loop {
+        buffer.clear();
+        let read = input.read_line(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+
+        all.push(find_seat_id(buffer.trim().as_bytes()));
+    }
+
+    Ok(all)
+}
+
+pub fn part_one(ids: &[u16]) -> Option<u16> {
+    ids.iter().copied().max()
+}
+
+/// Finds our seat, the only one missing in between the others.
+pub fn part_two(ids: &[u16]) -> Option<u16> {
+    // collect in order to sort, and find the missing one in between
+    let mut all = ids.to_vec();
+    all.sort_unstable();
+
+    all.windows(2)
+        .filter_map(|w| {
+            let earlier = w[0];
+            let later = w[1];
+            assert!(earlier < later);
+            if later - earlier == 2 {
+                Some(later - 1)
+            } else {
+                None
+            }
+        })
+        .fold(None, |acc, next| {
+            if acc.is_none() {
+                Some(next)
+            } else {
+                panic!("multiple results: {} and {}", acc.unwrap(), next);
+            }
+        })
+}
+
+pub fn find_seat_id(ins: &[u8]) -> u16 {
+    #[cfg(feature = "nightly")]
+    {
+        find_seat_id_simd(ins)
+    }
+    #[cfg(not(feature = "nightly"))]
+    {
+        find_seat_id_scalar(ins)
+    }
+}
+
+#[cfg(feature = "nightly")]
+#[no_mangle]
+pub fn find_seat_id_simd(ins: &[u8]) -> u16 {
+    use packed_simd::u8x16;
+
+    // 0b01000010
+    //            => 1
+    // 0b01010010
+    //
+    // 0b01000110
+    //            => 0
+    // 0b01001100
+    //
+    // mask idea?
+    // 0b00000100 => 1st => 0, 2 => 0, 3 => 0100, 4 => 0100
+    assert_eq!(ins.len(), 10);
+
+    let wz = 4; // we want these to be zeroes after negation
+
+    let all = u8x16::new(
+        ins[9], ins[8], ins[7], ins[6], ins[5], ins[4], ins[3], ins[2], ins[1], ins[0], wz, wz, wz,
+        wz, wz, wz,
+    );
+    let next = all & wz;
+
+    // now all B and R should be zeroes, F and B (and wz) will have 0x04
+
+    // now shift all 0x04 to become the highest bit; lowest might be set as well
+    let next = next << 5;
+
+    // bitmask of highest should be the negation
+    let bitmask = next.bitmask();
+    let id = !bitmask;
+
+    // this at least produces the right results but probably isn't much faster because de-inlining
+    // view asm with:
+    //
+    // RUSTFLAGS="-C target-cpu=native" cargo +nightly rustc --release -p aoc2020 --lib --features nightly -- --emit asm
+    //
+    // find the .s file with: find target/release -name '*.s'
+    //
+    //find_seat_id:
+    //        .cfi_startproc
+    //        subq    $104, %rsp
+    //        .cfi_def_cfa_offset 112
+    //        movq    %rsi, (%rsp)
+    //        cmpq    $10, %rsi
+    //        jne     .LBB19_1
+    //        movzbl  9(%rdi), %eax
+    //        vmovd   %eax, %xmm0
+    //        vpinsrb $1, 8(%rdi), %xmm0, %xmm0
+    //        movl    $4, %eax
+    //        vpinsrb $2, 7(%rdi), %xmm0, %xmm0
+    //        vpinsrb $3, 6(%rdi), %xmm0, %xmm0
+    //        vpinsrb $4, 5(%rdi), %xmm0, %xmm0
+    //        vpinsrb $5, 4(%rdi), %xmm0, %xmm0
+    //        vpinsrb $6, 3(%rdi), %xmm0, %xmm0
+    //        vpinsrb $7, 2(%rdi), %xmm0, %xmm0
+    //        vpinsrb $8, 1(%rdi), %xmm0, %xmm0
+    //        vpinsrb $9, (%rdi), %xmm0, %xmm0
+    //        vpinsrb $10, %eax, %xmm0, %xmm0
+    //        vpinsrb $11, %eax, %xmm0, %xmm0
+    //        vpinsrb $12, %eax, %xmm0, %xmm0
+    //        vpinsrb $13, %eax, %xmm0, %xmm0
+    //        vpinsrb $14, %eax, %xmm0, %xmm0
+    //        vpinsrb $15, %eax, %xmm0, %xmm0
+    //        vpsllw  $5, %xmm0, %xmm0
+    //        vpmovmskb       %xmm0, %eax
+    //        notl    %eax
+    //        addq    $104, %rsp
+    //        .cfi_def_cfa_offset 8
+    //        retq
+
+    id
+}
+
+pub fn find_seat_id_scalar(ins: &[u8]) -> u16 {
+    let pos = find_seat(ins);
+    (pos.0 as u16) << 3 | pos.1 as u16
+}
+
+fn find_seat(ins: &[u8]) -> (u8, u8) {
+    let mut main = ins.iter();
+
+    // just a binary number encoded with FB and LR for 01
+    // rewriting to use u8::from_str_radix would probably take too long
+    //
+    // >>> bin(ord('B'))
+    // '0b0100 0010'
+    // >>> bin(ord('R'))
+    // '0b0101 0010'
+
+    let row = main
+        .by_ref()
+        .copied()
+        .take(7)
+        .enumerate()
+        .inspect(|&(_, ch)| assert!(ch == b'F' || ch == b'B'))
+        .map(|(idx, ch)| if ch == b'B' { 1 << (7 - idx - 1) } else { 0 })
+        .fold(0u8, |acc, next| acc | next);
+
+    let seat = main
+        .by_ref()
+        .copied()
+        .take(3)
+        .enumerate()
+        .inspect(|&(_, ch)| assert!(ch == b'L' || ch == b'R'))
+        .map(|(idx, ch)| if ch == b'R' { 1 << (3 - idx - 1) } else { 0 })
+        .fold(0u8, |acc, next| acc | next);
+
+    assert_eq!(main.next(), None);
+
+    (row, seat)
+}
+
+#[test]
+fn seat_finding_example() {
+    let ins = b"FBFBBFFRLR";
+    assert_eq!(find_seat(ins), (44, 5));
+}
+
+#[test]
+fn seat_id_examples() {
+    let examples = [
+        (b"BFFFBBFRRR", 70, 7, 567),
+        (b"FFFBBBFRRR", 14, 7, 119),
+        (b"BBFFBBFRLL", 102, 4, 820),
+    ];
+
+    for (example, row, column, id) in &examples {
+        let pos = find_seat(&example[..]);
+        assert_eq!((*row, *column), pos);
+        assert_eq!(*id, (pos.0 as u16) << 3 | pos.1 as u16);
+    }
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+fn simd_agrees_with_scalar() {
+    for example in &[b"FBFBBFFRLR", b"BFFFBBFRRR", b"FFFBBBFRRR", b"BBFFBBFRLL"] {
+        assert_eq!(find_seat_id_simd(&example[..]), find_seat_id_scalar(&example[..]));
+    }
+}
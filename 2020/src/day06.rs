@@ -0,0 +1,89 @@
+use aoc_common::io::Records;
+use aoc_common::solution::{Error, Solution};
+use std::collections::HashSet;
+use std::io::BufRead;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    // both of the counts are summed in a single pass over the groups
+    type Input = (usize, usize);
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        process(input.as_bytes())
+    }
+
+    fn part_one(counts: &Self::Input) -> Result<usize, Error> {
+        Ok(counts.0)
+    }
+
+    fn part_two(counts: &Self::Input) -> Result<usize, Error> {
+        Ok(counts.1)
+    }
+}
+
+/// Sums the questions anyone and everyone answered yes to within each group.
+pub fn process<R: BufRead>(
+    input: R,
+) -> Result<(usize, usize), Box<dyn std::error::Error + 'static>> {
+    let mut questions_anyone_answered_yes: HashSet<u8> = HashSet::new();
+    let mut questions_everyone_answered_yes: HashSet<u8> = HashSet::new();
+    let mut current_person_answers = HashSet::new();
+
+    let mut part_one = 0;
+    let mut part_two = 0;
+
+    let mut groups = Records::new(input);
+
+    while let Some(group) = groups.read_next()? {
+        for (i, person) in group.lines().enumerate() {
+            // process one persons answers
+            current_person_answers.extend(person.trim().as_bytes().iter().copied());
+
+            if i == 0 {
+                questions_everyone_answered_yes.extend(current_person_answers.iter().copied());
+            } else {
+                questions_everyone_answered_yes.retain(|b| current_person_answers.contains(b));
+            }
+
+            questions_anyone_answered_yes.extend(current_person_answers.drain());
+        }
+
+        part_one += questions_anyone_answered_yes.len();
+        questions_anyone_answered_yes.clear();
+
+        part_two += questions_everyone_answered_yes.len();
+        questions_everyone_answered_yes.clear();
+    }
+
+    Ok((part_one, part_two))
+}
+
+#[test]
+fn example() {
+    let input = b"abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b
+";
+    assert_eq!(process(&input[..]).unwrap(), (11, 6));
+}
+
+#[test]
+fn crlf_and_repeated_blank_lines() {
+    let input = b"abc\r\n\r\n\r\na\r\nb\r\nc\r\n\r\nab\r\nac";
+    assert_eq!(process(&input[..]).unwrap(), (9, 4));
+}
@@ -0,0 +1,83 @@
+use crate::vm::{self, Instruction, InstructionSet, Machine, Next, Opcode, Operand, Outcome};
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<Instruction>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse(input)?)
+    }
+
+    /// The accumulator before the first instruction would be executed twice.
+    fn part_one(code: &Self::Input) -> Result<i64, Error> {
+        let mut machine = Machine::new(&BOOT_CODE, code).detect_loops();
+        match machine.run() {
+            Outcome::Looped(_) => Ok(machine.registers[0]),
+            x => Err(format!("boot code did not loop: {:?}", x).into()),
+        }
+    }
+
+    /// The accumulator after the boot code terminates with the corrupted instruction fixed.
+    fn part_two(code: &Self::Input) -> Result<i64, Error> {
+        let nop = BOOT_CODE.opcode("nop").expect("boot code has nop");
+        let jmp = BOOT_CODE.opcode("jmp").expect("boot code has jmp");
+
+        // the corrupted instruction is a jmp which should be a nop or the other way around
+        let (_, machine) = vm::find_terminating_patch(&BOOT_CODE, code, |i| {
+            if i.opcode == nop {
+                Some(Instruction { opcode: jmp, ..*i })
+            } else if i.opcode == jmp {
+                Some(Instruction { opcode: nop, ..*i })
+            } else {
+                None
+            }
+        })
+        .ok_or("could not find a replacement")?;
+
+        Ok(machine.registers[0])
+    }
+}
+
+/// The handheld game console with the accumulator as the only register.
+pub const BOOT_CODE: InstructionSet = InstructionSet {
+    opcodes: &[
+        Opcode {
+            name: "nop",
+            operands: &[Operand::Immediate],
+            execute: |_, _| Next::Step,
+        },
+        Opcode {
+            name: "acc",
+            operands: &[Operand::Immediate],
+            execute: |acc, args| {
+                acc[0] += args[0];
+                Next::Step
+            },
+        },
+        Opcode {
+            name: "jmp",
+            operands: &[Operand::Immediate],
+            execute: |_, args| Next::Relative(args[0]),
+        },
+    ],
+    registers: 1,
+};
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    BOOT_CODE.parse(input)
+}
+
+#[test]
+fn example() {
+    use aoc_common::solution::solve;
+
+    let input = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+    let answers = solve::<Day08>(input).unwrap();
+    assert_eq!(answers.part_one.as_deref(), Some("5"));
+    assert_eq!(answers.part_two.as_deref(), Some("8"));
+}
@@ -0,0 +1,396 @@
+use aoc_common::geometry::Point2;
+use aoc_common::grid::{Dimensions, Grid};
+use aoc_common::solution::{Error, Solution};
+#[cfg(test)]
+use std::fmt;
+use std::io::BufRead;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = (usize, Vec<Spot>);
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        process(input.as_bytes())
+    }
+
+    fn part_one((width, map): &Self::Input) -> Result<usize, Error> {
+        Ok(gol_until_settled(
+            RuleSet::PartOne,
+            *width,
+            map.len() / width,
+            map,
+        ))
+    }
+
+    fn part_two((width, map): &Self::Input) -> Result<usize, Error> {
+        Ok(gol_until_settled(
+            RuleSet::PartTwo,
+            *width,
+            map.len() / width,
+            map,
+        ))
+    }
+}
+
+pub enum RuleSet {
+    PartOne,
+    PartTwo,
+}
+
+impl RuleSet {
+    fn count_adjacent_taken(
+        &self,
+        old: &[Spot],
+        coord: Point2,
+        width: usize,
+        height: usize,
+    ) -> usize {
+        (match self {
+            RuleSet::PartOne => vanilla_taken,
+            RuleSet::PartTwo => directional_taken,
+        })(old, coord, width, height)
+    }
+
+    fn too_many_occupied_seats(&self) -> usize {
+        match self {
+            RuleSet::PartOne => 4,
+            RuleSet::PartTwo => 5,
+        }
+    }
+}
+
+fn vanilla_taken(old: &[Spot], coord: Point2, width: usize, height: usize) -> usize {
+    // just look at each direction
+    let dimensions = Dimensions::new(width, height);
+    coord
+        .neighbours()
+        .filter_map(|p| dimensions.to_index(&p))
+        .filter(|&idx| old[idx] == Spot::TakenSeat)
+        .count()
+}
+
+fn directional_taken(old: &[Spot], coord: Point2, width: usize, height: usize) -> usize {
+    let dimensions = Dimensions::new(width, height);
+    Point2::ORIGIN
+        .neighbours()
+        .inspect(|_dir| {
+            #[cfg(test)]
+            println!("looking at direction {:?}", _dir);
+        })
+        .filter_map(|dir| {
+            // walk multiple into this direction while on the map
+            let mut first = dimensions
+                .ray(coord, dir)
+                .map(|(idx, coord)| (old[idx], coord))
+                .inspect(|(_spot, _at_coord)| {
+                    #[cfg(test)]
+                    println!("  {:?} looking at {:?} at {:?}", coord, _spot, _at_coord)
+                })
+                // filter out the floors so that the first is either a seat or nothing
+                .filter(|&(spot, _)| spot != Spot::Floor);
+
+            // we want to find the number of taken seats in every direction so use one and zero
+            // to be able to sum them up
+            first.next().map(|(kind, _)| match kind {
+                Spot::Floor => unreachable!(),
+                Spot::TakenSeat => 1,
+                Spot::EmptySeat => 0,
+            })
+        })
+        .inspect(|_count| {
+            #[cfg(test)]
+            println!("  --> {:?}", _count);
+        })
+        .sum::<usize>()
+}
+
+pub fn gol_until_settled(rules: RuleSet, width: usize, height: usize, map: &[Spot]) -> usize {
+    let mut first = map.to_vec();
+    let mut second = map.to_vec();
+
+    let mut old = &mut first;
+    let mut new = &mut second;
+
+    for _i in 0.. {
+        gol_round(&rules, old, new, width, height);
+        #[cfg(test)]
+        println!("\n{:?}", MapDebug(new, width));
+
+        if old == new {
+            break;
+        }
+
+        std::mem::swap(&mut old, &mut new);
+
+        #[cfg(test)]
+        if _i > 7 {
+            panic!();
+        }
+    }
+
+    first.iter().filter(|&&s| s == Spot::TakenSeat).count()
+}
+
+fn gol_round(rules: &RuleSet, old: &[Spot], new: &mut [Spot], width: usize, height: usize) {
+    let seat_adjacent_counts = old
+        .iter()
+        .zip(Dimensions::new(width, height).points())
+        .map(|(spot, coord)| {
+            if *spot == Spot::Floor {
+                (Spot::Floor, 0, coord)
+            } else {
+                let count = rules.count_adjacent_taken(&old, coord, width, height);
+                assert!(count <= 8);
+                (*spot, count, coord)
+            }
+        })
+        .inspect(|(_spot, _count, _coord)| {
+            #[cfg(test)]
+            {
+                if _coord.x() == 0 && _coord.y() > 0 {
+                    println!();
+                }
+
+                match _spot {
+                    Spot::Floor => print!("."),
+                    _ => print!("{}", _count),
+                }
+            }
+        })
+        .map(|(spot, count, _)| (spot, count));
+
+    for (target, (current_spot, count)) in new.iter_mut().zip(seat_adjacent_counts) {
+        *target = match current_spot {
+            Spot::TakenSeat if count >= rules.too_many_occupied_seats() => Spot::EmptySeat,
+            Spot::EmptySeat if count == 0 => Spot::TakenSeat,
+            Spot::Floor => {
+                assert_eq!(count, 0);
+                Spot::Floor
+            }
+            x => x,
+        };
+    }
+
+    #[cfg(test)]
+    println!("\n");
+}
+
+/// Reads the width and the spots of the seat layout.
+pub fn process<I: BufRead>(
+    input: I,
+) -> Result<(usize, Vec<Spot>), Box<dyn std::error::Error + 'static>> {
+    let grid = Grid::from_reader(input, |ch| {
+        Ok::<_, std::convert::Infallible>(match ch {
+            b'.' => Spot::Floor,
+            b'L' => Spot::EmptySeat,
+            #[cfg(test)]
+            b'#' => Spot::TakenSeat,
+            x => unreachable!("invalid byte {}", x),
+        })
+    })?;
+
+    Ok((grid.width(), grid.into_cells()))
+}
+
+#[cfg(test)]
+struct MapDebug<'a>(&'a [Spot], usize);
+
+#[cfg(test)]
+impl<'a> fmt::Debug for MapDebug<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+            .iter()
+            .zip(Dimensions::new(self.1, self.0.len() / self.1).points())
+            .try_for_each(|(spot, p)| {
+                if p.x() == 0 {
+                    writeln!(fmt)?;
+                }
+                write!(
+                    fmt,
+                    "{}",
+                    match spot {
+                        Spot::Floor => '.',
+                        Spot::TakenSeat => '#',
+                        Spot::EmptySeat => 'L',
+                    }
+                )
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Spot {
+    Floor,
+    EmptySeat,
+    TakenSeat,
+}
+
+#[test]
+fn first_example() {
+    let input = b"L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+    let (width, map) = process(std::io::BufReader::new(std::io::Cursor::new(input))).unwrap();
+    let height = map.len() / width;
+
+    assert_eq!(gol_until_settled(RuleSet::PartOne, width, height, &map), 37);
+}
+
+#[test]
+fn second_example() {
+    let input = b"L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+    let (width, map) = process(std::io::BufReader::new(std::io::Cursor::new(input))).unwrap();
+    let height = map.len() / width;
+
+    let mut first = map.to_vec();
+    let mut second = map.to_vec();
+
+    let mut old = &mut first;
+    let mut new = &mut second;
+
+    let phases = [
+        "#.##.##.##
+#######.##
+#.#.#..#..
+####.##.##
+#.##.##.##
+#.#####.##
+..#.#.....
+##########
+#.######.#
+#.#####.##",
+        "#.LL.LL.L#
+#LLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLL#
+#.LLLLLL.L
+#.LLLLL.L#",
+        "#.L#.##.L#
+#L#####.LL
+L.#.#..#..
+##L#.##.##
+#.##.#L.##
+#.#####.#L
+..#.#.....
+LLL####LL#
+#.L#####.L
+#.L####.L#",
+        "#.L#.L#.L#
+#LLLLLL.LL
+L.L.L..#..
+##LL.LL.L#
+L.LL.LL.L#
+#.LLLLL.LL
+..L.L.....
+LLLLLLLLL#
+#.LLLLL#.L
+#.L#LL#.L#",
+        "#.L#.L#.L#
+#LLLLLL.LL
+L.L.L..#..
+##L#.#L.L#
+L.L#.#L.L#
+#.L####.LL
+..#.#.....
+LLL###LLL#
+#.LLLLL#.L
+#.L#LL#.L#",
+        "#.L#.L#.L#
+#LLLLLL.LL
+L.L.L..#..
+##L#.#L.L#
+L.L#.LL.L#
+#.LLLL#.LL
+..#.L.....
+LLL###LLL#
+#.LLLLL#.L
+#.L#LL#.L#",
+    ];
+
+    for (i, &phase) in phases.iter().enumerate() {
+        gol_round(&RuleSet::PartTwo, old, new, width, height);
+
+        let got = format!("{:?}", MapDebug(new, width));
+        let got = got.trim();
+
+        for (a, b) in got.lines().zip(phase.lines()) {
+            println!("{}\t{}", a, b);
+        }
+        println!();
+
+        if got != phase {
+            panic!("round {} failed", i);
+        }
+
+        std::mem::swap(&mut old, &mut new);
+    }
+
+    assert_eq!(gol_until_settled(RuleSet::PartTwo, width, height, &map), 26);
+}
+
+#[test]
+fn part_two_directional_examples() {
+    let map0 = b".......#.
+...#.....
+.#.......
+.........
+..#L....#
+....#....
+.........
+#........
+...#.....";
+    let map1 = b".............
+.L.L.#.#.#.#.
+.............";
+    let map2 = b".##.##.
+#.#.#.#
+##...##
+...L...
+##...##
+#.#.#.#
+.##.##.";
+
+    let examples = [
+        (&map0[..], (3, 4), 8),
+        (&map1[..], (1, 1), 0),
+        (&map2[..], (3, 3), 0),
+    ];
+
+    for &(input, pos, occupied_seats) in &examples {
+        let (width, map) = process(std::io::BufReader::new(std::io::Cursor::new(input))).unwrap();
+        let height = map.len() / width;
+
+        let found = directional_taken(&map, Point2::from(pos), width, height);
+
+        if found != occupied_seats {
+            println!("{:?}", MapDebug(&map, width));
+        }
+
+        assert_eq!(found, occupied_seats);
+    }
+}
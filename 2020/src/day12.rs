@@ -0,0 +1,206 @@
+use aoc_common::geometry::{Direction, Point2};
+use aoc_common::io::OnePerLine;
+use aoc_common::solution::{Error, Solution};
+use either::Either;
+use std::io::BufRead;
+use std::str::FromStr;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Op>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse(input.as_bytes())
+    }
+
+    fn part_one(ops: &Self::Input) -> Result<i64, Error> {
+        Ok(part_one(ops))
+    }
+
+    fn part_two(ops: &Self::Input) -> Result<i64, Error> {
+        Ok(part_two(ops))
+    }
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<Vec<Op>, Box<dyn std::error::Error + 'static>> {
+    OnePerLine::<_, Op>::new(input)
+        .map(|op| {
+            op.map_err(|e| match e {
+                Either::Left(()) => "invalid instruction".into(),
+                Either::Right(e) => e.into(),
+            })
+        })
+        .collect()
+}
+
+pub fn part_one(ops: &[Op]) -> i64 {
+    let mut ship = Ship::default();
+    ops.iter().for_each(|&op| ship.execute(op));
+    ship.manhattan_distance()
+}
+
+// had misunderstood how left and right work
+// rotations took some time thanks to imaging a contrived coordinate system
+
+pub fn part_two(ops: &[Op]) -> i64 {
+    let mut ship = WaypointGuidedShip::default();
+    ops.iter().for_each(|&op| ship.execute(op));
+    ship.manhattan_distance()
+}
+
+// directions are mapped so that west is right, and north is up
+#[derive(Debug)]
+struct Ship {
+    position: Point2,
+    direction: i16,
+}
+
+impl Default for Ship {
+    fn default() -> Ship {
+        Ship {
+            position: Point2::ORIGIN,
+            direction: 270,
+        }
+    }
+}
+
+impl Ship {
+    fn execute(&mut self, op: Op) {
+        use Op::*;
+        match op {
+            Move(dir, amt) => self.do_move(dir, amt),
+            Turn(LeftRight::Left, amt) => {
+                self.direction += amt;
+            }
+            Turn(LeftRight::Right, amt) => {
+                self.direction -= amt;
+            }
+            Forward(amt) => {
+                let dir = self.direction.rem_euclid(360);
+                let dir = direction_from_heading(dir).expect("uneven");
+                self.do_move(dir, amt)
+            }
+        }
+    }
+
+    fn do_move(&mut self, dir: Direction, amt: i16) {
+        self.position += dir.to_point() * i64::from(amt);
+    }
+
+    fn manhattan_distance(self) -> i64 {
+        self.position.manhattan_distance(&Point2::ORIGIN)
+    }
+}
+
+#[derive(Debug)]
+struct WaypointGuidedShip {
+    waypoint: Point2,
+    position: Point2,
+}
+
+impl Default for WaypointGuidedShip {
+    fn default() -> WaypointGuidedShip {
+        WaypointGuidedShip {
+            waypoint: Point2::new([-10, -1]),
+            position: Point2::ORIGIN,
+        }
+    }
+}
+
+impl WaypointGuidedShip {
+    fn execute(&mut self, op: Op) {
+        use Op::*;
+        match op {
+            Move(dir, amt) => {
+                self.waypoint += dir.to_point() * i64::from(amt);
+            }
+            Turn(d, amt) => {
+                // R90 -- clockwise 90
+                // from: (-10, -4)
+                // to:   ( -4, 10)
+
+                assert_eq!(amt % 90, 0);
+                let times = amt / 90;
+
+                for _ in 0..times {
+                    // apologies, not good with geometry
+                    let (x, y) = self.waypoint.into();
+                    if d == LeftRight::Right {
+                        self.waypoint = Point2::new([y, -x]);
+                    } else {
+                        self.waypoint = Point2::new([-y, x]);
+                    }
+                }
+            }
+            Forward(amt) => {
+                self.position += self.waypoint * i64::from(amt);
+            }
+        }
+    }
+
+    fn manhattan_distance(self) -> i64 {
+        self.position.manhattan_distance(&Point2::ORIGIN)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Op {
+    Move(Direction, i16),
+    Turn(LeftRight, i16),
+    Forward(i16),
+}
+
+impl FromStr for Op {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let amount = s[1..].parse::<i16>().map_err(|_| ())?;
+
+        Ok(match s.as_bytes()[0] {
+            b'N' | b'W' | b'S' | b'E' => Op::Move(parse_direction(&s[0..1])?, amount),
+            b'L' | b'R' => Op::Turn(LeftRight::from_str(&s[0..1])?, amount),
+            b'F' => Op::Forward(amount),
+            _ => return Err(()),
+        })
+    }
+}
+
+fn direction_from_heading(heading: i16) -> Result<Direction, i16> {
+    Ok(match heading {
+        0 => Direction::Up,
+        90 => Direction::Right,
+        180 => Direction::Down,
+        270 => Direction::Left,
+        x => return Err(x),
+    })
+}
+
+fn parse_direction(s: &str) -> Result<Direction, ()> {
+    Ok(match s.as_bytes()[0] {
+        b'N' => Direction::Up,
+        b'W' => Direction::Right,
+        b'S' => Direction::Down,
+        b'E' => Direction::Left,
+        _ => return Err(()),
+    })
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LeftRight {
+    Left,
+    Right,
+}
+
+impl FromStr for LeftRight {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use LeftRight::*;
+        Ok(match s.as_bytes()[0] {
+            b'L' => Left,
+            b'R' => Right,
+            _ => return Err(()),
+        })
+    }
+}
@@ -0,0 +1,128 @@
+use aoc_common::math::chinese_remainder;
+use aoc_common::solution::{Error, Solution};
+use either::Either;
+use std::io::BufRead;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Notes;
+    type PartOne = usize;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Notes::read(input.as_bytes())
+    }
+
+    fn part_one(notes: &Self::Input) -> Result<usize, Error> {
+        Ok(part_one(notes))
+    }
+
+    fn part_two(notes: &Self::Input) -> Result<u64, Error> {
+        part_two(notes).ok_or_else(|| "the buslines never depart one after another".into())
+    }
+}
+
+/// Multiplies the earliest busline we can take with the minutes we need to wait for it.
+pub fn part_one(notes: &Notes) -> usize {
+    let (earliest_busline, ttl): (usize, usize) = notes
+        .buslines
+        .iter()
+        .filter_map(|e| e.as_ref().left())
+        .map(|x| {
+            (
+                *x,
+                notes.first_timestamp_to_depart + x - (notes.first_timestamp_to_depart % x),
+            )
+        })
+        .min_by_key(|&x| x.1)
+        .expect("multiple buslines => min must exist");
+
+    (ttl - notes.first_timestamp_to_depart) * earliest_busline
+}
+
+/// Finds the earliest timestamp at which the buslines depart one after another at the offsets
+/// listed.
+pub fn part_two(notes: &Notes) -> Option<u64> {
+    let congruences = notes
+        .buslines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, e)| e.as_ref().left().map(|e| (i, *e as u64)))
+        .map(|(t_plus, busline)| {
+            // t_plus is the minute offset for this busline from a timestamp after which all busses
+            // leave one each minute, so at ts: (ts + t_plus) % busline == 0, or:
+            //
+            // ts % busline == (busline - t_plus) % busline
+            let remainder = (busline as i64 - t_plus as i64).rem_euclid(busline as i64);
+            (remainder as u64, busline)
+        })
+        .collect::<Vec<_>>();
+
+    chinese_remainder(&congruences).map(|(ts, _)| ts)
+}
+
+pub struct Notes {
+    first_timestamp_to_depart: usize,
+    buslines: Vec<Either<usize, X>>,
+}
+
+impl Notes {
+    pub fn read<R: BufRead>(mut input: R) -> Result<Self, Box<dyn std::error::Error + 'static>> {
+        let mut line = String::new();
+
+        let read = input.read_line(&mut line)?;
+        if read == 0 {
+            return Err("missing timestamp line".into());
+        }
+
+        let first_timestamp_to_depart = line.trim().parse::<usize>()?;
+
+        line.clear();
+
+        let read = input.read_line(&mut line)?;
+        if read == 0 {
+            return Err("missing buslines line".into());
+        }
+
+        let buslines = line
+            .trim()
+            .split(',')
+            .map::<Result<Either<usize, X>, <usize as std::str::FromStr>::Err>, _>(|part| {
+                Ok(if part == "x" {
+                    Either::Right(X)
+                } else {
+                    Either::Left(part.parse::<usize>()?)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Notes {
+            first_timestamp_to_depart,
+            buslines,
+        })
+    }
+}
+
+// the x which appears in the notes
+pub struct X;
+
+#[test]
+fn example() {
+    use aoc_common::solution::solve;
+
+    let answers = solve::<Day13>("939\n7,13,x,x,59,x,31,19\n").unwrap();
+    assert_eq!(answers.part_one.as_deref(), Some("295"));
+    assert_eq!(answers.part_two.as_deref(), Some("1068781"));
+}
+
+#[test]
+fn missing_lines() {
+    use aoc_common::solution::solve;
+
+    let e = solve::<Day13>("").unwrap_err();
+    assert_eq!(e.to_string(), "missing timestamp line");
+
+    let e = solve::<Day13>("939\n").unwrap_err();
+    assert_eq!(e.to_string(), "missing buslines line");
+}
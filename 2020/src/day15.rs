@@ -0,0 +1,80 @@
+use aoc_common::solution::{Error, Solution};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    // the input is the starting numbers separated by commas
+    type Input = Vec<u32>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input
+            .trim()
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part_one(starting_numbers: &Self::Input) -> Result<u32, Error> {
+        Ok(process(starting_numbers, 2020))
+    }
+
+    fn part_two(starting_numbers: &Self::Input) -> Result<u32, Error> {
+        Ok(process(starting_numbers, 30_000_000))
+    }
+}
+
+/// The `nth` number spoken in the memory game.
+pub fn process(starting_numbers: &[u32], nth: u32) -> u32 {
+    //use std::collections::{btree_map::Entry, BTreeMap as Collection};
+    use std::collections::{hash_map::Entry, HashMap as Collection};
+    let mut last_turn = Collection::new();
+
+    starting_numbers
+        .iter()
+        .enumerate()
+        .for_each(|(i, s)| assert_eq!(last_turn.insert(*s, i as u32 + 1), None));
+
+    let mut last = starting_numbers[starting_numbers.len() - 1];
+    for turn in ((starting_numbers.len() + 1) as u32).. {
+        // print!("{} following {} => ", turn, last);
+        match last_turn.entry(last) {
+            Entry::Vacant(ve) => {
+                // last starting number was the first time it was spoken
+                ve.insert(turn - 1);
+                last = 0;
+            }
+            Entry::Occupied(mut oe) => {
+                let slot = oe.get_mut();
+                let when = *slot;
+                *slot = turn - 1;
+
+                last = turn - 1 - when;
+            }
+        }
+        //println!("{}", last);
+        if turn as u32 == nth {
+            return last;
+        }
+    }
+
+    todo!();
+}
+
+#[test]
+fn part_one_examples() {
+    let examples = [
+        ([0, 3, 6], 2020, 436),
+        ([1, 3, 2], 2020, 1),
+        ([2, 1, 3], 2020, 10),
+        ([1, 2, 3], 2020, 27),
+        ([2, 3, 1], 2020, 78),
+        ([3, 2, 1], 2020, 438),
+        ([3, 1, 2], 2020, 1836),
+    ];
+
+    for (starting_numbers, nth, expected) in &examples {
+        assert_eq!(process(&starting_numbers[..], *nth), *expected);
+    }
+}
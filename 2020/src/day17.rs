@@ -0,0 +1,175 @@
+use aoc_common::geometry::Point;
+use aoc_common::solution::{Error, Solution};
+use std::collections::{HashMap, HashSet};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = String;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.to_owned())
+    }
+
+    fn part_one(input: &Self::Input) -> Result<usize, Error> {
+        Ok(boot::<3>(input))
+    }
+
+    fn part_two(input: &Self::Input) -> Result<usize, Error> {
+        Ok(boot::<4>(input))
+    }
+}
+
+/// Number of active cubes after the six boot cycles in `N` dimensions.
+pub fn boot<const N: usize>(input: &str) -> usize {
+    let mut world = World::<N>::default();
+    parse_slice(input, Point::ORIGIN, &mut world);
+    n_gol_rounds(&mut world, 6);
+
+    world.len()
+}
+
+pub fn n_gol_rounds<const N: usize>(world: &mut World<N>, n: usize) {
+    let mut first = world.to_owned();
+    let mut second = world;
+    let mut scratch = HashMap::new();
+
+    // round one
+    for _ in 0..n {
+        gol_round(&first, &mut second, &mut scratch);
+
+        std::mem::swap(&mut first, &mut second);
+    }
+
+    if n % 2 == 0 {
+        std::mem::swap(&mut first, &mut second);
+    }
+}
+
+fn gol_round<const N: usize>(
+    old: &World<N>,
+    new: &mut World<N>,
+    discovered_neighbours: &mut HashMap<Point<N>, usize>,
+) {
+    new.clear();
+    discovered_neighbours.clear();
+
+    for point in &old.inner {
+        let mut current_neighbours = 0;
+        for neighbour in point.neighbours() {
+            let their_neighbours = discovered_neighbours.entry(neighbour).or_insert(0);
+            // one for being the neighbour of the current
+            *their_neighbours += 1;
+            if old.contains(&neighbour) {
+                current_neighbours += 1;
+            }
+        }
+
+        if current_neighbours == 2 || current_neighbours == 3 {
+            new.insert(*point);
+        }
+    }
+
+    for (point, count) in discovered_neighbours.drain() {
+        // how to process only those we haven't already processed from the old world?
+        if old.contains(&point) {
+            continue;
+        }
+
+        if count == 3 {
+            new.insert(point);
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct World<const N: usize> {
+    inner: HashSet<Point<N>>,
+}
+
+impl<const N: usize> World<N> {
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn insert(&mut self, p: Point<N>) -> bool {
+        self.inner.insert(p)
+    }
+
+    fn contains(&self, p: &Point<N>) -> bool {
+        self.inner.contains(p)
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+}
+
+/// Parses the slice to the first two axis starting from `start`, returns the width and height of
+/// the slice parsed
+pub fn parse_slice<const N: usize>(s: &str, start: Point<N>, target: &mut World<N>) -> (i64, i64) {
+    let mut w = None;
+
+    let mut y = start[1];
+
+    for line in s.lines() {
+        if let Some(w) = w {
+            assert_eq!(line.len(), w);
+        } else {
+            w = Some(line.len());
+            assert_eq!(line.len(), line.trim().len());
+        }
+
+        line.as_bytes()
+            .iter()
+            .inspect(|&&ch| assert!(ch == b'#' || ch == b'.'))
+            .zip(start[0]..)
+            .filter_map(|(&ch, x)| {
+                if ch == b'#' {
+                    let mut p = start;
+                    p[0] = x;
+                    p[1] = y;
+                    Some(p)
+                } else {
+                    None
+                }
+            })
+            .for_each(|p| assert!(target.insert(p)));
+
+        y += 1;
+    }
+
+    (w.unwrap() as i64, y - start[1])
+}
+
+#[test]
+fn first_example() {
+    let mut world = World::<3>::default();
+
+    let (w, h) = parse_slice(".#.\n..#\n###", Point([-1, -1, 0]), &mut world);
+    assert_eq!((w, h), (3, 3));
+
+    let mut first = world.clone();
+    let mut second = world;
+    let mut scratch = HashMap::new();
+
+    // round one
+    for _ in 1..=6 {
+        gol_round(&first, &mut second, &mut scratch);
+
+        std::mem::swap(&mut first, &mut second);
+    }
+
+    // initially got 155; there seems to be zero kept active at all six
+    assert_eq!(first.inner.len(), 112);
+}
+
+#[test]
+fn first_example_four_dimensional() {
+    let mut world = World::<4>::default();
+    parse_slice(".#.\n..#\n###", Point::ORIGIN, &mut world);
+    n_gol_rounds(&mut world, 6);
+    assert_eq!(world.len(), 848);
+}
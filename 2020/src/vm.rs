@@ -0,0 +1,378 @@
+//! Register machine for the puzzles with assembly like programs, such as the boot code of day 8.
+//!
+//! The instruction set is a table of [`Opcode`]s, each with a name, the kinds of its arguments and
+//! a function executing it on the registers. Programs are parsed against the table and run by a
+//! [`Machine`], which can detect loops by remembering the instructions it has executed, run with a
+//! single instruction patched and record a trace.
+
+use aoc_common::parse::{self, Cursor, ParseError};
+use bitvec::{order::LocalBits, slice::BitSlice, vec::BitVec};
+use std::convert::TryFrom;
+
+/// Most arguments any instruction can have.
+pub const MAX_ARGUMENTS: usize = 3;
+
+/// Where the execution continues after an instruction.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Next {
+    /// The following instruction.
+    Step,
+    /// Instruction at an offset from the current one.
+    Relative(i64),
+    /// Instruction at an index in the program.
+    Absolute(i64),
+}
+
+/// Kind of an argument of an instruction.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Operand {
+    /// Index of a register, checked against the number of registers when parsing.
+    Register,
+    /// Any number.
+    Immediate,
+}
+
+pub struct Opcode {
+    pub name: &'static str,
+    /// Kinds of the arguments following the name.
+    pub operands: &'static [Operand],
+    /// Executes the instruction with the given arguments on the registers.
+    pub execute: fn(&mut [i64], &[i64]) -> Next,
+}
+
+/// Table of the opcodes and the number of registers of a machine.
+pub struct InstructionSet {
+    pub opcodes: &'static [Opcode],
+    pub registers: usize,
+}
+
+impl InstructionSet {
+    /// Parses a program of one instruction per line, with the arguments separated by whitespace.
+    pub fn parse(&self, input: &str) -> Result<Vec<Instruction>, ParseError> {
+        parse::lines(input, |c| self.parse_instruction(c))
+    }
+
+    fn parse_instruction(&self, c: &mut Cursor) -> Result<Instruction, ParseError> {
+        let start = c.clone();
+        let name = c.word("an instruction")?;
+        let opcode = match self.opcode(name) {
+            Some(opcode) => opcode,
+            None => {
+                let names = self
+                    .opcodes
+                    .iter()
+                    .map(|op| format!("`{}`", op.name))
+                    .collect::<Vec<_>>();
+                return Err(start.error(format!("one of {}", names.join(", "))));
+            }
+        };
+
+        let mut args = [0; MAX_ARGUMENTS];
+        for (arg, operand) in args.iter_mut().zip(self.opcodes[opcode].operands) {
+            c.literal(" ")?;
+            c.skip_whitespace();
+            let start = c.clone();
+            *arg = c.number()?;
+
+            if *operand == Operand::Register && !(0..self.registers as i64).contains(arg) {
+                return Err(start.error(format!(
+                    "a register from 0 to {}",
+                    self.registers as i64 - 1
+                )));
+            }
+        }
+
+        Ok(Instruction { opcode, args })
+    }
+
+    /// Index of the opcode called `name`.
+    pub fn opcode(&self, name: &str) -> Option<usize> {
+        self.opcodes.iter().position(|op| op.name == name)
+    }
+}
+
+/// Instruction of a program, with the opcode as an index to the instruction set.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Instruction {
+    pub opcode: usize,
+    pub args: [i64; MAX_ARGUMENTS],
+}
+
+/// How a run of a [`Machine`] stopped.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Outcome {
+    /// Program counter moved to the instruction right after the last one.
+    Terminated,
+    /// Instruction at the index would had been executed for the second time, only detected by a
+    /// machine created with [`Machine::detect_loops`].
+    Looped(usize),
+    /// Program counter moved elsewhere outside of the program.
+    Escaped(i64),
+}
+
+/// Instruction executed during a traced run, with the registers after it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TraceStep {
+    pub pc: usize,
+    pub registers: Vec<i64>,
+}
+
+pub struct Machine<'a> {
+    set: &'a InstructionSet,
+    program: &'a [Instruction],
+    patch: Option<(usize, Instruction)>,
+    pub pc: usize,
+    pub registers: Vec<i64>,
+    visited: Option<BitVec<LocalBits, usize>>,
+    trace: Option<Vec<TraceStep>>,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(set: &'a InstructionSet, program: &'a [Instruction]) -> Self {
+        Machine {
+            set,
+            program,
+            patch: None,
+            pc: 0,
+            registers: vec![0; set.registers],
+            visited: None,
+            trace: None,
+        }
+    }
+
+    /// Runs `replacement` instead of the instruction at `index`.
+    pub fn with_patch(mut self, index: usize, replacement: Instruction) -> Self {
+        self.patch = Some((index, replacement));
+        self
+    }
+
+    /// Stops the run with [`Outcome::Looped`] when an instruction would be executed for the second
+    /// time. Without it a program which loops forever also runs forever.
+    pub fn detect_loops(mut self) -> Self {
+        self.visited = Some(bitvec::bitvec![LocalBits, usize; 0; self.program.len()]);
+        self
+    }
+
+    /// Records every executed instruction, see [`Machine::trace`].
+    pub fn traced(mut self) -> Self {
+        self.trace = Some(Vec::new());
+        self
+    }
+
+    /// Executes the instruction at the program counter, or returns how the run has stopped.
+    pub fn step(&mut self) -> Result<(), Outcome> {
+        let at = self.pc;
+
+        if at == self.program.len() {
+            return Err(Outcome::Terminated);
+        }
+
+        if let Some(visited) = self.visited.as_mut() {
+            if visited[at] {
+                return Err(Outcome::Looped(at));
+            }
+
+            visited.set(at, true);
+        }
+
+        let instruction = match self.patch {
+            Some((index, replacement)) if index == at => replacement,
+            _ => self.program[at],
+        };
+
+        let opcode = &self.set.opcodes[instruction.opcode];
+        let args = &instruction.args[..opcode.operands.len()];
+        let next = (opcode.execute)(&mut self.registers, args);
+
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(TraceStep {
+                pc: at,
+                registers: self.registers.clone(),
+            });
+        }
+
+        let next = match next {
+            Next::Step => at as i64 + 1,
+            Next::Relative(offset) => at as i64 + offset,
+            Next::Absolute(index) => index,
+        };
+
+        match usize::try_from(next) {
+            Ok(next) if next <= self.program.len() => {
+                self.pc = next;
+                Ok(())
+            }
+            _ => Err(Outcome::Escaped(next)),
+        }
+    }
+
+    /// Runs until the program terminates, escapes or, when detecting loops, an instruction would be
+    /// executed twice.
+    pub fn run(&mut self) -> Outcome {
+        loop {
+            if let Err(outcome) = self.step() {
+                return outcome;
+            }
+        }
+    }
+
+    /// The instructions executed so far by a machine detecting loops, or an empty slice.
+    pub fn visited(&self) -> &BitSlice<LocalBits, usize> {
+        self.visited.as_deref().unwrap_or_default()
+    }
+
+    /// Executed instructions of a traced machine, or an empty slice.
+    pub fn trace(&self) -> &[TraceStep] {
+        self.trace.as_deref().unwrap_or(&[])
+    }
+}
+
+/// Searches for the single instruction which when replaced with the one given by `patch` makes the
+/// program terminate, returning its index and the machine after the run. Only the instructions
+/// executed by the unpatched program need to be tried, as patching any other makes no difference.
+pub fn find_terminating_patch<'a, F>(
+    set: &'a InstructionSet,
+    program: &'a [Instruction],
+    mut patch: F,
+) -> Option<(usize, Machine<'a>)>
+where
+    F: FnMut(&Instruction) -> Option<Instruction>,
+{
+    let mut unpatched = Machine::new(set, program).detect_loops();
+    if unpatched.run() == Outcome::Terminated {
+        return None;
+    }
+
+    unpatched
+        .visited()
+        .iter()
+        .enumerate()
+        .filter(|(_, executed)| **executed)
+        .filter_map(|(index, _)| patch(&program[index]).map(|replacement| (index, replacement)))
+        .find_map(|(index, replacement)| {
+            let mut machine = Machine::new(set, program)
+                .with_patch(index, replacement)
+                .detect_loops();
+            if machine.run() == Outcome::Terminated {
+                Some((index, machine))
+            } else {
+                None
+            }
+        })
+}
+
+#[cfg(test)]
+const COUNTER: InstructionSet = InstructionSet {
+    opcodes: &[
+        Opcode {
+            name: "set",
+            operands: &[Operand::Register, Operand::Immediate],
+            execute: |regs, args| {
+                regs[args[0] as usize] = args[1];
+                Next::Step
+            },
+        },
+        Opcode {
+            name: "add",
+            operands: &[Operand::Register, Operand::Register],
+            execute: |regs, args| {
+                regs[args[0] as usize] += regs[args[1] as usize];
+                Next::Step
+            },
+        },
+        Opcode {
+            name: "jlt",
+            operands: &[Operand::Register, Operand::Immediate, Operand::Immediate],
+            execute: |regs, args| {
+                if regs[args[0] as usize] < args[1] {
+                    Next::Absolute(args[2])
+                } else {
+                    Next::Step
+                }
+            },
+        },
+        Opcode {
+            name: "jmp",
+            operands: &[Operand::Immediate],
+            execute: |_, args| Next::Relative(args[0]),
+        },
+    ],
+    registers: 2,
+};
+
+#[test]
+fn runs_with_registers_and_trace() {
+    let program = COUNTER.parse("set 1 1\nadd 0 1\njlt 0 3 1\n").unwrap();
+    let mut machine = Machine::new(&COUNTER, &program).traced();
+
+    // the counter goes around the loop until it reaches 3
+    assert_eq!(machine.run(), Outcome::Terminated);
+    assert_eq!(machine.registers, vec![3, 1]);
+    assert_eq!(
+        machine.trace().iter().map(|s| s.pc).collect::<Vec<_>>(),
+        vec![0, 1, 2, 1, 2, 1, 2]
+    );
+    assert_eq!(machine.trace()[3].registers, vec![2, 1]);
+    assert!(machine.visited().is_empty());
+}
+
+#[test]
+fn detects_loops_when_asked() {
+    let program = COUNTER.parse("set 1 1\nadd 0 1\njlt 0 3 1\n").unwrap();
+    let mut machine = Machine::new(&COUNTER, &program).detect_loops();
+
+    // the loop is detected before the second round over `add`
+    assert_eq!(machine.run(), Outcome::Looped(1));
+    assert_eq!(machine.registers, vec![1, 1]);
+    assert_eq!(machine.visited().count_ones(), 3);
+}
+
+#[test]
+fn patching_finds_termination() {
+    let program = COUNTER.parse("set 0 1\njmp 0\n").unwrap();
+    let jmp = COUNTER.opcode("jmp").unwrap();
+
+    let (index, machine) = find_terminating_patch(&COUNTER, &program, |i| {
+        if i.opcode == jmp {
+            Some(Instruction {
+                opcode: jmp,
+                args: [1, 0, 0],
+            })
+        } else {
+            None
+        }
+    })
+    .unwrap();
+
+    assert_eq!(index, 1);
+    assert_eq!(machine.registers, vec![1, 0]);
+
+    let mut escaping = Machine::new(&COUNTER, &program).with_patch(
+        1,
+        Instruction {
+            opcode: jmp,
+            args: [-5, 0, 0],
+        },
+    );
+    assert_eq!(escaping.run(), Outcome::Escaped(-4));
+}
+
+#[test]
+fn parse_errors_name_the_opcodes() {
+    let e = COUNTER.parse("set 0 1\nmul 0 1\n").unwrap_err();
+    assert_eq!((e.line, e.column), (2, 1));
+    assert_eq!(e.expected, "one of `set`, `add`, `jlt`, `jmp`");
+
+    let e = COUNTER.parse("add 0\n").unwrap_err();
+    assert_eq!((e.line, e.column), (1, 6));
+}
+
+#[test]
+fn parse_checks_the_registers() {
+    let e = COUNTER.parse("set 0 -1\nadd 0 2\n").unwrap_err();
+    assert_eq!((e.line, e.column), (2, 7));
+    assert_eq!(e.expected, "a register from 0 to 1");
+
+    let e = COUNTER.parse("jlt -1 3 1\n").unwrap_err();
+    assert_eq!((e.line, e.column), (1, 5));
+}
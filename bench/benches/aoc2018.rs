@@ -0,0 +1,66 @@
+use aoc_bench::{group, input};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use aoc2018::*;
+
+fn day01(c: &mut Criterion) {
+    let input = match input(2018, 1) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2018, 1));
+    let numbers = day01::parse(input.as_bytes()).unwrap();
+    g.bench_function("part1", |b| b.iter(|| day01::part1(black_box(&numbers))));
+    g.bench_function("part2", |b| b.iter(|| day01::part2(black_box(&numbers))));
+}
+
+fn day02(c: &mut Criterion) {
+    let input = match input(2018, 2) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2018, 2));
+    let ids = day02::parse(input.as_bytes()).unwrap();
+    g.bench_function("part1", |b| b.iter(|| day02::part1(black_box(&ids))));
+    g.bench_function("part2", |b| b.iter(|| day02::part2(black_box(&ids))));
+}
+
+fn day03(c: &mut Criterion) {
+    let input = match input(2018, 3) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2018, 3));
+    let claims = day03::parse(&input).unwrap();
+    g.bench_function("parse", |b| b.iter(|| day03::parse(black_box(&input))));
+    g.bench_function("part1", |b| b.iter(|| day03::part1(black_box(&claims))));
+    g.bench_function("part2", |b| b.iter(|| day03::part2(black_box(&claims))));
+}
+
+fn day04(c: &mut Criterion) {
+    let input = match input(2018, 4) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2018, 4));
+    let schedule = day04::parse(input.as_bytes()).unwrap();
+    g.bench_function("parse", |b| {
+        b.iter(|| day04::parse(black_box(input.as_bytes())))
+    });
+    g.bench_function("part1", |b| b.iter(|| day04::part1(black_box(&schedule))));
+    g.bench_function("part2", |b| b.iter(|| day04::part2(black_box(&schedule))));
+}
+
+fn day05(c: &mut Criterion) {
+    let input = match input(2018, 5) {
+        Some(input) => input,
+        None => return,
+    };
+    let polymer = input.lines().next().unwrap_or_default();
+    let mut g = c.benchmark_group(group(2018, 5));
+    g.bench_function("part1", |b| b.iter(|| day05::part1(black_box(polymer))));
+    g.bench_function("part2", |b| b.iter(|| day05::part2(black_box(polymer))));
+}
+
+criterion_group!(benches, day01, day02, day03, day04, day05);
+criterion_main!(benches);
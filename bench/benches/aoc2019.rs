@@ -0,0 +1,380 @@
+//! Day 23 is not benchmarked as the network is left running in threads after the answers have
+//! been found and the scheduling makes the runs nondeterministic, and day 25 is not benchmarked as
+//! the exploration does not finish yet.
+
+use aoc_bench::{group, input, slow};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use intcode::{util::parse_program_n_lines, Word};
+use std::str::FromStr;
+
+fn program(day: u8) -> Option<Vec<Word>> {
+    input(2019, day).map(|input| parse_program_n_lines(input.as_bytes(), Some(1)).unwrap())
+}
+
+fn day01(c: &mut Criterion) {
+    use aoc2019_day01::{fuel_requirements, parse_masses};
+
+    let input = match input(2019, 1) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2019, 1));
+    let masses = parse_masses(input.as_bytes()).unwrap();
+    g.bench_function("both", |b| b.iter(|| fuel_requirements(black_box(&masses))));
+}
+
+fn day02(c: &mut Criterion) {
+    use aoc2019_day02::{find_coords, stage1};
+
+    let data = match program(2) {
+        Some(data) => data,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2019, 2));
+    g.bench_function("part1", |b| b.iter(|| stage1(black_box(&data))));
+    g.bench_function("part2", |b| {
+        b.iter(|| find_coords(black_box(&data), 19690720))
+    });
+}
+
+fn day03(c: &mut Criterion) {
+    use aoc2019_day03::{stage1, stage2};
+
+    let input = match input(2019, 3) {
+        Some(input) => input,
+        None => return,
+    };
+    let lines = input.lines().collect::<Vec<_>>();
+    let mut g = c.benchmark_group(group(2019, 3));
+    g.bench_function("part1", |b| b.iter(|| stage1(black_box(&lines))));
+    g.bench_function("part2", |b| b.iter(|| stage2(black_box(&lines))));
+}
+
+fn day04(c: &mut Criterion) {
+    use aoc2019_day04::run_stages;
+
+    // the input is in the binary
+    let mut g = c.benchmark_group(group(2019, 4));
+    g.bench_function("both", |b| {
+        b.iter(|| run_stages(black_box(108_457..=562_041)))
+    });
+}
+
+fn day05(c: &mut Criterion) {
+    use aoc2019_day05::{stage1, stage2};
+
+    let data = match program(5) {
+        Some(data) => data,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2019, 5));
+    g.bench_function("part1", |b| b.iter(|| stage1(black_box(&data))));
+    g.bench_function("part2", |b| b.iter(|| stage2(black_box(&data))));
+}
+
+fn day06(c: &mut Criterion) {
+    use aoc2019_day06::Graphthing;
+
+    let input = match input(2019, 6) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2019, 6));
+    g.bench_function("part1", |b| {
+        b.iter(|| {
+            Graphthing::builder()
+                .parse_and_push_all(black_box(&input))
+                .build()
+                .reachable()
+        })
+    });
+    g.bench_function("part2", |b| {
+        b.iter(|| {
+            Graphthing::builder()
+                .parse_and_push_all(black_box(&input))
+                .build()
+                .undirected()
+                .distance("YOU", "SAN")
+        })
+    });
+}
+
+fn day07(c: &mut Criterion) {
+    use aoc2019_day07::{find_max_feedback_output_with, find_max_output, Strategy};
+
+    let data = match program(7) {
+        Some(data) => data,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2019, 7));
+    g.bench_function("part1", |b| b.iter(|| find_max_output(0, black_box(&data))));
+    g.bench_function("part2", |b| {
+        b.iter(|| find_max_feedback_output_with(Strategy::SingleThread, 0, black_box(&data)))
+    });
+    g.bench_function("part2/threaded_naive", |b| {
+        b.iter(|| find_max_feedback_output_with(Strategy::ThreadedNaive, 0, black_box(&data)))
+    });
+}
+
+fn day08(c: &mut Criterion) {
+    use aoc2019_day08::{stage1, stage2};
+
+    let input = match input(2019, 8) {
+        Some(input) => input,
+        None => return,
+    };
+    let raw = input.trim_end().as_bytes();
+    let mut g = c.benchmark_group(group(2019, 8));
+    g.bench_function("part1", |b| b.iter(|| stage1(black_box(raw))));
+    g.bench_function("part2", |b| b.iter(|| stage2(black_box(raw))));
+}
+
+fn day09(c: &mut Criterion) {
+    use aoc2019_day09::boost;
+
+    let data = match program(9) {
+        Some(data) => data,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2019, 9));
+    g.bench_function("part1", |b| b.iter(|| boost(black_box(&data), 1)));
+    g.bench_function("part2", |b| b.iter(|| boost(black_box(&data), 2)));
+}
+
+fn day10(c: &mut Criterion) {
+    use aoc2019_day10::Map;
+
+    let input = match input(2019, 10) {
+        Some(input) => input,
+        None => return,
+    };
+    let map = Map::read(input.as_bytes()).unwrap();
+    let (best, _) = map.best_asteroid_for_monitoring();
+    let mut g = c.benchmark_group(group(2019, 10));
+    g.bench_function("part1", |b| {
+        b.iter(|| black_box(&map).best_asteroid_for_monitoring())
+    });
+    g.bench_function("part2", |b| {
+        b.iter(|| black_box(&map).killed_asteroids_clockwise(best)[199])
+    });
+}
+
+fn day11(c: &mut Criterion) {
+    use aoc2019_day11::{stage1, stage2};
+
+    let data = match program(11) {
+        Some(data) => data,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2019, 11));
+    g.bench_function("part1", |b| b.iter(|| stage1(black_box(&data))));
+    g.bench_function("part2", |b| b.iter(|| stage2(black_box(&data))));
+}
+
+fn day12(c: &mut Criterion) {
+    use aoc2019_day12::{parse, stage1, stage2};
+
+    let input = match input(2019, 12) {
+        Some(input) => input,
+        None => return,
+    };
+    let system = parse(&input).unwrap();
+    let mut g = c.benchmark_group(group(2019, 12));
+    g.bench_function("part1", |b| b.iter(|| stage1(black_box(&system))));
+    g.bench_function("part2", |b| b.iter(|| stage2(black_box(&system))));
+}
+
+fn day13(c: &mut Criterion) {
+    use aoc2019_day13::{stage1, stage2, Screen};
+
+    let data = match program(13) {
+        Some(data) => data,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2019, 13));
+    g.bench_function("part1", |b| b.iter(|| stage1(black_box(&data))));
+    g.bench_function("part2", |b| {
+        b.iter(|| stage2(black_box(&data), Screen::Hidden))
+    });
+}
+
+fn day14(c: &mut Criterion) {
+    use aoc2019_day14::{fuel_for_ore, stage1, Context};
+
+    let input = match input(2019, 14) {
+        Some(input) => input,
+        None => return,
+    };
+    let ctx = Context::parse(&input).unwrap();
+    let mut g = c.benchmark_group(group(2019, 14));
+    g.bench_function("part1", |b| b.iter(|| stage1(black_box(&ctx))));
+    g.bench_function("part2", |b| {
+        b.iter(|| fuel_for_ore(black_box(&ctx), 1_000_000_000_000))
+    });
+}
+
+fn day15(c: &mut Criterion) {
+    use aoc2019_day15::{explore, stage1, stage2};
+
+    let data = match program(15) {
+        Some(data) => data,
+        None => return,
+    };
+    let area = explore(data.clone());
+    let mut g = c.benchmark_group(group(2019, 15));
+    g.bench_function("explore", |b| b.iter(|| explore(black_box(data.clone()))));
+    g.bench_function("part1", |b| b.iter(|| stage1(black_box(&area))));
+    g.bench_function("part2", |b| b.iter(|| stage2(black_box(&area), None)));
+}
+
+fn day16(c: &mut Criterion) {
+    use aoc2019_day16::{naive_fft, parse_str, repeated_fft};
+
+    let input = match input(2019, 16) {
+        Some(input) => input,
+        None => return,
+    };
+    let bytes = parse_str(input.trim());
+    let mut g = c.benchmark_group(group(2019, 16));
+    g.bench_function("part1", |b| {
+        b.iter(|| naive_fft(black_box(&bytes).iter().cloned(), 100))
+    });
+    g.bench_function("part2", |b| {
+        b.iter(|| repeated_fft(&input, black_box(&bytes)))
+    });
+}
+
+fn day17(c: &mut Criterion) {
+    use aoc2019_day17::{
+        alignment_parameters, movement_functions, part2_dust_collected, part2_find_path,
+        ScaffoldProgram,
+    };
+
+    let data = match program(17) {
+        Some(data) => data,
+        None => return,
+    };
+    let map = ScaffoldProgram::new(data.clone()).print_map();
+    let mut g = c.benchmark_group(group(2019, 17));
+    g.bench_function("part1", |b| {
+        b.iter(|| alignment_parameters(&ScaffoldProgram::new(black_box(data.clone())).print_map()))
+    });
+    g.sample_size(10);
+    g.bench_function("part2", |b| {
+        b.iter(|| {
+            let (_, compression) = part2_find_path(black_box(&map));
+            part2_dust_collected(&movement_functions().render(&compression), data.clone())
+        })
+    });
+}
+
+fn day18(c: &mut Criterion) {
+    use aoc2019_day18::{steps_to_collect_all_keys, Map, Part};
+
+    let input = match input(2019, 18) {
+        Some(input) => input,
+        None => return,
+    };
+    if !slow() {
+        return;
+    }
+    let mut g = c.benchmark_group(group(2019, 18));
+    g.sample_size(10);
+    for &(name, part) in &[("part1", Part::One), ("part2", Part::Two)] {
+        g.bench_function(name, |b| {
+            b.iter_batched(
+                || Map::from_str(&input).unwrap(),
+                |mut map| steps_to_collect_all_keys(&mut map, part),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+}
+
+fn day19(c: &mut Criterion) {
+    use aoc2019_day19::{part1, part2, DroneDeployer};
+
+    let data = match program(19) {
+        Some(data) => data,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2019, 19));
+    g.bench_function("part1", |b| {
+        b.iter(|| part1(&mut DroneDeployer::new(black_box(&data)), (50, 50)))
+    });
+    g.bench_function("part2", |b| {
+        b.iter(|| part2(&mut DroneDeployer::new(black_box(&data)), 100))
+    });
+}
+
+fn day20(c: &mut Criterion) {
+    use aoc2019_day20::{shortest_path, Levels, ParsedTile};
+    use intcode::util::GameDisplay;
+
+    let input = match input(2019, 20) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut map: GameDisplay<ParsedTile> = GameDisplay::default();
+    map.parse_from_reader((0, 0), input.as_bytes()).unwrap();
+    let mut g = c.benchmark_group(group(2019, 20));
+    g.bench_function("part1", |b| {
+        b.iter(|| shortest_path(black_box(&map), "AA", "ZZ", Levels::Flat))
+    });
+    g.bench_function("part2", |b| {
+        b.iter(|| shortest_path(black_box(&map), "AA", "ZZ", Levels::Recursive))
+    });
+}
+
+fn day21(c: &mut Criterion) {
+    use aoc2019_day21::{random_stage, Part1, Part2};
+
+    let data = match program(21) {
+        Some(data) => data,
+        None => return,
+    };
+    // the search for the springscript is random, seeded to take the same time on every run
+    if !slow() {
+        return;
+    }
+    let mut g = c.benchmark_group(group(2019, 21));
+    g.sample_size(10);
+    g.bench_function("part1", |b| {
+        b.iter(|| random_stage::<Part1, _>(black_box(&data), 0, ()))
+    });
+    g.bench_function("part2", |b| {
+        b.iter(|| random_stage::<Part2, _>(black_box(&data), 0, ()))
+    });
+}
+
+fn day22(c: &mut Criterion) {
+    use aoc2019_day22::{parse_ops, stage1, stage2};
+
+    let input = match input(2019, 22) {
+        Some(input) => input,
+        None => return,
+    };
+    let ops = parse_ops(&input).unwrap();
+    let mut g = c.benchmark_group(group(2019, 22));
+    g.bench_function("part1", |b| b.iter(|| stage1(black_box(&ops))));
+    g.bench_function("part2", |b| b.iter(|| stage2(black_box(&ops))));
+}
+
+fn day24(c: &mut Criterion) {
+    use aoc2019_day24::{stage1, stage2, BugTile};
+
+    let input = match input(2019, 24) {
+        Some(input) => input,
+        None => return,
+    };
+    let initial = BugTile::from_str(&input).unwrap();
+    let mut g = c.benchmark_group(group(2019, 24));
+    g.bench_function("part1", |b| b.iter(|| stage1(black_box(initial))));
+    g.bench_function("part2", |b| b.iter(|| stage2(black_box(initial), 200)));
+}
+
+criterion_group!(
+    benches, day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12,
+    day13, day14, day15, day16, day17, day18, day19, day20, day21, day22, day24
+);
+criterion_main!(benches);
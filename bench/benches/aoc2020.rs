@@ -0,0 +1,250 @@
+use aoc_bench::{group, input, slow};
+use aoc_common::solution::Solution;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use aoc2020::*;
+
+fn day01(c: &mut Criterion) {
+    let input = match input(2020, 1) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 1));
+    let set = day01::parse(input.as_bytes()).unwrap();
+    g.bench_function("parse", |b| {
+        b.iter(|| day01::parse(black_box(input.as_bytes())))
+    });
+    g.bench_function("part1", |b| b.iter(|| day01::part_one(black_box(&set))));
+    g.bench_function("part2", |b| b.iter(|| day01::part_two(black_box(&set))));
+}
+
+fn day02(c: &mut Criterion) {
+    let input = match input(2020, 2) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 2));
+    let passwords = day02::parse(input.as_bytes()).unwrap();
+    g.bench_function("parse", |b| {
+        b.iter(|| day02::parse(black_box(input.as_bytes())))
+    });
+    g.bench_function("part1", |b| {
+        b.iter(|| day02::part_one(black_box(&passwords)))
+    });
+    g.bench_function("part2", |b| {
+        b.iter(|| day02::part_two(black_box(&passwords)))
+    });
+}
+
+fn day03(c: &mut Criterion) {
+    let input = match input(2020, 3) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 3));
+    g.bench_function("both", |b| {
+        b.iter(|| day03::count_trees(black_box(input.as_bytes()), day03::SLOPES))
+    });
+}
+
+fn day04(c: &mut Criterion) {
+    let input = match input(2020, 4) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 4));
+    g.bench_function("both", |b| {
+        b.iter(|| day04::process(black_box(input.as_bytes())))
+    });
+}
+
+fn day05(c: &mut Criterion) {
+    let input = match input(2020, 5) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 5));
+    let ids = day05::seat_ids(input.as_bytes()).unwrap();
+    g.bench_function("parse", |b| {
+        b.iter(|| day05::seat_ids(black_box(input.as_bytes())))
+    });
+    g.bench_function("part1", |b| b.iter(|| day05::part_one(black_box(&ids))));
+    g.bench_function("part2", |b| b.iter(|| day05::part_two(black_box(&ids))));
+
+    // the same passes with the two implementations, the simd one only builds on nightly
+    let passes = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+    g.bench_function("find_seat_id/scalar", |b| {
+        b.iter(|| {
+            passes
+                .iter()
+                .map(|pass| day05::find_seat_id_scalar(black_box(pass)))
+                .max()
+        })
+    });
+    #[cfg(feature = "nightly")]
+    g.bench_function("find_seat_id/simd", |b| {
+        b.iter(|| {
+            passes
+                .iter()
+                .map(|pass| day05::find_seat_id_simd(black_box(pass)))
+                .max()
+        })
+    });
+}
+
+fn day06(c: &mut Criterion) {
+    let input = match input(2020, 6) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 6));
+    g.bench_function("both", |b| {
+        b.iter(|| day06::process(black_box(input.as_bytes())))
+    });
+}
+
+fn day07(c: &mut Criterion) {
+    let input = match input(2020, 7) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 7));
+    let graph = day07::parse(input.as_bytes()).unwrap();
+    g.bench_function("parse", |b| {
+        b.iter(|| day07::parse(black_box(input.as_bytes())))
+    });
+    g.bench_function("part1", |b| {
+        b.iter(|| day07::query_reachability("shiny gold", black_box(&graph)))
+    });
+    g.bench_function("part2", |b| {
+        b.iter(|| day07::query_nesting("shiny gold", black_box(&graph)))
+    });
+}
+
+fn day08(c: &mut Criterion) {
+    let input = match input(2020, 8) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 8));
+    let code = day08::parse(&input).unwrap();
+    g.bench_function("parse", |b| b.iter(|| day08::parse(black_box(&input))));
+    g.bench_function("part1", |b| {
+        b.iter(|| day08::Day08::part_one(black_box(&code)))
+    });
+    g.bench_function("part2", |b| {
+        b.iter(|| day08::Day08::part_two(black_box(&code)))
+    });
+}
+
+fn day09(c: &mut Criterion) {
+    let input = match input(2020, 9) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 9));
+    let (index, all) = day09::find_invalid(input.as_bytes()).unwrap();
+    g.bench_function("part1", |b| {
+        b.iter(|| day09::find_invalid(black_box(input.as_bytes())))
+    });
+    g.bench_function("part2", |b| {
+        b.iter(|| day09::find_weakness((index, all[index]), black_box(&all)))
+    });
+}
+
+fn day10(c: &mut Criterion) {
+    let input = match input(2020, 10) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 10));
+    let ratings = day10::parse(input.as_bytes()).unwrap();
+    g.bench_function("parse", |b| {
+        b.iter(|| day10::parse(black_box(input.as_bytes())))
+    });
+    g.bench_function("part1", |b| b.iter(|| day10::part_one(black_box(&ratings))));
+}
+
+fn day11(c: &mut Criterion) {
+    let input = match input(2020, 11) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 11));
+    let (width, map) = day11::process(input.as_bytes()).unwrap();
+    let height = map.len() / width;
+    g.bench_function("part1", |b| {
+        b.iter(|| day11::gol_until_settled(day11::RuleSet::PartOne, width, height, black_box(&map)))
+    });
+    g.bench_function("part2", |b| {
+        b.iter(|| day11::gol_until_settled(day11::RuleSet::PartTwo, width, height, black_box(&map)))
+    });
+}
+
+fn day12(c: &mut Criterion) {
+    let input = match input(2020, 12) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 12));
+    let ops = day12::parse(input.as_bytes()).unwrap();
+    g.bench_function("parse", |b| {
+        b.iter(|| day12::parse(black_box(input.as_bytes())))
+    });
+    g.bench_function("part1", |b| b.iter(|| day12::part_one(black_box(&ops))));
+    g.bench_function("part2", |b| b.iter(|| day12::part_two(black_box(&ops))));
+}
+
+fn day13(c: &mut Criterion) {
+    let input = match input(2020, 13) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 13));
+    let notes = day13::Notes::read(input.as_bytes()).unwrap();
+    g.bench_function("part1", |b| b.iter(|| day13::part_one(black_box(&notes))));
+    g.bench_function("part2", |b| b.iter(|| day13::part_two(black_box(&notes))));
+}
+
+fn day14(c: &mut Criterion) {
+    let input = match input(2020, 14) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 14));
+    g.bench_function("both", |b| b.iter(|| day14::process(black_box(&input))));
+}
+
+fn day15(c: &mut Criterion) {
+    let input = match input(2020, 15) {
+        Some(input) => input,
+        None => return,
+    };
+    let starting_numbers = day15::Day15::parse(&input).unwrap();
+    let mut g = c.benchmark_group(group(2020, 15));
+    g.bench_function("part1", |b| {
+        b.iter(|| day15::process(black_box(&starting_numbers), 2020))
+    });
+    if slow() {
+        g.sample_size(10);
+        g.bench_function("part2", |b| {
+            b.iter(|| day15::process(black_box(&starting_numbers), 30_000_000))
+        });
+    }
+}
+
+fn day17(c: &mut Criterion) {
+    let input = match input(2020, 17) {
+        Some(input) => input,
+        None => return,
+    };
+    let mut g = c.benchmark_group(group(2020, 17));
+    g.bench_function("part1", |b| b.iter(|| day17::boot::<3>(black_box(&input))));
+    g.bench_function("part2", |b| b.iter(|| day17::boot::<4>(black_box(&input))));
+}
+
+criterion_group!(
+    benches, day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12,
+    day13, day14, day15, day17
+);
+criterion_main!(benches);
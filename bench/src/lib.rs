@@ -0,0 +1,35 @@
+//! Benchmarks of all of the years, see `benches/`, and the `aoc-bench` tool for comparing the
+//! saved criterion baselines of two runs, see [`report`].
+//!
+//! The benchmarks read the puzzle inputs from the input store (see `aoc_common::input`) and skip
+//! the days which have no input. Days taking tens of seconds per iteration are only benchmarked
+//! when `AOC_BENCH_SLOW` is set.
+
+pub mod report;
+
+use aoc_common::input::{InputError, InputStore, DEFAULT_SET};
+
+/// Reads the puzzle input of the day, or returns `None` after noting on stderr why the day is
+/// skipped.
+pub fn input(year: u16, day: u8) -> Option<String> {
+    match InputStore::from_env().read(year, day, DEFAULT_SET) {
+        Ok(input) => Some(input),
+        Err(InputError::Missing { .. }) => {
+            eprintln!("skipping {} day{:02}: no input", year, day);
+            None
+        }
+        Err(e) => panic!("reading input of {} day{:02} failed: {}", year, day, e),
+    }
+}
+
+/// True if the slow days should be benchmarked as well.
+pub fn slow() -> bool {
+    std::env::var_os("AOC_BENCH_SLOW").is_some()
+}
+
+/// Name of the benchmark group of the day, for example `2020/day07`. The benchmarks within are
+/// named `part1` and `part2`, or `both` when the parts are solved in a single pass, giving ids
+/// like `2020/day07/part1`.
+pub fn group(year: u16, day: u8) -> String {
+    format!("{}/day{:02}", year, day)
+}
@@ -0,0 +1,187 @@
+//! `aoc-bench compare <old> <new>` prints a table of the benchmarks saved as the two criterion
+//! baselines and exits with non-zero status if any of them regressed by more than the threshold,
+//! 5% by default.
+
+use aoc_bench::report::{self, Report};
+use std::fmt;
+use std::path::PathBuf;
+
+fn main() {
+    let options = match Options::from_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!(
+                "usage: aoc-bench compare <old> <new> [--threshold percent] [--dir criterion_dir]"
+            );
+            std::process::exit(2);
+        }
+    };
+
+    let Options {
+        old,
+        new,
+        threshold,
+        dir,
+    } = options;
+    let dir = dir.unwrap_or_else(report::criterion_dir);
+
+    let report = report::load(&dir, &old).and_then(|old| {
+        let new = report::load(&dir, &new)?;
+        Ok(Report::compare(&old, &new, threshold / 100.0))
+    });
+
+    match report {
+        Ok(report) => {
+            print!("{}", report);
+            let regressions = report.regressions().count();
+            if regressions > 0 {
+                println!(
+                    "{} of {} benchmarks regressed by more than {}%",
+                    regressions,
+                    report.rows.len(),
+                    threshold
+                );
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("aoc-bench: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    old: String,
+    new: String,
+    threshold: f64,
+    dir: Option<PathBuf>,
+}
+
+impl Options {
+    fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Self, UsageError> {
+        let mut threshold = 5.0;
+        let mut dir = None;
+        let mut positional = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--threshold" => {
+                    let value = args.next().ok_or(UsageError::MissingValue("--threshold"))?;
+                    threshold = match value.parse::<f64>() {
+                        Ok(pct) if pct >= 0.0 => pct,
+                        _ => return Err(UsageError::InvalidThreshold(value)),
+                    };
+                }
+                "--dir" => {
+                    dir = Some(PathBuf::from(
+                        args.next().ok_or(UsageError::MissingValue("--dir"))?,
+                    ))
+                }
+                flag if flag.starts_with("--") => return Err(UsageError::UnknownFlag(arg)),
+                _ => positional.push(arg),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        match positional.next().as_deref() {
+            Some("compare") => {}
+            Some(other) => return Err(UsageError::UnknownCommand(other.to_owned())),
+            None => return Err(UsageError::MissingBaselines),
+        }
+
+        let (old, new) = match (positional.next(), positional.next(), positional.next()) {
+            (Some(old), Some(new), None) => (old, new),
+            (_, _, Some(_)) => return Err(UsageError::TooManyArguments),
+            _ => return Err(UsageError::MissingBaselines),
+        };
+
+        Ok(Options {
+            old,
+            new,
+            threshold,
+            dir,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum UsageError {
+    UnknownCommand(String),
+    MissingBaselines,
+    InvalidThreshold(String),
+    TooManyArguments,
+    MissingValue(&'static str),
+    UnknownFlag(String),
+}
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UsageError::UnknownCommand(s) => write!(fmt, "unknown command: {:?}", s),
+            UsageError::MissingBaselines => write!(fmt, "two baselines are needed"),
+            UsageError::InvalidThreshold(s) => write!(fmt, "invalid threshold: {:?}", s),
+            UsageError::TooManyArguments => write!(fmt, "too many arguments"),
+            UsageError::MissingValue(flag) => write!(fmt, "missing value for {}", flag),
+            UsageError::UnknownFlag(s) => write!(fmt, "unknown flag: {}", s),
+        }
+    }
+}
+
+#[cfg(test)]
+fn args(s: &str) -> Vec<String> {
+    s.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn parse_options() {
+    assert_eq!(
+        Options::from_args(args("compare before after")),
+        Ok(Options {
+            old: "before".into(),
+            new: "after".into(),
+            threshold: 5.0,
+            dir: None,
+        })
+    );
+    assert_eq!(
+        Options::from_args(args("compare --threshold 2.5 before after --dir /tmp/c")),
+        Ok(Options {
+            old: "before".into(),
+            new: "after".into(),
+            threshold: 2.5,
+            dir: Some(PathBuf::from("/tmp/c")),
+        })
+    );
+}
+
+#[test]
+fn usage_errors() {
+    assert_eq!(
+        Options::from_args(args("")),
+        Err(UsageError::MissingBaselines)
+    );
+    assert_eq!(
+        Options::from_args(args("compare before")),
+        Err(UsageError::MissingBaselines)
+    );
+    assert_eq!(
+        Options::from_args(args("compare a b c")),
+        Err(UsageError::TooManyArguments)
+    );
+    assert_eq!(
+        Options::from_args(args("diff a b")),
+        Err(UsageError::UnknownCommand("diff".into()))
+    );
+    assert_eq!(
+        Options::from_args(args("compare a b --threshold -1")),
+        Err(UsageError::InvalidThreshold("-1".into()))
+    );
+    assert_eq!(
+        Options::from_args(args("compare a b --threshold")),
+        Err(UsageError::MissingValue("--threshold"))
+    );
+}
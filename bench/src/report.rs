@@ -0,0 +1,452 @@
+//! Comparison of two saved criterion baselines.
+//!
+//! Running the benchmarks with `-- --save-baseline <name>` makes criterion store the estimates
+//! of every benchmark as `<criterion dir>/<benchmark>/<name>/estimates.json`, next to a
+//! `benchmark.json` with the id of the benchmark. A [`Report`] pairs the benchmarks of two
+//! baselines by id and flags the ones which got slower by more than the threshold.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Time per iteration in nanoseconds with its confidence interval, as estimated by criterion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub point: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl Estimate {
+    /// Reads the typical time from criterion's `estimates.json`: the slope of the linear
+    /// regression when there is one, the mean otherwise, which is what criterion reports as well.
+    fn from_json(estimates: &Value) -> Option<Self> {
+        let typical = match estimates.get("slope") {
+            Some(slope) if !slope.is_null() => slope,
+            _ => estimates.get("mean")?,
+        };
+
+        let interval = typical.get("confidence_interval")?;
+
+        Some(Estimate {
+            point: typical.get("point_estimate")?.as_f64()?,
+            lower: interval.get("lower_bound")?.as_f64()?,
+            upper: interval.get("upper_bound")?.as_f64()?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum ReportError {
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Json {
+        path: PathBuf,
+        error: serde_json::Error,
+    },
+    /// The file is json but not in the format written by criterion.
+    Malformed {
+        path: PathBuf,
+    },
+    /// No benchmark had been saved with the name of the baseline.
+    NoSuchBaseline {
+        dir: PathBuf,
+        baseline: String,
+    },
+}
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReportError::Io { path, error } => {
+                write!(fmt, "failed to read {}: {}", path.display(), error)
+            }
+            ReportError::Json { path, error } => {
+                write!(fmt, "failed to parse {}: {}", path.display(), error)
+            }
+            ReportError::Malformed { path } => {
+                write!(fmt, "unexpected contents in {}", path.display())
+            }
+            ReportError::NoSuchBaseline { dir, baseline } => write!(
+                fmt,
+                "no benchmarks saved as baseline {:?} under {}",
+                baseline,
+                dir.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+/// The default location of criterion's output: `CRITERION_HOME`, or `criterion` in
+/// `CARGO_TARGET_DIR` or in the `target` of the workspace.
+pub fn criterion_dir() -> PathBuf {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("..")
+                .join("target")
+        })
+        .join("criterion")
+}
+
+/// Reads the estimates of all benchmarks saved under the baseline name, keyed by benchmark id.
+pub fn load(dir: &Path, baseline: &str) -> Result<BTreeMap<String, Estimate>, ReportError> {
+    let mut found = BTreeMap::new();
+    visit(dir, baseline, &mut found)?;
+
+    if found.is_empty() {
+        return Err(ReportError::NoSuchBaseline {
+            dir: dir.to_owned(),
+            baseline: baseline.to_owned(),
+        });
+    }
+
+    Ok(found)
+}
+
+fn visit(
+    dir: &Path,
+    baseline: &str,
+    found: &mut BTreeMap<String, Estimate>,
+) -> Result<(), ReportError> {
+    let io_error = |path: &Path| {
+        let path = path.to_owned();
+        move |error| ReportError::Io { path, error }
+    };
+
+    let saved = dir.join(baseline);
+    let estimates = saved.join("estimates.json");
+    if estimates.is_file() {
+        let benchmark = saved.join("benchmark.json");
+        let id = read_json(&benchmark)?
+            .get("full_id")
+            .and_then(Value::as_str)
+            .map(String::from)
+            .ok_or(ReportError::Malformed { path: benchmark })?;
+        let estimate = Estimate::from_json(&read_json(&estimates)?)
+            .ok_or(ReportError::Malformed { path: estimates })?;
+        found.insert(id, estimate);
+        return Ok(());
+    }
+
+    for entry in std::fs::read_dir(dir).map_err(io_error(dir))? {
+        let entry = entry.map_err(io_error(dir))?;
+        // the html reports live in `report` directories, which never contain the baselines
+        if entry.file_type().map_err(io_error(dir))?.is_dir() && entry.file_name() != "report" {
+            visit(&entry.path(), baseline, found)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn read_json(path: &Path) -> Result<Value, ReportError> {
+    let contents = std::fs::read_to_string(path).map_err(|error| ReportError::Io {
+        path: path.to_owned(),
+        error,
+    })?;
+    serde_json::from_str(&contents).map_err(|error| ReportError::Json {
+        path: path.to_owned(),
+        error,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Slower by more than the threshold, with non-overlapping confidence intervals.
+    Regressed,
+    /// Faster by more than the threshold, with non-overlapping confidence intervals.
+    Improved,
+    Unchanged,
+    /// Only in the new baseline.
+    Added,
+    /// Only in the old baseline.
+    Removed,
+}
+
+impl Verdict {
+    fn judge(old: &Estimate, new: &Estimate, threshold: f64) -> Self {
+        let change = new.point / old.point - 1.0;
+
+        if change > threshold && new.lower > old.upper {
+            Verdict::Regressed
+        } else if change < -threshold && new.upper < old.lower {
+            Verdict::Improved
+        } else {
+            Verdict::Unchanged
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Verdict::Regressed => "regressed",
+            Verdict::Improved => "improved",
+            Verdict::Unchanged => "",
+            Verdict::Added => "added",
+            Verdict::Removed => "removed",
+        };
+        fmt.pad(s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub id: String,
+    pub old: Option<Estimate>,
+    pub new: Option<Estimate>,
+    pub verdict: Verdict,
+}
+
+impl Row {
+    /// Relative change of the point estimates, `0.1` being 10% slower.
+    pub fn change(&self) -> Option<f64> {
+        match (self.old, self.new) {
+            (Some(old), Some(new)) => Some(new.point / old.point - 1.0),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub rows: Vec<Row>,
+}
+
+impl Report {
+    /// Pairs the benchmarks by id; the threshold is a fraction, `0.05` allowing a 5% slowdown.
+    pub fn compare(
+        old: &BTreeMap<String, Estimate>,
+        new: &BTreeMap<String, Estimate>,
+        threshold: f64,
+    ) -> Self {
+        let mut ids = old.keys().chain(new.keys()).collect::<Vec<_>>();
+        ids.sort();
+        ids.dedup();
+
+        let rows = ids
+            .into_iter()
+            .map(|id| {
+                let old = old.get(id).copied();
+                let new = new.get(id).copied();
+                let verdict = match (&old, &new) {
+                    (Some(old), Some(new)) => Verdict::judge(old, new, threshold),
+                    (None, _) => Verdict::Added,
+                    (_, None) => Verdict::Removed,
+                };
+                Row {
+                    id: id.clone(),
+                    old,
+                    new,
+                    verdict,
+                }
+            })
+            .collect();
+
+        Report { rows }
+    }
+
+    pub fn regressions(&self) -> impl Iterator<Item = &Row> + '_ {
+        self.rows
+            .iter()
+            .filter(|row| row.verdict == Verdict::Regressed)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let width = self
+            .rows
+            .iter()
+            .map(|row| row.id.len())
+            .chain(std::iter::once("benchmark".len()))
+            .max()
+            .unwrap();
+
+        writeln!(
+            fmt,
+            "{:<width$}  {:>10}  {:>10}  {:>8}  verdict",
+            "benchmark",
+            "old",
+            "new",
+            "change",
+            width = width
+        )?;
+
+        for row in &self.rows {
+            let time = |e: Option<Estimate>| e.map(|e| Nanos(e.point).to_string());
+            let change = row.change().map(|c| format!("{:+.1}%", c * 100.0));
+            let line = format!(
+                "{:<width$}  {:>10}  {:>10}  {:>8}  {}",
+                row.id,
+                time(row.old).as_deref().unwrap_or("-"),
+                time(row.new).as_deref().unwrap_or("-"),
+                change.as_deref().unwrap_or("-"),
+                row.verdict,
+                width = width
+            );
+            writeln!(fmt, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Duration in nanoseconds, displayed in the largest unit keeping the value at least one.
+struct Nanos(f64);
+
+impl fmt::Display for Nanos {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let units = [("ns", 1.0), ("µs", 1e3), ("ms", 1e6), ("s", 1e9)];
+        let (unit, scale) = units
+            .iter()
+            .rev()
+            .find(|(_, scale)| self.0 >= *scale)
+            .unwrap_or(&units[0]);
+        write!(fmt, "{:.2} {}", self.0 / scale, unit)
+    }
+}
+
+#[cfg(test)]
+fn estimate(point: f64) -> Estimate {
+    Estimate {
+        point,
+        lower: point * 0.99,
+        upper: point * 1.01,
+    }
+}
+
+#[test]
+fn verdicts() {
+    let old = [("a", 100.0), ("b", 100.0), ("c", 100.0), ("d", 100.0)]
+        .iter()
+        .map(|&(id, t)| (String::from(id), estimate(t)))
+        .collect();
+    let new = [("a", 120.0), ("b", 103.0), ("c", 50.0), ("e", 1.0)]
+        .iter()
+        .map(|&(id, t)| (String::from(id), estimate(t)))
+        .collect();
+
+    let report = Report::compare(&old, &new, 0.05);
+    let verdicts = report
+        .rows
+        .iter()
+        .map(|row| (row.id.as_str(), row.verdict))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        verdicts,
+        &[
+            ("a", Verdict::Regressed),
+            ("b", Verdict::Unchanged),
+            ("c", Verdict::Improved),
+            ("d", Verdict::Removed),
+            ("e", Verdict::Added),
+        ]
+    );
+    assert_eq!(
+        report
+            .regressions()
+            .map(|row| &row.id[..])
+            .collect::<Vec<_>>(),
+        &["a"]
+    );
+}
+
+#[test]
+fn overlapping_intervals_are_noise() {
+    let old = Estimate {
+        point: 100.0,
+        lower: 80.0,
+        upper: 130.0,
+    };
+    let new = Estimate {
+        point: 120.0,
+        lower: 100.0,
+        upper: 140.0,
+    };
+    assert_eq!(Verdict::judge(&old, &new, 0.05), Verdict::Unchanged);
+}
+
+#[test]
+fn nanos() {
+    assert_eq!(Nanos(12.0).to_string(), "12.00 ns");
+    assert_eq!(Nanos(1_500.0).to_string(), "1.50 µs");
+    assert_eq!(Nanos(2_345_678.0).to_string(), "2.35 ms");
+    assert_eq!(Nanos(3e9).to_string(), "3.00 s");
+}
+
+#[test]
+fn load_saved_baselines() {
+    let dir = std::env::temp_dir().join(format!("aoc-bench-report-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let save = |directory: &str, full_id: &str, baseline: &str, slope: &str| {
+        let saved = dir.join(directory).join(baseline);
+        std::fs::create_dir_all(&saved).unwrap();
+        std::fs::write(
+            saved.join("benchmark.json"),
+            format!(r#"{{"group_id":"g","full_id":"{}"}}"#, full_id),
+        )
+        .unwrap();
+        std::fs::write(
+            saved.join("estimates.json"),
+            format!(
+                r#"{{
+                    "mean":{{"confidence_interval":{{"confidence_level":0.95,"lower_bound":9.0,"upper_bound":11.0}},"point_estimate":10.0,"standard_error":1.0}},
+                    "slope":{}
+                }}"#,
+                slope
+            ),
+        )
+        .unwrap();
+    };
+
+    let slope = r#"{"confidence_interval":{"confidence_level":0.95,"lower_bound":19.0,"upper_bound":21.0},"point_estimate":20.0,"standard_error":1.0}"#;
+
+    save("2020_day07/part1", "2020/day07/part1", "before", "null");
+    save("2020_day07/part2", "2020/day07/part2", "before", slope);
+    save("2020_day07/part2", "2020/day07/part2", "new", "null");
+
+    let loaded = load(&dir, "before").unwrap();
+    assert_eq!(
+        loaded.into_iter().collect::<Vec<_>>(),
+        &[
+            (
+                String::from("2020/day07/part1"),
+                estimate_of(10.0, 9.0, 11.0)
+            ),
+            (
+                String::from("2020/day07/part2"),
+                estimate_of(20.0, 19.0, 21.0)
+            ),
+        ]
+    );
+
+    match load(&dir, "missing") {
+        Err(ReportError::NoSuchBaseline { .. }) => {}
+        x => panic!("unexpected {:?}", x),
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    fn estimate_of(point: f64, lower: f64, upper: f64) -> Estimate {
+        Estimate {
+            point,
+            lower,
+            upper,
+        }
+    }
+}
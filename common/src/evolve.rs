@@ -0,0 +1,444 @@
+//! Evolutionary search over genomes of any type, given as functions creating, mutating and
+//! crossing them.
+//!
+//! An [`Evolution`] keeps a population scored by a fitness function, carries the best of every
+//! generation over as they are, and breeds the rest from parents chosen by a [`Selection`]. The
+//! randomness comes from a seeded [`ChaCha8Rng`], which unlike `StdRng` keeps its algorithm between
+//! versions of `rand`, so that a run is repeated exactly with the same seed.
+//! The search ends when the fitness function reports a genome [solved](Score::Solved) the problem.
+
+pub use rand_chacha::ChaCha8Rng;
+
+use rand::{Rng, SeedableRng};
+use std::cmp::Reverse;
+
+/// What the fitness function found out about a genome.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Score<A> {
+    /// Not a solution, higher is fitter.
+    Fitness(usize),
+    /// Solution to the problem with the answer.
+    Solved(A),
+}
+
+/// How the parents are chosen from the population.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    /// The fittest of the given number of genomes picked at random.
+    Tournament(usize),
+    /// At random with the chance relative to the fitness plus one.
+    Roulette,
+}
+
+/// Summary of a generation given to the progress callback.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Generation counting from zero.
+    pub generation: usize,
+    /// Fitness of the fittest genome of the generation.
+    pub best: usize,
+    /// Fitness of the least fit genome of the generation.
+    pub worst: usize,
+    /// Genomes scored since the start, counting the ones of this generation.
+    pub evaluated: usize,
+}
+
+/// The genome solving the problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution<G, A> {
+    pub genome: G,
+    pub answer: A,
+    /// Generation the genome was found on, counting from zero.
+    pub generation: usize,
+}
+
+/// Genetic algorithm with the functions creating random genomes (`N`), mutating them (`M`),
+/// crossing two parents into a child (`X`) and observing the progress (`P`).
+pub struct Evolution<N, M, X, P> {
+    random: N,
+    mutate: M,
+    crossover: X,
+    crossover_rate: f64,
+    progress: P,
+    population: usize,
+    elitism: usize,
+    newcomers: usize,
+    selection: Selection,
+    stagnation: Option<(usize, usize)>,
+    generations: Option<usize>,
+    seed: u64,
+}
+
+impl<N, M> Evolution<N, M, (), ()> {
+    /// Evolution creating genomes with `random` and changing them with `mutate`, without
+    /// crossover. Defaults to a population of 100 with the 20 fittest carried over, tournaments
+    /// of two and the seed zero.
+    pub fn new(random: N, mutate: M) -> Self {
+        Evolution {
+            random,
+            mutate,
+            crossover: (),
+            crossover_rate: 0.0,
+            progress: (),
+            population: 100,
+            elitism: 20,
+            newcomers: 0,
+            selection: Selection::Tournament(2),
+            stagnation: None,
+            generations: None,
+            seed: 0,
+        }
+    }
+}
+
+impl<N, M, X, P> Evolution<N, M, X, P> {
+    /// Number of genomes in every generation.
+    pub fn population(mut self, population: usize) -> Self {
+        assert!(population > 0, "population cannot be empty");
+        self.population = population;
+        self
+    }
+
+    /// Number of the fittest genomes carried over to the next generation unchanged.
+    pub fn elitism(mut self, elitism: usize) -> Self {
+        self.elitism = elitism;
+        self
+    }
+
+    /// Number of new random genomes in every generation in addition to the bred ones.
+    pub fn newcomers(mut self, newcomers: usize) -> Self {
+        self.newcomers = newcomers;
+        self
+    }
+
+    pub fn selection(mut self, selection: Selection) -> Self {
+        if let Selection::Tournament(size) = selection {
+            assert!(size > 0, "tournament needs at least one genome");
+        }
+        self.selection = selection;
+        self
+    }
+
+    /// Keeps only the `keep` fittest genomes when the best fitness has not improved for
+    /// `generations`, filling the rest of the population with random ones.
+    pub fn restart_after(mut self, generations: usize, keep: usize) -> Self {
+        self.stagnation = Some((generations, keep));
+        self
+    }
+
+    /// Gives up after the number of generations.
+    pub fn generations(mut self, generations: usize) -> Self {
+        self.generations = Some(generations);
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Crosses the parents into a child with `crossover` at the given rate, otherwise the child is
+    /// a copy of a parent. The child is mutated in either case.
+    pub fn crossover<X2>(self, crossover: X2, rate: f64) -> Evolution<N, M, X2, P> {
+        assert!((0.0..=1.0).contains(&rate), "rate is a probability");
+        Evolution {
+            random: self.random,
+            mutate: self.mutate,
+            crossover,
+            crossover_rate: rate,
+            progress: self.progress,
+            population: self.population,
+            elitism: self.elitism,
+            newcomers: self.newcomers,
+            selection: self.selection,
+            stagnation: self.stagnation,
+            generations: self.generations,
+            seed: self.seed,
+        }
+    }
+
+    /// Calls `progress` after every generation has been scored.
+    pub fn progress<P2>(self, progress: P2) -> Evolution<N, M, X, P2> {
+        Evolution {
+            random: self.random,
+            mutate: self.mutate,
+            crossover: self.crossover,
+            crossover_rate: self.crossover_rate,
+            progress,
+            population: self.population,
+            elitism: self.elitism,
+            newcomers: self.newcomers,
+            selection: self.selection,
+            stagnation: self.stagnation,
+            generations: self.generations,
+            seed: self.seed,
+        }
+    }
+
+    /// Evolves the population until `fitness` reports a solution, or returns `None` when the
+    /// generations run out.
+    pub fn run<G, A, F>(&mut self, mut fitness: F) -> Option<Solution<G, A>>
+    where
+        G: Clone,
+        N: FnMut(&mut ChaCha8Rng) -> G,
+        M: FnMut(&mut G, &mut ChaCha8Rng),
+        X: Crossover<G>,
+        P: Observer,
+        F: FnMut(&G) -> Score<A>,
+    {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let mut population: Vec<(G, usize)> = Vec::with_capacity(self.population);
+        let mut progress = Progress::default();
+        let mut best_so_far = None;
+        let mut stale = 0;
+        // the first generation and the one after a restart are filled with random genomes
+        let mut breed = false;
+
+        loop {
+            if self.generations.is_some_and(|g| progress.generation >= g) {
+                return None;
+            }
+
+            let generation = progress.generation;
+            let evaluated = &mut progress.evaluated;
+
+            let mut score = |genome: G, population: &mut Vec<(G, usize)>| {
+                *evaluated += 1;
+                match fitness(&genome) {
+                    Score::Fitness(f) => {
+                        population.push((genome, f));
+                        None
+                    }
+                    Score::Solved(answer) => Some(Solution {
+                        genome,
+                        answer,
+                        generation,
+                    }),
+                }
+            };
+
+            if breed {
+                let parents = std::mem::take(&mut population);
+                population.extend(parents.iter().take(self.elitism).cloned());
+
+                let bred = self.population.saturating_sub(self.newcomers);
+                while population.len() < bred {
+                    let first = self.select(&parents, &mut rng);
+                    let mut child = if rng.gen_bool(self.crossover_rate) {
+                        let second = self.select(&parents, &mut rng);
+                        self.crossover.cross(first, second, &mut rng)
+                    } else {
+                        first.clone()
+                    };
+                    (self.mutate)(&mut child, &mut rng);
+                    if let Some(solution) = score(child, &mut population) {
+                        return Some(solution);
+                    }
+                }
+            }
+
+            while population.len() < self.population {
+                if let Some(solution) = score((self.random)(&mut rng), &mut population) {
+                    return Some(solution);
+                }
+            }
+
+            // stable so that the older genomes stay ahead of the equally fit new ones
+            population.sort_by_key(|(_, f)| Reverse(*f));
+
+            progress.best = population.first().map(|(_, f)| *f).unwrap_or(0);
+            progress.worst = population.last().map(|(_, f)| *f).unwrap_or(0);
+            self.progress.observe(&progress);
+
+            if best_so_far.map(|best| progress.best > best).unwrap_or(true) {
+                best_so_far = Some(progress.best);
+                stale = 0;
+            } else {
+                stale += 1;
+            }
+
+            breed = true;
+
+            if let Some((generations, keep)) = self.stagnation {
+                if stale > generations {
+                    population.truncate(keep);
+                    stale = 0;
+                    breed = false;
+                }
+            }
+
+            progress.generation += 1;
+        }
+    }
+
+    /// Picks a parent from the population sorted by descending fitness.
+    fn select<'a, G>(&self, population: &'a [(G, usize)], rng: &mut ChaCha8Rng) -> &'a G {
+        let index = match self.selection {
+            // the lowest index is the fittest
+            Selection::Tournament(size) => (0..size)
+                .map(|_| rng.gen_range(0, population.len()))
+                .min()
+                .unwrap(),
+            Selection::Roulette => {
+                let total = population.iter().map(|(_, f)| f + 1).sum::<usize>();
+                let mut ball = rng.gen_range(0, total);
+                population
+                    .iter()
+                    .position(|(_, f)| match ball.checked_sub(f + 1) {
+                        Some(rest) => {
+                            ball = rest;
+                            false
+                        }
+                        None => true,
+                    })
+                    .unwrap()
+            }
+        };
+        &population[index].0
+    }
+}
+
+/// Crosses two parents into a child. Implemented for the closures, and for `()` which is no
+/// crossover at all.
+pub trait Crossover<G> {
+    fn cross(&mut self, first: &G, second: &G, rng: &mut ChaCha8Rng) -> G;
+}
+
+impl<G: Clone> Crossover<G> for () {
+    fn cross(&mut self, first: &G, _: &G, _: &mut ChaCha8Rng) -> G {
+        first.clone()
+    }
+}
+
+impl<G, F: FnMut(&G, &G, &mut ChaCha8Rng) -> G> Crossover<G> for F {
+    fn cross(&mut self, first: &G, second: &G, rng: &mut ChaCha8Rng) -> G {
+        self(first, second, rng)
+    }
+}
+
+/// Observes the progress of the evolution. Implemented for the closures, and for `()` which does
+/// nothing.
+pub trait Observer {
+    fn observe(&mut self, progress: &Progress);
+}
+
+impl Observer for () {
+    fn observe(&mut self, _: &Progress) {}
+}
+
+impl<F: FnMut(&Progress)> Observer for F {
+    fn observe(&mut self, progress: &Progress) {
+        self(progress)
+    }
+}
+
+/// One point crossover of two sequences, taking the start of the first and the end of the
+/// second.
+pub fn splice<T: Clone>(first: &[T], second: &[T], rng: &mut ChaCha8Rng) -> Vec<T> {
+    let at = rng.gen_range(0, first.len().min(second.len()) + 1);
+    first[..at].iter().chain(&second[at..]).cloned().collect()
+}
+
+#[cfg(test)]
+const BITS: u32 = (1 << 24) - 1;
+
+#[cfg(test)]
+fn random_bits(rng: &mut ChaCha8Rng) -> u32 {
+    rng.gen::<u32>() & BITS
+}
+
+#[cfg(test)]
+fn flip_bit(genome: &mut u32, rng: &mut ChaCha8Rng) {
+    *genome ^= 1 << rng.gen_range(0, 24);
+}
+
+#[cfg(test)]
+fn count_ones(genome: &u32) -> Score<u32> {
+    match genome.count_ones() {
+        24 => Score::Solved(24),
+        n => Score::Fitness(n as usize),
+    }
+}
+
+#[test]
+fn evolves_all_ones() {
+    for selection in [Selection::Tournament(3), Selection::Roulette].iter() {
+        let found = Evolution::new(random_bits, flip_bit)
+            .population(30)
+            .elitism(5)
+            .selection(*selection)
+            .generations(1000)
+            .run(count_ones)
+            .unwrap();
+        assert_eq!(found.genome, BITS);
+        assert_eq!(found.answer, 24);
+    }
+}
+
+#[test]
+fn same_seed_same_run() {
+    let run = |seed| {
+        let mut seen = Vec::new();
+        let found = Evolution::new(random_bits, flip_bit)
+            .population(30)
+            .elitism(5)
+            .crossover(
+                |a: &u32, b: &u32, rng: &mut ChaCha8Rng| {
+                    let low = (1 << rng.gen_range(0, 25)) - 1;
+                    a & low | b & !low
+                },
+                0.5,
+            )
+            .seed(seed)
+            .progress(|p: &Progress| seen.push(*p))
+            .run(count_ones)
+            .unwrap();
+        (found.generation, seen)
+    };
+
+    let (generation, seen) = run(7);
+    assert_eq!((generation, seen.clone()), run(7));
+    assert_eq!(seen.len(), generation);
+    assert!(seen.windows(2).all(|w| w[0].best <= w[1].best));
+    assert_eq!(seen.last().unwrap().evaluated, 30 + (seen.len() - 1) * 25);
+}
+
+#[test]
+fn restarts_with_random_genomes() {
+    let mut seen = Vec::new();
+    let found = Evolution::new(random_bits, flip_bit)
+        .population(10)
+        .elitism(2)
+        .restart_after(1, 3)
+        .generations(5)
+        .progress(|p: &Progress| seen.push(p.evaluated))
+        .run(|_: &u32| Score::<()>::Fitness(0));
+    assert_eq!(found, None);
+    // the third generation did not improve and the fourth refilled 7 after keeping 3
+    assert_eq!(seen, vec![10, 18, 26, 33, 41]);
+}
+
+#[test]
+fn no_generations_scores_nothing() {
+    let mut scored = 0;
+    let found = Evolution::new(random_bits, flip_bit)
+        .generations(0)
+        .run(|_: &u32| {
+            scored += 1;
+            Score::Solved(())
+        });
+    assert_eq!(found, None);
+    assert_eq!(scored, 0);
+}
+
+#[test]
+fn splices_sequences() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    for _ in 0..20 {
+        let child = splice(&[1, 1, 1, 1], &[2, 2], &mut rng);
+        let at = child.iter().take_while(|&&x| x == 1).count();
+        assert!(at <= 2);
+        assert_eq!(child.len(), 2);
+        assert!(child[at..].iter().all(|&x| x == 2));
+    }
+}
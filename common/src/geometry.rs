@@ -0,0 +1,519 @@
+//! Points, directions and bounding boxes of any dimension with integer coordinates.
+//!
+//! Two dimensional helpers follow the screen convention of y growing downwards, so
+//! `Direction::Up` is `(0, -1)`.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+pub type Coord = i64;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<const N: usize>(pub [Coord; N]);
+
+pub type Point2 = Point<2>;
+pub type Point3 = Point<3>;
+pub type Point4 = Point<4>;
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Self {
+        Self::ORIGIN
+    }
+}
+
+impl<const N: usize> fmt::Debug for Point<N> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let mut t = fmt.debug_tuple("");
+        for c in &self.0 {
+            t.field(c);
+        }
+        t.finish()
+    }
+}
+
+impl<const N: usize> Point<N> {
+    pub const ORIGIN: Point<N> = Point([0; N]);
+
+    pub fn new(coords: [Coord; N]) -> Self {
+        Point(coords)
+    }
+
+    pub fn coords(&self) -> &[Coord; N] {
+        &self.0
+    }
+
+    /// Point with the given value in `axis` and zeroes elsewhere.
+    pub fn unit(axis: usize, value: Coord) -> Self {
+        let mut p = Self::ORIGIN;
+        p.0[axis] = value;
+        p
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> Coord {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| (a - b).abs())
+            .sum()
+    }
+
+    pub fn chebyshev_distance(&self, other: &Self) -> Coord {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| (a - b).abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Componentwise signum, useful for stepping towards another point.
+    pub fn signum(&self) -> Self {
+        self.map(Coord::signum)
+    }
+
+    pub fn map<F: FnMut(Coord) -> Coord>(&self, mut f: F) -> Self {
+        let mut out = *self;
+        out.0.iter_mut().for_each(|c| *c = f(*c));
+        out
+    }
+
+    pub fn zip_with<F: FnMut(Coord, Coord) -> Coord>(&self, other: &Self, mut f: F) -> Self {
+        let mut out = *self;
+        out.0
+            .iter_mut()
+            .zip(&other.0)
+            .for_each(|(c, o)| *c = f(*c, *o));
+        out
+    }
+
+    /// The `2 * N` neighbours differing by one in exactly one axis.
+    pub fn orthogonal_neighbours(&self) -> impl Iterator<Item = Self> {
+        let p = *self;
+        (0..N).flat_map(move |axis| {
+            let mut minus = p;
+            let mut plus = p;
+            minus.0[axis] -= 1;
+            plus.0[axis] += 1;
+            std::iter::once(minus).chain(std::iter::once(plus))
+        })
+    }
+
+    /// The `3^N - 1` neighbours differing by at most one in every axis, including diagonals.
+    pub fn neighbours(&self) -> impl Iterator<Item = Self> {
+        let p = *self;
+        let total = 3usize.pow(N as u32);
+        (0..total).filter_map(move |mut index| {
+            let mut out = p;
+            let mut all_zero = true;
+            for c in out.0.iter_mut() {
+                let d = (index % 3) as Coord - 1;
+                index /= 3;
+                all_zero &= d == 0;
+                *c += d;
+            }
+            if all_zero {
+                None
+            } else {
+                Some(out)
+            }
+        })
+    }
+}
+
+impl Point<2> {
+    pub fn x(&self) -> Coord {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> Coord {
+        self.0[1]
+    }
+
+    /// Quarter turn counterclockwise around the origin as seen on screen.
+    pub fn rotate_left(&self) -> Self {
+        Point([self.y(), -self.x()])
+    }
+
+    /// Quarter turn clockwise around the origin as seen on screen.
+    pub fn rotate_right(&self) -> Self {
+        Point([-self.y(), self.x()])
+    }
+}
+
+impl Point<3> {
+    pub fn x(&self) -> Coord {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> Coord {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> Coord {
+        self.0[2]
+    }
+
+    /// Quarter turn around the x axis using the right hand rule.
+    pub fn rotate_x(&self) -> Self {
+        Point([self.x(), -self.z(), self.y()])
+    }
+
+    /// Quarter turn around the y axis using the right hand rule.
+    pub fn rotate_y(&self) -> Self {
+        Point([self.z(), self.y(), -self.x()])
+    }
+
+    /// Quarter turn around the z axis using the right hand rule.
+    pub fn rotate_z(&self) -> Self {
+        Point([-self.y(), self.x(), self.z()])
+    }
+
+    /// All 24 orientations of this point reachable by quarter turns, starting with itself.
+    pub fn rotations(&self) -> impl Iterator<Item = Self> {
+        let p = *self;
+        // face each of the six directions by turning around z or y, then spin around x
+        let facings = [
+            p,
+            p.rotate_z(),
+            p.rotate_z().rotate_z(),
+            p.rotate_z().rotate_z().rotate_z(),
+            p.rotate_y(),
+            p.rotate_y().rotate_y().rotate_y(),
+        ];
+        IntoIterator::into_iter(facings)
+            .flat_map(|f| std::iter::successors(Some(f), |p| Some(p.rotate_x())).take(4))
+    }
+}
+
+impl<const N: usize> From<[Coord; N]> for Point<N> {
+    fn from(coords: [Coord; N]) -> Self {
+        Point(coords)
+    }
+}
+
+impl From<(Coord, Coord)> for Point<2> {
+    fn from((x, y): (Coord, Coord)) -> Self {
+        Point([x, y])
+    }
+}
+
+impl From<Point<2>> for (Coord, Coord) {
+    fn from(p: Point<2>) -> Self {
+        (p.0[0], p.0[1])
+    }
+}
+
+impl From<(Coord, Coord, Coord)> for Point<3> {
+    fn from((x, y, z): (Coord, Coord, Coord)) -> Self {
+        Point([x, y, z])
+    }
+}
+
+impl From<Point<3>> for (Coord, Coord, Coord) {
+    fn from(p: Point<3>) -> Self {
+        (p.0[0], p.0[1], p.0[2])
+    }
+}
+
+impl<const N: usize> Index<usize> for Point<N> {
+    type Output = Coord;
+
+    fn index(&self, axis: usize) -> &Coord {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for Point<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut Coord {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize, T: Into<Point<N>>> Add<T> for Point<N> {
+    type Output = Point<N>;
+
+    fn add(self, rhs: T) -> Self::Output {
+        self.zip_with(&rhs.into(), |a, b| a + b)
+    }
+}
+
+impl<const N: usize, T: Into<Point<N>>> AddAssign<T> for Point<N> {
+    fn add_assign(&mut self, rhs: T) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize, T: Into<Point<N>>> Sub<T> for Point<N> {
+    type Output = Point<N>;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        self.zip_with(&rhs.into(), |a, b| a - b)
+    }
+}
+
+impl<const N: usize, T: Into<Point<N>>> SubAssign<T> for Point<N> {
+    fn sub_assign(&mut self, rhs: T) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> Mul<Coord> for Point<N> {
+    type Output = Point<N>;
+
+    fn mul(self, rhs: Coord) -> Self::Output {
+        self.map(|c| c * rhs)
+    }
+}
+
+impl<const N: usize> Neg for Point<N> {
+    type Output = Point<N>;
+
+    fn neg(self) -> Self::Output {
+        self.map(|c| -c)
+    }
+}
+
+/// The four directions on a screen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions in clockwise order starting from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_left(&self) -> Self {
+        use Direction::*;
+        match *self {
+            Up => Left,
+            Right => Up,
+            Down => Right,
+            Left => Down,
+        }
+    }
+
+    pub fn turn_right(&self) -> Self {
+        use Direction::*;
+        match *self {
+            Up => Right,
+            Right => Down,
+            Down => Left,
+            Left => Up,
+        }
+    }
+
+    pub fn reverse(&self) -> Self {
+        use Direction::*;
+        match *self {
+            Up => Down,
+            Right => Left,
+            Down => Up,
+            Left => Right,
+        }
+    }
+
+    pub fn to_point(&self) -> Point<2> {
+        use Direction::*;
+        match *self {
+            Up => Point([0, -1]),
+            Right => Point([1, 0]),
+            Down => Point([0, 1]),
+            Left => Point([-1, 0]),
+        }
+    }
+}
+
+impl From<Direction> for Point<2> {
+    fn from(d: Direction) -> Self {
+        d.to_point()
+    }
+}
+
+impl From<&Direction> for Point<2> {
+    fn from(d: &Direction) -> Self {
+        d.to_point()
+    }
+}
+
+/// Axis aligned bounding box with inclusive corners.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct BoundingBox<const N: usize> {
+    min: Point<N>,
+    max: Point<N>,
+}
+
+impl<const N: usize> BoundingBox<N> {
+    pub fn from_point(p: Point<N>) -> Self {
+        BoundingBox { min: p, max: p }
+    }
+
+    /// Smallest box containing both of the corners, in any order.
+    pub fn from_corners(a: Point<N>, b: Point<N>) -> Self {
+        BoundingBox {
+            min: a.zip_with(&b, Coord::min),
+            max: a.zip_with(&b, Coord::max),
+        }
+    }
+
+    /// Smallest box containing all of the points, or `None` if there were no points.
+    pub fn from_points<I: IntoIterator<Item = Point<N>>>(points: I) -> Option<Self> {
+        let mut iter = points.into_iter();
+        let first = iter.next()?;
+        Some(iter.fold(Self::from_point(first), |mut bb, p| {
+            bb.extend(p);
+            bb
+        }))
+    }
+
+    pub fn min(&self) -> Point<N> {
+        self.min
+    }
+
+    pub fn max(&self) -> Point<N> {
+        self.max
+    }
+
+    /// Grows the box to contain the given point.
+    pub fn extend(&mut self, p: Point<N>) {
+        self.min = self.min.zip_with(&p, Coord::min);
+        self.max = self.max.zip_with(&p, Coord::max);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        BoundingBox {
+            min: self.min.zip_with(&other.min, Coord::min),
+            max: self.max.zip_with(&other.max, Coord::max),
+        }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = self.min.zip_with(&other.min, Coord::max);
+        let max = self.max.zip_with(&other.max, Coord::min);
+        if min.0.iter().zip(&max.0).all(|(a, b)| a <= b) {
+            Some(BoundingBox { min, max })
+        } else {
+            None
+        }
+    }
+
+    /// Box grown by `amount` in every direction.
+    pub fn grown(&self, amount: Coord) -> Self {
+        BoundingBox {
+            min: self.min.map(|c| c - amount),
+            max: self.max.map(|c| c + amount),
+        }
+    }
+
+    pub fn contains(&self, p: &Point<N>) -> bool {
+        (0..N).all(|axis| self.min[axis] <= p[axis] && p[axis] <= self.max[axis])
+    }
+
+    /// Number of points along each axis.
+    pub fn size(&self) -> [Coord; N] {
+        let mut size = [0; N];
+        for (axis, s) in size.iter_mut().enumerate() {
+            *s = self.max[axis] - self.min[axis] + 1;
+        }
+        size
+    }
+
+    /// Number of points contained in the box.
+    pub fn volume(&self) -> u64 {
+        self.size().iter().map(|&s| s as u64).product()
+    }
+
+    /// All of the points in the box, with the first axis changing the fastest.
+    pub fn points(&self) -> impl Iterator<Item = Point<N>> {
+        let min = self.min;
+        let size = self.size();
+        (0..self.volume()).map(move |mut index| {
+            let mut p = min;
+            for axis in 0..N {
+                let s = size[axis] as u64;
+                p[axis] += (index % s) as Coord;
+                index /= s;
+            }
+            p
+        })
+    }
+}
+
+#[test]
+fn distances() {
+    let a = Point([1, -2, 3]);
+    let b = Point([-1, 2, 4]);
+    assert_eq!(a.manhattan_distance(&b), 7);
+    assert_eq!(a.chebyshev_distance(&b), 4);
+    assert_eq!(a + b, Point([0, 0, 7]));
+    assert_eq!(a - b, Point([2, -4, -1]));
+    assert_eq!(-a * 2, Point([-2, 4, -6]));
+}
+
+#[test]
+fn neighbour_counts() {
+    use std::collections::HashSet;
+
+    let p = Point([5, 5, 5, 5]);
+    let all = p.neighbours().collect::<HashSet<_>>();
+    assert_eq!(all.len(), 80);
+    assert!(!all.contains(&p));
+    assert!(all.iter().all(|n| n.chebyshev_distance(&p) == 1));
+
+    let orthogonal = Point([0, 0]).orthogonal_neighbours().collect::<Vec<_>>();
+    assert_eq!(
+        orthogonal,
+        vec![Point([-1, 0]), Point([1, 0]), Point([0, -1]), Point([0, 1])]
+    );
+}
+
+#[test]
+fn rotations_2d_follow_directions() {
+    for d in Direction::ALL.iter() {
+        assert_eq!(d.to_point().rotate_right(), d.turn_right().to_point());
+        assert_eq!(d.to_point().rotate_left(), d.turn_left().to_point());
+        assert_eq!(d.to_point() + d.reverse(), Point2::ORIGIN);
+    }
+}
+
+#[test]
+fn all_3d_rotations_are_unique() {
+    use std::collections::HashSet;
+
+    let p = Point([1, 2, 3]);
+    let all = p.rotations().collect::<HashSet<_>>();
+    assert_eq!(all.len(), 24);
+    assert!(all.contains(&p));
+    assert_eq!(p.rotate_z().rotate_z().rotate_z().rotate_z(), p);
+}
+
+#[test]
+fn bounding_box() {
+    let bb = BoundingBox::from_points(vec![Point([2, -1]), Point([-1, 3]), Point([0, 0])]).unwrap();
+    assert_eq!(bb.min(), Point([-1, -1]));
+    assert_eq!(bb.max(), Point([2, 3]));
+    assert_eq!(bb.size(), [4, 5]);
+    assert_eq!(bb.volume(), 20);
+    assert_eq!(bb.points().count(), 20);
+    assert!(bb.points().all(|p| bb.contains(&p)));
+    assert_eq!(bb.points().next(), Some(Point([-1, -1])));
+    assert_eq!(bb.points().nth(1), Some(Point([0, -1])));
+    assert!(!bb.contains(&Point([3, 0])));
+
+    let other = BoundingBox::from_corners(Point([5, 5]), Point([1, 2]));
+    assert_eq!(
+        bb.intersection(&other),
+        Some(BoundingBox::from_corners(Point([1, 2]), Point([2, 3])))
+    );
+    assert_eq!(bb.union(&other).volume(), 7 * 7);
+    assert_eq!(bb.grown(1).size(), [6, 7]);
+    assert_eq!(BoundingBox::<2>::from_points(vec![]), None);
+}
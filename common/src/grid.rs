@@ -0,0 +1,309 @@
+//! Fixed size two dimensional maps stored row by row, as most of the puzzle inputs are.
+
+use crate::geometry::{Coord, Point2};
+use std::fmt;
+use std::io::BufRead;
+use std::ops::{Index, IndexMut};
+
+/// Width and height of a grid, mapping between points and indices of the row by row storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Dimensions {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Dimensions {
+    pub fn new(width: usize, height: usize) -> Self {
+        Dimensions { width, height }
+    }
+
+    pub fn len(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, p: &Point2) -> bool {
+        p.x() >= 0 && p.y() >= 0 && (p.x() as usize) < self.width && (p.y() as usize) < self.height
+    }
+
+    pub fn to_index(&self, p: &Point2) -> Option<usize> {
+        if self.contains(p) {
+            Some(p.y() as usize * self.width + p.x() as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_point(&self, index: usize) -> Point2 {
+        assert!(index < self.len(), "index {} out of {:?}", index, self);
+        Point2::new([(index % self.width) as Coord, (index / self.width) as Coord])
+    }
+
+    /// All points row by row, in the same order as the cells are stored.
+    pub fn points(&self) -> impl Iterator<Item = Point2> {
+        let d = *self;
+        (0..d.len()).map(move |i| d.to_point(i))
+    }
+
+    /// Points starting from the one after `from` in steps of `step` for as long as they are
+    /// inside the grid, paired with their indices.
+    pub fn ray(&self, from: Point2, step: Point2) -> impl Iterator<Item = (usize, Point2)> {
+        let d = *self;
+        std::iter::successors(Some(from + step), move |p| Some(*p + step))
+            .map(move |p| d.to_index(&p).map(|i| (i, p)))
+            .take_while(Option::is_some)
+            .map(Option::unwrap)
+    }
+}
+
+#[derive(Debug)]
+pub enum GridError<E> {
+    Io(std::io::Error),
+    /// Line (starting from zero) did not have the same length as the first line.
+    UnevenLine {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// Cell could not be converted.
+    Cell {
+        line: usize,
+        column: usize,
+        error: E,
+    },
+}
+
+impl<E: fmt::Display> fmt::Display for GridError<E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::Io(e) => write!(fmt, "failed to read grid: {}", e),
+            GridError::UnevenLine {
+                line,
+                expected,
+                found,
+            } => write!(
+                fmt,
+                "line {} has {} cells, expected {}",
+                line + 1,
+                found,
+                expected
+            ),
+            GridError::Cell {
+                line,
+                column,
+                error,
+            } => write!(fmt, "bad cell at {}:{}: {}", line + 1, column + 1, error),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for GridError<E> {}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid<T> {
+    dimensions: Dimensions,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(dimensions: Dimensions, cells: Vec<T>) -> Self {
+        assert_eq!(dimensions.len(), cells.len());
+        Grid { dimensions, cells }
+    }
+
+    pub fn filled(dimensions: Dimensions, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            dimensions,
+            cells: vec![value; dimensions.len()],
+        }
+    }
+
+    /// Parses non-empty lines of `s` converting each byte with `f`.
+    pub fn parse<F, E>(s: &str, f: F) -> Result<Self, GridError<E>>
+    where
+        F: FnMut(u8) -> Result<T, E>,
+    {
+        Self::from_reader(s.as_bytes(), f)
+    }
+
+    /// Reads non-empty lines until EOF converting each byte with `f`.
+    pub fn from_reader<R, F, E>(mut reader: R, mut f: F) -> Result<Self, GridError<E>>
+    where
+        R: BufRead,
+        F: FnMut(u8) -> Result<T, E>,
+    {
+        let mut buffer = String::new();
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut line = 0;
+
+        loop {
+            buffer.clear();
+            let read = reader.read_line(&mut buffer).map_err(GridError::Io)?;
+            if read == 0 {
+                break;
+            }
+
+            let trimmed = buffer.trim();
+            if trimmed.is_empty() {
+                line += 1;
+                continue;
+            }
+
+            match width {
+                Some(expected) if expected != trimmed.len() => {
+                    return Err(GridError::UnevenLine {
+                        line,
+                        expected,
+                        found: trimmed.len(),
+                    })
+                }
+                Some(_) => {}
+                None => width = Some(trimmed.len()),
+            }
+
+            for (column, b) in trimmed.bytes().enumerate() {
+                cells.push(f(b).map_err(|error| GridError::Cell {
+                    line,
+                    column,
+                    error,
+                })?);
+            }
+
+            line += 1;
+        }
+
+        let width = width.unwrap_or(0);
+        let height = cells.len().checked_div(width).unwrap_or(0);
+
+        Ok(Grid::new(Dimensions::new(width, height), cells))
+    }
+
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    pub fn width(&self) -> usize {
+        self.dimensions.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.dimensions.height
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn into_cells(self) -> Vec<T> {
+        self.cells
+    }
+
+    pub fn get(&self, p: &Point2) -> Option<&T> {
+        self.dimensions.to_index(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: &Point2) -> Option<&mut T> {
+        let index = self.dimensions.to_index(p)?;
+        Some(&mut self.cells[index])
+    }
+
+    /// Cells paired with their points, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.dimensions.points().zip(self.cells.iter())
+    }
+
+    /// The up to eight neighbouring cells which are inside the grid.
+    pub fn neighbours(&self, p: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        p.neighbours()
+            .filter_map(move |n| self.get(&n).map(|t| (n, t)))
+    }
+
+    /// The up to four orthogonally neighbouring cells which are inside the grid.
+    pub fn orthogonal_neighbours(&self, p: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        p.orthogonal_neighbours()
+            .filter_map(move |n| self.get(&n).map(|t| (n, t)))
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2) -> &T {
+        self.get(&p)
+            .unwrap_or_else(|| panic!("{:?} is outside of {:?}", p, self.dimensions))
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, p: Point2) -> &mut T {
+        let dimensions = self.dimensions;
+        self.get_mut(&p)
+            .unwrap_or_else(|| panic!("{:?} is outside of {:?}", p, dimensions))
+    }
+}
+
+#[cfg(test)]
+fn parse_example(s: &str) -> Result<Grid<bool>, GridError<char>> {
+    Grid::parse(s, |b| match b {
+        b'#' => Ok(true),
+        b'.' => Ok(false),
+        x => Err(x as char),
+    })
+}
+
+#[test]
+fn parse_and_index() {
+    let grid = parse_example("#..\n.#.\n\n..#\n.#.\n").unwrap();
+    assert_eq!(grid.dimensions(), Dimensions::new(3, 4));
+    assert!(grid[Point2::new([1, 1])]);
+    assert!(!grid[Point2::new([0, 1])]);
+    assert_eq!(grid.get(&Point2::new([3, 0])), None);
+    assert_eq!(grid.iter().filter(|(_, &lit)| lit).count(), 4);
+    assert_eq!(grid.neighbours(Point2::new([0, 0])).count(), 3);
+    assert_eq!(grid.orthogonal_neighbours(Point2::new([1, 1])).count(), 4);
+}
+
+#[test]
+fn parse_errors() {
+    match parse_example("#..\n.#\n") {
+        Err(GridError::UnevenLine {
+            line: 1,
+            expected: 3,
+            found: 2,
+        }) => {}
+        x => panic!("unexpected {:?}", x),
+    }
+
+    match parse_example("#..\n.x.\n") {
+        Err(GridError::Cell {
+            line: 1,
+            column: 1,
+            error: 'x',
+        }) => {}
+        x => panic!("unexpected {:?}", x),
+    }
+}
+
+#[test]
+fn rays_stop_at_edges() {
+    let d = Dimensions::new(4, 3);
+    let ray = d
+        .ray(Point2::new([0, 0]), Point2::new([1, 1]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ray,
+        vec![(5, Point2::new([1, 1])), (10, Point2::new([2, 2]))]
+    );
+    assert_eq!(d.points().nth(5), Some(Point2::new([1, 1])));
+}
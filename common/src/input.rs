@@ -0,0 +1,470 @@
+//! Puzzle inputs by year and day.
+//!
+//! Inputs are stored as `<dir>/<year>/dayNN/<set>`, where the set is [`DEFAULT_SET`] for the
+//! puzzle input and any other name for alternative inputs, for example from other people or the
+//! examples in the puzzle text. The SHA-256 of the inputs are listed in `<dir>/SHA256SUMS` in the
+//! format of `sha256sum`, so the listing can also be checked with `sha256sum -c`.
+//!
+//! The directory defaults to `inputs/` at the root of the repository and can be changed with the
+//! `AOC_INPUTS` environment variable.
+
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The name of the set for the actual puzzle input.
+pub const DEFAULT_SET: &str = "default";
+
+const CHECKSUMS: &str = "SHA256SUMS";
+
+pub type FetchError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Provides the puzzle inputs which are not yet stored.
+pub trait Fetch {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, FetchError>;
+}
+
+impl<F> Fetch for F
+where
+    F: Fn(u16, u8) -> Result<String, FetchError>,
+{
+    fn fetch(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        self(year, day)
+    }
+}
+
+/// Downloads the inputs with `curl` using the session cookie of a logged in browser.
+///
+/// The cookie is passed to `curl` on its standard input so that the session does not show up in
+/// the process listing.
+pub struct CurlFetcher {
+    session: String,
+    base_url: String,
+}
+
+impl CurlFetcher {
+    pub fn new(session: String) -> Self {
+        CurlFetcher {
+            session,
+            base_url: "https://adventofcode.com".to_string(),
+        }
+    }
+
+    /// Creates a fetcher if the `AOC_SESSION` environment variable is set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var("AOC_SESSION").ok().map(CurlFetcher::new)
+    }
+
+    /// Fetches from another server, for example `http://127.0.0.1:8080`.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+}
+
+impl Fetch for CurlFetcher {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--header", "@-"])
+            .arg(&url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        {
+            let mut stdin = child.stdin.take().expect("stdin is piped");
+            writeln!(stdin, "Cookie: session={}", self.session)?;
+        }
+
+        let output = child.wait_with_output()?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "curl {} failed with {}: {}",
+                url,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .into());
+        }
+
+        Ok(String::from_utf8(output.stdout)?)
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// There was no input and it could not be fetched.
+    Missing {
+        path: PathBuf,
+    },
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    /// The input has changed since its checksum was recorded.
+    ChecksumMismatch {
+        path: PathBuf,
+        expected: String,
+        found: String,
+    },
+    Fetch {
+        year: u16,
+        day: u8,
+        error: FetchError,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Missing { path } => {
+                write!(fmt, "input missing, expected it at {}", path.display())
+            }
+            InputError::Io { path, error } => {
+                write!(fmt, "failed to read {}: {}", path.display(), error)
+            }
+            InputError::ChecksumMismatch {
+                path,
+                expected,
+                found,
+            } => write!(
+                fmt,
+                "checksum of {} is {} but {} was recorded",
+                path.display(),
+                found,
+                expected
+            ),
+            InputError::Fetch { year, day, error } => {
+                write!(
+                    fmt,
+                    "failed to fetch input of {} day {}: {}",
+                    year, day, error
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub struct InputStore {
+    dir: PathBuf,
+    fetcher: Option<Box<dyn Fetch>>,
+}
+
+impl InputStore {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        InputStore {
+            dir: dir.into(),
+            fetcher: None,
+        }
+    }
+
+    /// Store in `AOC_INPUTS` or the `inputs/` of the repository.
+    pub fn from_env() -> Self {
+        let dir = std::env::var_os("AOC_INPUTS")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("..")
+                    .join("inputs")
+            });
+        Self::new(dir)
+    }
+
+    /// Fetches the missing puzzle inputs of the default set, storing them for later use.
+    pub fn with_fetcher<F: Fetch + 'static>(mut self, fetcher: F) -> Self {
+        self.fetcher = Some(Box::new(fetcher));
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, year: u16, day: u8, set: &str) -> PathBuf {
+        self.dir.join(relative(year, day, set))
+    }
+
+    /// Names of the sets stored for the day, in order.
+    pub fn sets(&self, year: u16, day: u8) -> io::Result<Vec<String>> {
+        let dir = self.path(year, day, "");
+        let mut sets = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries
+                .filter_map(|e| e.map(|e| e.file_name().into_string().ok()).transpose())
+                .collect::<io::Result<Vec<_>>>()?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        sets.sort();
+        Ok(sets)
+    }
+
+    /// Reads the input, fetching it first if it is missing and a fetcher has been configured.
+    pub fn read(&self, year: u16, day: u8, set: &str) -> Result<String, InputError> {
+        let path = self.path(year, day, set);
+
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return match (set, self.fetcher.as_ref()) {
+                    (DEFAULT_SET, Some(fetcher)) => {
+                        let contents = fetcher
+                            .fetch(year, day)
+                            .map_err(|error| InputError::Fetch { year, day, error })?;
+                        self.store(year, day, set, &contents)?;
+                        Ok(contents)
+                    }
+                    _ => Err(InputError::Missing { path }),
+                };
+            }
+            Err(error) => return Err(InputError::Io { path, error }),
+        };
+
+        let checksums = self.read_checksums()?;
+        let relative = relative(year, day, set);
+        if let Some((expected, _)) = checksums.iter().find(|(_, p)| *p == relative) {
+            let found = sha256(&contents);
+            if *expected != found {
+                return Err(InputError::ChecksumMismatch {
+                    path,
+                    expected: expected.clone(),
+                    found,
+                });
+            }
+        }
+
+        Ok(contents)
+    }
+
+    /// Writes the input and records its checksum, replacing any earlier one.
+    pub fn store(
+        &self,
+        year: u16,
+        day: u8,
+        set: &str,
+        contents: &str,
+    ) -> Result<PathBuf, InputError> {
+        let path = self.path(year, day, set);
+        let io_error = |path: &Path| {
+            let path = path.to_owned();
+            move |error| InputError::Io { path, error }
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(io_error(parent))?;
+        }
+        std::fs::write(&path, contents).map_err(io_error(&path))?;
+
+        let relative = relative(year, day, set);
+        let mut checksums = self.read_checksums()?;
+        checksums.retain(|(_, p)| *p != relative);
+        checksums.push((sha256(contents), relative));
+        checksums.sort_by(|a, b| a.1.cmp(&b.1));
+
+        let listing = checksums
+            .iter()
+            .map(|(sum, p)| format!("{}  {}\n", sum, p))
+            .collect::<String>();
+
+        let sums = self.dir.join(CHECKSUMS);
+        std::fs::write(&sums, listing).map_err(io_error(&sums))?;
+
+        Ok(path)
+    }
+
+    fn read_checksums(&self) -> Result<Vec<(String, String)>, InputError> {
+        let path = self.dir.join(CHECKSUMS);
+        let listing = match std::fs::read_to_string(&path) {
+            Ok(listing) => listing,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(InputError::Io { path, error }),
+        };
+
+        Ok(listing
+            .lines()
+            .filter_map(|line| {
+                let mut split = line.splitn(2, "  ");
+                Some((split.next()?.to_owned(), split.next()?.to_owned()))
+            })
+            .collect())
+    }
+}
+
+/// Path relative to the store directory, always with forward slashes as in the checksum listing.
+fn relative(year: u16, day: u8, set: &str) -> String {
+    format!("{}/day{:02}/{}", year, day, set)
+}
+
+fn sha256(contents: &str) -> String {
+    format!("{:x}", Sha256::digest(contents.as_bytes()))
+}
+
+#[cfg(test)]
+fn temp_store(name: &str) -> InputStore {
+    let dir = std::env::temp_dir().join(format!("aoc-input-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    InputStore::new(dir)
+}
+
+#[test]
+fn store_and_read_sets() {
+    let store = temp_store("sets");
+
+    match store.read(2019, 17, DEFAULT_SET) {
+        Err(InputError::Missing { path }) => assert!(path.ends_with("2019/day17/default")),
+        x => panic!("unexpected {:?}", x),
+    }
+
+    store.store(2019, 17, DEFAULT_SET, "1,2,3\n").unwrap();
+    store.store(2019, 17, "example", "99\n").unwrap();
+
+    assert_eq!(store.read(2019, 17, DEFAULT_SET).unwrap(), "1,2,3\n");
+    assert_eq!(store.read(2019, 17, "example").unwrap(), "99\n");
+    assert_eq!(store.sets(2019, 17).unwrap(), vec!["default", "example"]);
+    assert!(store.sets(2019, 18).unwrap().is_empty());
+
+    let sums = std::fs::read_to_string(store.dir().join(CHECKSUMS)).unwrap();
+    let expected = format!("{}  2019/day17/default", sha256("1,2,3\n"));
+    assert_eq!(sums.lines().next(), Some(expected.as_str()));
+
+    std::fs::remove_dir_all(store.dir()).unwrap();
+}
+
+#[test]
+fn checksum_mismatch() {
+    assert_eq!(
+        sha256("abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+
+    let store = temp_store("checksum");
+    let path = store.store(2020, 1, DEFAULT_SET, "1721\n979\n").unwrap();
+
+    std::fs::write(&path, "1721\r\n979\r\n").unwrap();
+
+    match store.read(2020, 1, DEFAULT_SET) {
+        Err(InputError::ChecksumMismatch {
+            expected, found, ..
+        }) => {
+            assert_eq!(expected, sha256("1721\n979\n"));
+            assert_eq!(found, sha256("1721\r\n979\r\n"));
+        }
+        x => panic!("unexpected {:?}", x),
+    }
+
+    std::fs::remove_dir_all(store.dir()).unwrap();
+}
+
+#[test]
+fn fetches_missing_default_set() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let fetched = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&fetched);
+
+    let store = temp_store("fetch").with_fetcher(move |year, day| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        if day == 25 {
+            Err("no such day yet".into())
+        } else {
+            Ok(format!("input of {} day {}\n", year, day))
+        }
+    });
+
+    assert_eq!(
+        store.read(2018, 3, DEFAULT_SET).unwrap(),
+        "input of 2018 day 3\n"
+    );
+    // the second read is from the stored copy
+    assert_eq!(
+        store.read(2018, 3, DEFAULT_SET).unwrap(),
+        "input of 2018 day 3\n"
+    );
+    assert_eq!(fetched.load(Ordering::SeqCst), 1);
+
+    // only the puzzle input can be fetched
+    match store.read(2018, 3, "example") {
+        Err(InputError::Missing { .. }) => {}
+        x => panic!("unexpected {:?}", x),
+    }
+
+    match store.read(2018, 25, DEFAULT_SET) {
+        Err(e @ InputError::Fetch { .. }) => {
+            assert_eq!(
+                e.to_string(),
+                "failed to fetch input of 2018 day 25: no such day yet"
+            )
+        }
+        x => panic!("unexpected {:?}", x),
+    }
+
+    std::fs::remove_dir_all(store.dir()).unwrap();
+}
+
+#[cfg(test)]
+fn serve_once(
+    status: &'static str,
+    body: &'static str,
+) -> (String, std::thread::JoinHandle<String>) {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request = String::new();
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                break;
+            }
+            request.push_str(&line);
+        }
+
+        let mut stream = stream;
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+        .unwrap();
+        request
+    });
+
+    (base_url, server)
+}
+
+#[test]
+fn curl_fetches_with_session_cookie() {
+    let (base_url, server) = serve_once("200 OK", "1721\n979\n");
+    let fetcher = CurlFetcher::new("53cr37".to_string()).base_url(base_url);
+
+    assert_eq!(fetcher.fetch(2020, 1).unwrap(), "1721\n979\n");
+
+    let request = server.join().unwrap();
+    assert!(request.starts_with("GET /2020/day/1/input HTTP/1.1\r\n"));
+    assert!(request.contains("\r\nCookie: session=53cr37\r\n"));
+
+    let (base_url, server) = serve_once("404 Not Found", "Not found\n");
+    let fetcher = CurlFetcher::new("53cr37".to_string()).base_url(base_url);
+
+    let error = fetcher.fetch(2020, 26).unwrap_err().to_string();
+    assert!(error.contains("/2020/day/26/input failed"), "{}", error);
+    assert!(!error.contains("53cr37"), "{}", error);
+
+    server.join().unwrap();
+}
@@ -0,0 +1,308 @@
+//! Reading the puzzle input from stdin or any other `BufRead`.
+
+use either::Either;
+use std::io::BufRead;
+use std::str::FromStr;
+
+pub fn try_fold_stdin<F, St, E>(initial: St, inner: F) -> Result<St, E>
+where
+    F: for<'a> FnMut(&mut St, &'a str) -> Result<(), E>,
+{
+    let stdin = std::io::stdin();
+    try_fold_lines(stdin.lock(), initial, inner)
+}
+
+/// Folds over the lines of `input` including the line endings.
+pub fn try_fold_lines<R, F, St, E>(mut input: R, initial: St, mut inner: F) -> Result<St, E>
+where
+    R: BufRead,
+    F: for<'a> FnMut(&mut St, &'a str) -> Result<(), E>,
+{
+    let mut buffer = String::new();
+
+    let mut state = initial;
+
+    loop {
+        buffer.clear();
+        let bytes = input
+            .read_line(&mut buffer)
+            .expect("Failed to read line from input");
+
+        if bytes == 0 {
+            break;
+        }
+
+        inner(&mut state, buffer.as_str())?;
+    }
+
+    Ok(state)
+}
+
+pub fn process_stdin_lines<F>(inner: F)
+where
+    F: for<'a> FnMut(&'a str),
+{
+    let stdin = std::io::stdin();
+    process_lines(stdin.lock(), inner)
+}
+
+/// Calls `inner` with each of the lines of `input` including the line endings.
+pub fn process_lines<R, F>(mut input: R, mut inner: F)
+where
+    R: BufRead,
+    F: for<'a> FnMut(&'a str),
+{
+    let mut buffer = String::new();
+
+    loop {
+        buffer.clear();
+        let bytes = input
+            .read_line(&mut buffer)
+            .expect("Failed to read line from input");
+
+        if bytes == 0 {
+            break;
+        }
+
+        inner(buffer.as_str());
+    }
+}
+
+/// Streaming reader of records separated by one or more blank lines, like the passports or the
+/// groups of answers of 2020. The lines of a record are joined with `\n` without their line
+/// endings, which can be either `\n` or `\r\n`, and the last line does not need one.
+///
+/// [`Records::read_next`] lends each record from a buffer which is reused between records, while
+/// the `Iterator` implementation yields owned records and [`Records::parse`] converts them with
+/// `FromStr`.
+pub struct Records<R> {
+    input: R,
+    line: String,
+    record: String,
+}
+
+impl<R: BufRead> Records<R> {
+    pub fn new(input: R) -> Self {
+        Self {
+            input,
+            line: String::new(),
+            record: String::new(),
+        }
+    }
+
+    /// Reads the next record, returning `None` at the end of input.
+    pub fn read_next(&mut self) -> Result<Option<&str>, std::io::Error> {
+        self.record.clear();
+
+        loop {
+            self.line.clear();
+            let read = self.input.read_line(&mut self.line)?;
+
+            match record_line(&self.line) {
+                Some(line) => {
+                    if !self.record.is_empty() {
+                        self.record.push('\n');
+                    }
+                    self.record.push_str(line);
+                }
+                None if read == 0 || !self.record.is_empty() => break,
+                // blank lines before the first record or repeated between records
+                None => {}
+            }
+        }
+
+        Ok(if self.record.is_empty() {
+            None
+        } else {
+            Some(&self.record)
+        })
+    }
+
+    /// Converts the records with `FromStr`.
+    pub fn parse<T: FromStr>(self) -> ParsedRecords<R, T> {
+        ParsedRecords {
+            records: self,
+            _type_of_t: Default::default(),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = Result<String, std::io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_next() {
+            Ok(Some(_)) => Some(Ok(std::mem::take(&mut self.record))),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Records of a [`Records`] reader converted with `FromStr`.
+pub struct ParsedRecords<R, T> {
+    records: Records<R>,
+    _type_of_t: std::marker::PhantomData<T>,
+}
+
+impl<R, T> Iterator for ParsedRecords<R, T>
+where
+    R: BufRead,
+    T: FromStr,
+{
+    type Item = Result<T, Either<T::Err, std::io::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.records.read_next() {
+            Ok(Some(record)) => Some(T::from_str(record).map_err(Either::Left)),
+            Ok(None) => None,
+            Err(e) => Some(Err(Either::Right(e))),
+        }
+    }
+}
+
+/// Records separated by blank lines borrowed from a string already in memory, split the same way
+/// as [`Records`] does. As the records are slices of the input, the line endings between the lines
+/// of a record are kept as they were.
+pub struct BorrowedRecords<'a> {
+    rest: &'a str,
+    line: usize,
+}
+
+impl<'a> BorrowedRecords<'a> {
+    pub fn new(input: &'a str) -> Self {
+        BorrowedRecords {
+            rest: input,
+            line: 1,
+        }
+    }
+
+    /// Yields the records along with the line number of their first line, counted from one.
+    pub fn numbered(mut self) -> impl Iterator<Item = (usize, &'a str)> {
+        std::iter::from_fn(move || self.next_numbered())
+    }
+
+    fn next_numbered(&mut self) -> Option<(usize, &'a str)> {
+        let rest = self.rest;
+        let mut start = None;
+        let mut end = 0;
+        let mut offset = 0;
+
+        for line in rest.split_inclusive('\n') {
+            let next = offset + line.len();
+
+            match record_line(line) {
+                Some(content) => {
+                    start.get_or_insert((self.line, offset));
+                    end = offset + content.len();
+                }
+                None => {
+                    if let Some((number, start)) = start {
+                        self.rest = &rest[next..];
+                        self.line += 1;
+                        return Some((number, &rest[start..end]));
+                    }
+                }
+            }
+
+            offset = next;
+            self.line += 1;
+        }
+
+        self.rest = "";
+        start.map(|(number, start)| (number, &rest[start..end]))
+    }
+}
+
+impl<'a> Iterator for BorrowedRecords<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        self.next_numbered().map(|(_, record)| record)
+    }
+}
+
+/// The line without its line ending, or `None` if it is blank and so separates records.
+fn record_line(line: &str) -> Option<&str> {
+    let line = line.trim_end_matches(&['\r', '\n'][..]);
+    if line.trim().is_empty() {
+        None
+    } else {
+        Some(line)
+    }
+}
+
+pub struct OnePerLine<I, T> {
+    input: I,
+    buffer: String,
+    _type_of_t: std::marker::PhantomData<T>,
+}
+
+impl<I: BufRead, T: FromStr> OnePerLine<I, T> {
+    pub fn new(input: I) -> Self {
+        Self {
+            input,
+            buffer: String::new(),
+            _type_of_t: Default::default(),
+        }
+    }
+}
+
+impl<I, T> Iterator for OnePerLine<I, T>
+where
+    I: BufRead,
+    T: FromStr + 'static,
+    T::Err: 'static,
+{
+    type Item = Result<T, Either<T::Err, std::io::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffer.clear();
+        let read = self.input.read_line(&mut self.buffer);
+        match read {
+            Ok(0) => None,
+            Ok(_) => match T::from_str(self.buffer.trim()) {
+                Ok(t) => Some(Ok(t)),
+                Err(e) => Some(Err(Either::Left(e))),
+            },
+            Err(e) => Some(Err(Either::Right(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+const RECORDS_EXAMPLE: &str = "\r\nab\r\nc\r\n\r\n\r\n  \nd\n\ne\nf";
+
+#[test]
+fn records_are_read_from_any_line_endings() {
+    let owned = Records::new(RECORDS_EXAMPLE.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(owned, vec!["ab\nc", "d", "e\nf"]);
+
+    let borrowed = BorrowedRecords::new(RECORDS_EXAMPLE).collect::<Vec<_>>();
+    assert_eq!(borrowed, vec!["ab\r\nc", "d", "e\nf"]);
+
+    assert_eq!(BorrowedRecords::new("\n\n").next(), None);
+    assert!(Records::new(&b"\n\n"[..]).next().is_none());
+
+    let numbered = BorrowedRecords::new(RECORDS_EXAMPLE)
+        .numbered()
+        .collect::<Vec<_>>();
+    assert_eq!(numbered, vec![(2, "ab\r\nc"), (7, "d"), (9, "e\nf")]);
+}
+
+#[test]
+fn records_are_lent_and_parsed() {
+    let mut records = Records::new(&b"a\nb\n\nc\n"[..]);
+    assert_eq!(records.read_next().unwrap(), Some("a\nb"));
+    assert_eq!(records.read_next().unwrap(), Some("c"));
+    assert_eq!(records.read_next().unwrap(), None);
+    assert_eq!(records.read_next().unwrap(), None);
+
+    let parsed = Records::new(&b"1\n\n2\n\nx"[..])
+        .parse::<u32>()
+        .map(|r| r.map_err(|e| e.left().is_some()))
+        .collect::<Vec<_>>();
+    assert_eq!(parsed, vec![Ok(1), Ok(2), Err(true)]);
+}
@@ -0,0 +1,11 @@
+// aoc-common: code shared by all of the years
+
+pub mod evolve;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod io;
+pub mod math;
+pub mod parse;
+pub mod search;
+pub mod solution;
@@ -0,0 +1,289 @@
+//! Small integer helpers needed every now and then, and the modular arithmetic of the puzzles
+//! about remainders and shuffles.
+
+use std::convert::TryInto;
+use std::fmt;
+use std::ops::{Div, Mul, Rem};
+
+/// Greatest common divisor of non-negative values.
+pub fn gcd<T>(mut a: T, mut b: T) -> T
+where
+    T: Copy + PartialEq + Default + Rem<Output = T>,
+{
+    let zero = T::default();
+    while b != zero {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+/// Least common multiple of non-negative values, zero if either is zero.
+pub fn lcm<T>(a: T, b: T) -> T
+where
+    T: Copy + PartialEq + Default + Rem<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    let zero = T::default();
+    if a == zero || b == zero {
+        zero
+    } else {
+        a / gcd(a, b) * b
+    }
+}
+
+/// Extended Euclidean algorithm, returning `(g, x, y)` where `g` is the non-negative greatest
+/// common divisor and `a * x + b * y == g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        let t = old_r - q * r;
+        old_r = r;
+        r = t;
+        let t = old_x - q * x;
+        old_x = x;
+        x = t;
+        let t = old_y - q * y;
+        old_y = y;
+        y = t;
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The value and the modulus did not have an inverse as their greatest common divisor is not one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotCoprime {
+    pub gcd: u64,
+}
+
+impl fmt::Display for NotCoprime {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            fmt,
+            "no modular inverse, greatest common divisor is {}",
+            self.gcd
+        )
+    }
+}
+
+impl std::error::Error for NotCoprime {}
+
+/// The `x` for which `a * x == 1 (mod m)`.
+pub fn mod_inverse(a: u64, m: u64) -> Result<u64, NotCoprime> {
+    let (g, x, _) = extended_gcd(a as i128, m as i128);
+    if g != 1 {
+        return Err(NotCoprime { gcd: g as u64 });
+    }
+    Ok(x.rem_euclid(m as i128) as u64)
+}
+
+/// `a * b % m` without overflowing.
+pub fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// `base.pow(exp) % m` without overflowing.
+pub fn pow_mod(base: u64, exp: u64, m: u64) -> u64 {
+    pow_mod_u128(base as u128, exp as u128, m as u128) as u64
+}
+
+/// `a * b % m` without overflowing for any `u128`, by doubling and adding.
+pub fn mul_mod_u128(a: u128, b: u128, m: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+
+    let add = |x: u128, y: u128| if x >= m - y { x - (m - y) } else { x + y };
+
+    let (mut a, mut b) = (a % m, b % m);
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add(result, a);
+        }
+        a = add(a, a);
+        b >>= 1;
+    }
+    result
+}
+
+/// `base.pow(exp) % m` without overflowing for any `u128`.
+pub fn pow_mod_u128(base: u128, mut exp: u128, m: u128) -> u128 {
+    let mut base = base % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod_u128(result, base, m);
+        }
+        base = mul_mod_u128(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Solves the system of `x == remainder (mod modulus)` congruences, returning the smallest
+/// non-negative `x` and the least common multiple of the moduli. The moduli do not need to be
+/// coprime. `None` if there is no solution or the combined modulus does not fit in `u64`.
+pub fn chinese_remainder(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    congruences
+        .iter()
+        .try_fold((0u64, 1u64), |(x, m), &(r, n)| {
+            let r = r % n;
+            let (g, p, _) = extended_gcd(m as i128, n as i128);
+            let diff = r as i128 - x as i128;
+            if diff % g != 0 {
+                return None;
+            }
+
+            let step = n / g as u64;
+            let lcm = (m as u128 * step as u128).try_into().ok()?;
+            // x + m * k, where m * k == diff (mod n), k == diff / g * p (mod n / g)
+            let k = (diff / g).rem_euclid(step as i128) as u128
+                * p.rem_euclid(step as i128) as u128
+                % step as u128;
+            let x = (x as u128 + m as u128 * k) % lcm as u128;
+            Some((x as u64, lcm))
+        })
+}
+
+/// Affine map `x => a * x + b (mod m)`, which composes and inverts as another affine map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Affine {
+    pub a: u64,
+    pub b: u64,
+    pub m: u64,
+}
+
+impl Affine {
+    /// Map with the coefficients reduced to `0..m`.
+    pub fn new(a: i64, b: i64, m: u64) -> Self {
+        let reduce = |x: i64| (x as i128).rem_euclid(m as i128) as u64;
+        Affine {
+            a: reduce(a),
+            b: reduce(b),
+            m,
+        }
+    }
+
+    pub fn identity(m: u64) -> Self {
+        Affine { a: 1 % m, b: 0, m }
+    }
+
+    pub fn apply(&self, x: u64) -> u64 {
+        ((mul_mod(self.a, x, self.m) as u128 + self.b as u128) % self.m as u128) as u64
+    }
+
+    /// Map applying `self` first and `next` on the result.
+    pub fn then(&self, next: &Affine) -> Affine {
+        assert_eq!(self.m, next.m, "maps of different moduli");
+        Affine {
+            a: mul_mod(next.a, self.a, self.m),
+            b: next.apply(self.b),
+            m: self.m,
+        }
+    }
+
+    /// Map applying `self` `n` times.
+    pub fn pow(&self, mut n: u64) -> Affine {
+        let mut result = Affine::identity(self.m);
+        let mut square = *self;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.then(&square);
+            }
+            square = square.then(&square);
+            n >>= 1;
+        }
+        result
+    }
+
+    /// Map undoing `self`, which exists if `a` is coprime with the modulus.
+    pub fn inverse(&self) -> Result<Affine, NotCoprime> {
+        let a = mod_inverse(self.a, self.m)?;
+        Ok(Affine {
+            a,
+            b: mul_mod(a, self.m - self.b, self.m),
+            m: self.m,
+        })
+    }
+}
+
+#[test]
+fn gcd_and_lcm() {
+    assert_eq!(gcd(12u64, 18), 6);
+    assert_eq!(gcd(0i64, 5), 5);
+    assert_eq!(gcd(7usize, 0), 7);
+    assert_eq!(lcm(4i64, 6), 12);
+    assert_eq!(lcm(0u32, 6), 0);
+    assert_eq!(lcm(lcm(18usize, 28), 44), 2772);
+}
+
+#[test]
+fn inverses() {
+    assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+    assert_eq!(extended_gcd(-4, 6).0, 2);
+    assert_eq!(mod_inverse(40, 7), Ok(3));
+    assert_eq!(mod_inverse(3, 11), Ok(4));
+    assert_eq!(mod_inverse(6, 9), Err(NotCoprime { gcd: 3 }));
+}
+
+#[test]
+fn no_overflow() {
+    let m = u64::MAX - 58; // largest 64-bit prime
+    assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+    // Fermat
+    assert_eq!(pow_mod(123_456_789, m - 1, m), 1);
+    assert_eq!(pow_mod(2, 10, 1000), 24);
+    assert_eq!(pow_mod(5, 0, 1), 0);
+
+    let m = u128::MAX - 158; // largest 128-bit prime
+    assert_eq!(mul_mod_u128(m - 1, m - 1, m), 1);
+    assert_eq!(pow_mod_u128(3, m - 1, m), 1);
+}
+
+#[test]
+fn chinese_remainders() {
+    assert_eq!(
+        chinese_remainder(&[(3, 5), (1, 7), (6, 8)]),
+        Some((78, 280))
+    );
+    // not coprime but consistent
+    assert_eq!(chinese_remainder(&[(2, 6), (8, 10)]), Some((8, 30)));
+    assert_eq!(chinese_remainder(&[(1, 6), (2, 10)]), None);
+    assert_eq!(chinese_remainder(&[]), Some((0, 1)));
+    assert_eq!(
+        chinese_remainder(&[(0, 17), (11, 13), (16, 19)]),
+        Some((3417, 4199))
+    );
+}
+
+#[test]
+fn affine_maps() {
+    let m = 10;
+    // deal into new stack, cut 3 and deal with increment 7 on a deck of 10
+    let shuffle = Affine::new(-1, -1, m)
+        .then(&Affine::new(1, -3, m))
+        .then(&Affine::new(7, 0, m));
+
+    assert_eq!(shuffle.apply(0), 2);
+    assert_eq!(shuffle.inverse().unwrap().apply(2), 0);
+
+    let thrice = shuffle.then(&shuffle).then(&shuffle);
+    assert_eq!(shuffle.pow(3), thrice);
+    assert_eq!(shuffle.pow(0), Affine::identity(m));
+
+    for x in 0..m {
+        assert_eq!(shuffle.pow(3).inverse().unwrap().apply(thrice.apply(x)), x);
+    }
+
+    assert!(Affine::new(2, 1, m).inverse().is_err());
+}
@@ -0,0 +1,383 @@
+//! Parsing of the puzzle inputs line by line, with errors telling where the input went wrong.
+//!
+//! A [`Cursor`] walks over a single line consuming literals, words and numbers, and every failure
+//! is a [`ParseError`] with the line and column, the offending line and what was expected there:
+//!
+//! ```text
+//! line 3, column 5: expected a number
+//!   acc +x
+//!       ^
+//! ```
+
+use crate::grid::{Grid, GridError};
+use crate::io::BorrowedRecords;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line number starting from one.
+    pub line: usize,
+    /// Column in characters starting from one.
+    pub column: usize,
+    /// The line with the error, without the line terminator.
+    pub snippet: String,
+    /// What was expected at the column, like "a number" or "`=>`".
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, snippet: &str, expected: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            snippet: snippet.to_owned(),
+            expected: expected.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            fmt,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        writeln!(fmt, "  {}", self.snippet)?;
+        write!(fmt, "  {:>width$}", "^", width = self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Position within a single line of input.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    line: &'a str,
+    number: usize,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// Cursor at the start of `line`, which is line `number` counting from one.
+    pub fn new(line: &'a str, number: usize) -> Self {
+        Cursor {
+            line,
+            number,
+            offset: 0,
+        }
+    }
+
+    /// The line number starting from one.
+    pub fn line_number(&self) -> usize {
+        self.number
+    }
+
+    /// The part of the line not yet consumed.
+    pub fn rest(&self) -> &'a str {
+        &self.line[self.offset..]
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.offset == self.line.len()
+    }
+
+    /// Error at the current position.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        self.error_at(self.offset, expected)
+    }
+
+    fn error_at(&self, offset: usize, expected: impl Into<String>) -> ParseError {
+        let column = self.line[..offset].chars().count() + 1;
+        ParseError::new(self.number, column, self.line, expected)
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Consumes `literal` if the rest of the line starts with it.
+    pub fn eat(&mut self, literal: &str) -> bool {
+        if self.rest().starts_with(literal) {
+            self.offset += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes `literal` or fails expecting it.
+    pub fn literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.eat(literal) {
+            Ok(())
+        } else {
+            Err(self.error(format!("`{}`", literal.trim())))
+        }
+    }
+
+    /// Consumes the longest prefix of characters for which `f` holds.
+    pub fn take_while<F: FnMut(char) -> bool>(&mut self, mut f: F) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.offset += len;
+        &rest[..len]
+    }
+
+    /// Consumes characters up to but not including `delimiter`, which has to be found.
+    pub fn until(&mut self, delimiter: &str) -> Result<&'a str, ParseError> {
+        let rest = self.rest();
+        match rest.find(delimiter) {
+            Some(len) => {
+                self.offset += len;
+                Ok(&rest[..len])
+            }
+            None => Err(self.error_at(self.line.len(), format!("`{}`", delimiter.trim()))),
+        }
+    }
+
+    /// Consumes a non-empty run of characters other than whitespace, failing with `expected`.
+    pub fn word(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        let word = self.take_while(|c| !c.is_whitespace());
+        if word.is_empty() {
+            Err(self.error(expected))
+        } else {
+            Ok(word)
+        }
+    }
+
+    /// Consumes a decimal number with an optional sign.
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let start = self.offset;
+        let rest = self.rest();
+        let sign = if rest.starts_with('+') || rest.starts_with('-') {
+            1
+        } else {
+            0
+        };
+        let digits = rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - sign);
+
+        if digits == 0 {
+            return Err(self.error("a number"));
+        }
+
+        let s = &rest[..sign + digits];
+        let value = s.parse::<T>().map_err(|_| {
+            self.error_at(
+                start,
+                format!("a number fitting {}", std::any::type_name::<T>()),
+            )
+        })?;
+        self.offset += s.len();
+        Ok(value)
+    }
+
+    /// Consumes the first of the `choices` the line continues with, returning its value.
+    pub fn one_of<T: Clone>(&mut self, choices: &[(&str, T)]) -> Result<T, ParseError> {
+        for (literal, value) in choices {
+            if self.eat(literal) {
+                return Ok(value.clone());
+            }
+        }
+
+        let names = choices
+            .iter()
+            .map(|(literal, _)| format!("`{}`", literal))
+            .collect::<Vec<_>>();
+
+        let expected = match names.split_last() {
+            Some((last, init)) if !init.is_empty() => format!("{} or {}", init.join(", "), last),
+            Some((last, _)) => last.clone(),
+            None => String::from("nothing"),
+        };
+
+        Err(self.error(format!("one of {}", expected)))
+    }
+
+    /// Fails unless the rest of the line is only whitespace.
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.is_at_end() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+/// Parses every non-empty line of `input` with `f`, which has to consume the whole line.
+pub fn lines<'a, T, F>(input: &'a str, mut f: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&mut Cursor<'a>) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let mut cursor = Cursor::new(line, i + 1);
+            let value = f(&mut cursor)?;
+            cursor.end()?;
+            Ok(value)
+        })
+        .collect()
+}
+
+/// Parses `s` as a single line with `f`, which has to consume all of it. Meant for `FromStr`
+/// implementations.
+pub fn line<'a, T, F>(s: &'a str, f: F) -> Result<T, ParseError>
+where
+    F: FnOnce(&mut Cursor<'a>) -> Result<T, ParseError>,
+{
+    let mut cursor = Cursor::new(s.trim_end_matches(&['\r', '\n'][..]), 1);
+    let value = f(&mut cursor)?;
+    cursor.end()?;
+    Ok(value)
+}
+
+/// Parses the groups of lines separated by blank lines with `f`, which gets a cursor for every
+/// line of the record. The records are split as by [`BorrowedRecords`].
+pub fn records<'a, T, F>(input: &'a str, mut f: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&mut [Cursor<'a>]) -> Result<T, ParseError>,
+{
+    BorrowedRecords::new(input)
+        .numbered()
+        .map(|(first, record)| {
+            let mut lines = record
+                .lines()
+                .enumerate()
+                .map(|(i, line)| Cursor::new(line, first + i))
+                .collect::<Vec<_>>();
+            f(&mut lines)
+        })
+        .collect()
+}
+
+/// Parses a grid of characters converting each with `f`, failing with `expected` on the ones it
+/// does not know.
+pub fn grid<T, F>(input: &str, expected: &str, mut f: F) -> Result<Grid<T>, ParseError>
+where
+    F: FnMut(u8) -> Option<T>,
+{
+    Grid::parse(input, |b| f(b).ok_or(())).map_err(|e| {
+        // lines are counted from zero and trimmed by the grid
+        let snippet = |line: usize| input.lines().nth(line).unwrap_or_default().trim();
+        match e {
+            GridError::Io(e) => unreachable!("reading from a str failed: {}", e),
+            GridError::UnevenLine {
+                line,
+                expected,
+                found,
+            } => ParseError::new(
+                line + 1,
+                expected.min(found) + 1,
+                snippet(line),
+                format!("a line of {} cells", expected),
+            ),
+            GridError::Cell { line, column, .. } => {
+                ParseError::new(line + 1, column + 1, snippet(line), expected)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+fn parse_jump(c: &mut Cursor) -> Result<(bool, i64), ParseError> {
+    let relative = c.one_of(&[("jmp ", true), ("jmpa ", false)])?;
+    Ok((relative, c.number()?))
+}
+
+#[test]
+fn lines_skip_blank_and_require_the_whole_line() {
+    let parsed = lines("jmp +4\r\n\njmpa -3\n", parse_jump).unwrap();
+    assert_eq!(parsed, vec![(true, 4), (false, -3)]);
+
+    let e = lines("jmp +4\njmp 3 4\n", parse_jump).unwrap_err();
+    assert_eq!((e.line, e.column), (2, 7));
+    assert_eq!(e.expected, "end of line");
+}
+
+#[test]
+fn errors_point_at_the_column() {
+    let e = lines("jmp +4\n\njmp +x\n", parse_jump).unwrap_err();
+    assert_eq!((e.line, e.column), (3, 5));
+    assert_eq!(e.snippet, "jmp +x");
+    assert_eq!(
+        e.to_string(),
+        "line 3, column 5: expected a number\n  jmp +x\n      ^"
+    );
+
+    let e = line("nop +0", parse_jump).unwrap_err();
+    assert_eq!(e.column, 1);
+    assert_eq!(e.expected, "one of `jmp ` or `jmpa `");
+}
+
+#[test]
+fn numbers_out_of_range() {
+    let e = line("mem[300] = 1", |c| {
+        c.literal("mem[")?;
+        c.number::<u8>()
+    })
+    .unwrap_err();
+    assert_eq!(e.column, 5);
+    assert_eq!(e.expected, "a number fitting u8");
+}
+
+#[test]
+fn words_and_delimiters() {
+    let parsed = line("7 A, 1 B => 1 C", |c| {
+        let lhs = c.until(" => ")?;
+        c.literal(" => ")?;
+        c.number::<u32>()?;
+        c.skip_whitespace();
+        Ok((lhs, c.word("a chemical")?))
+    })
+    .unwrap();
+    assert_eq!(parsed, ("7 A, 1 B", "C"));
+
+    let e = line("7 A, 1 B", |c| c.until(" => ")).unwrap_err();
+    assert_eq!((e.column, e.expected.as_str()), (9, "`=>`"));
+}
+
+#[test]
+fn records_are_separated_by_blank_lines() {
+    let parsed = records("1\n2\n\n\n3\r\n", |lines| {
+        lines
+            .iter_mut()
+            .map(|c| c.number::<u32>())
+            .sum::<Result<u32, _>>()
+    })
+    .unwrap();
+    assert_eq!(parsed, vec![3, 3]);
+
+    let e = records("1\n2\n\n \n3\r\nx\n", |lines| {
+        lines
+            .iter_mut()
+            .map(|c| c.number::<u32>())
+            .sum::<Result<u32, _>>()
+    })
+    .unwrap_err();
+    assert_eq!((e.line, e.snippet.as_str()), (6, "x"));
+}
+
+#[test]
+fn grid_errors() {
+    let convert = |b| match b {
+        b'#' => Some(true),
+        b'.' => Some(false),
+        _ => None,
+    };
+
+    assert_eq!(grid("#.\n.#\n", "`#` or `.`", convert).unwrap().width(), 2);
+
+    let e = grid("#.\n.x\n", "`#` or `.`", convert).unwrap_err();
+    assert_eq!((e.line, e.column), (2, 2));
+    assert_eq!(e.snippet, ".x");
+
+    let e = grid("#.\n.\n", "`#` or `.`", convert).unwrap_err();
+    assert_eq!((e.line, e.column), (2, 2));
+    assert_eq!(e.expected, "a line of 2 cells");
+}
@@ -0,0 +1,668 @@
+//! Searches over graphs of states given as a function listing the successors of a state.
+//!
+//! A [`Search`] runs breadth first, Dijkstra or A* from a start state until a goal state is taken
+//! from the frontier, returning the path to it. How the reached states are remembered is chosen
+//! with a [`Visited`] strategy, and states can be pruned when an already expanded state
+//! [dominates](Search::dominance) them. [`bidirectional_bfs`] searches from both ends at once.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Counters of the latest search.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// States taken from the frontier to have their successors listed.
+    pub expanded: usize,
+    /// Successors added to the frontier.
+    pub generated: usize,
+    /// States dropped as dominated by another.
+    pub pruned: usize,
+    /// Largest size of the frontier.
+    pub max_frontier: usize,
+}
+
+/// Path from the start to a goal, including both.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<S, C> {
+    pub path: Vec<S>,
+    pub cost: C,
+}
+
+impl<S, C> Found<S, C> {
+    pub fn goal(&self) -> &S {
+        self.path
+            .last()
+            .expect("the path includes at least the start")
+    }
+}
+
+/// How the states reached during a search are remembered.
+pub trait Visited<S, C> {
+    /// Records reaching `state` with `cost`, returning false if it had already been reached at
+    /// most as cheaply.
+    fn reach(&mut self, state: &S, cost: C) -> bool;
+
+    /// True if `state` taken from the frontier with `cost` has since been reached more cheaply.
+    fn is_stale(&self, state: &S, cost: C) -> bool;
+
+    fn clear(&mut self);
+}
+
+/// Remembers the cheapest cost of every state, which is the default.
+impl<S: Hash + Eq + Clone, C: Ord + Copy> Visited<S, C> for HashMap<S, C> {
+    fn reach(&mut self, state: &S, cost: C) -> bool {
+        match self.get_mut(state) {
+            Some(best) if *best <= cost => false,
+            Some(best) => {
+                *best = cost;
+                true
+            }
+            None => {
+                self.insert(state.clone(), cost);
+                true
+            }
+        }
+    }
+
+    fn is_stale(&self, state: &S, cost: C) -> bool {
+        self.get(state).map(|&best| best < cost).unwrap_or(false)
+    }
+
+    fn clear(&mut self) {
+        HashMap::clear(self)
+    }
+}
+
+/// Remembers nothing, for the state spaces which are trees, like when the states carry their
+/// history. The states do not need to be hashable.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Tree;
+
+impl<S, C> Visited<S, C> for Tree {
+    fn reach(&mut self, _: &S, _: C) -> bool {
+        true
+    }
+
+    fn is_stale(&self, _: &S, _: C) -> bool {
+        false
+    }
+
+    fn clear(&mut self) {}
+}
+
+/// Remembers the cheapest cost by a key of the states, treating the states with equal keys as the
+/// same.
+pub struct ByKey<F, K, C> {
+    key: F,
+    best: HashMap<K, C>,
+}
+
+impl<S, C, F, K> Visited<S, C> for ByKey<F, K, C>
+where
+    F: Fn(&S) -> K,
+    K: Hash + Eq + Clone,
+    C: Ord + Copy,
+{
+    fn reach(&mut self, state: &S, cost: C) -> bool {
+        self.best.reach(&(self.key)(state), cost)
+    }
+
+    fn is_stale(&self, state: &S, cost: C) -> bool {
+        self.best.is_stale(&(self.key)(state), cost)
+    }
+
+    fn clear(&mut self) {
+        self.best.clear()
+    }
+}
+
+/// Prunes the states which an already expanded state makes unnecessary.
+pub trait Dominance<S, C> {
+    /// True if `state` reached with `cost` is dominated by an expanded state, otherwise records it
+    /// as expanded.
+    fn is_dominated(&mut self, state: &S, cost: C) -> bool;
+
+    fn clear(&mut self);
+}
+
+/// Prunes nothing, which is the default.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoDominance;
+
+impl<S, C> Dominance<S, C> for NoDominance {
+    fn is_dominated(&mut self, _: &S, _: C) -> bool {
+        false
+    }
+
+    fn clear(&mut self) {}
+}
+
+/// Dominance between the states with equal keys, see [`Search::dominance`].
+pub struct Dominated<S, C, F, K, D> {
+    key: F,
+    dominates: D,
+    expanded: HashMap<K, Vec<(S, C)>>,
+}
+
+impl<S, C, F, K, D> Dominance<S, C> for Dominated<S, C, F, K, D>
+where
+    S: Clone,
+    C: Ord + Copy,
+    F: Fn(&S) -> K,
+    K: Hash + Eq,
+    D: Fn(&S, &S) -> bool,
+{
+    fn is_dominated(&mut self, state: &S, cost: C) -> bool {
+        let Dominated {
+            key,
+            dominates,
+            expanded,
+        } = self;
+
+        let bucket = expanded.entry(key(state)).or_default();
+
+        if bucket
+            .iter()
+            .any(|(other, c)| *c <= cost && dominates(other, state))
+        {
+            return true;
+        }
+
+        bucket.retain(|(other, c)| !(cost <= *c && dominates(state, other)));
+        bucket.push((state.clone(), cost));
+        false
+    }
+
+    fn clear(&mut self) {
+        self.expanded.clear()
+    }
+}
+
+struct Node<S, C> {
+    state: S,
+    parent: Option<usize>,
+    cost: C,
+}
+
+/// Search configured with the successor function, the [`Visited`] strategy and the
+/// [`Dominance`] pruning. The same search can be run many times; every run starts from scratch.
+///
+/// ```
+/// use aoc_common::search::Search;
+///
+/// // from 1 to 10 by doubling or adding one
+/// let mut search = Search::new(|&n: &u32| vec![n + 1, n * 2]);
+/// let found = search.bfs(1, |&n| n == 10).unwrap();
+/// assert_eq!(found.path, vec![1, 2, 4, 5, 10]);
+/// assert_eq!(found.cost, 4);
+/// ```
+pub struct Search<F, V, D> {
+    successors: F,
+    visited: V,
+    dominance: D,
+    stats: Stats,
+}
+
+impl<F, S, C> Search<F, HashMap<S, C>, NoDominance> {
+    /// Search remembering the cheapest cost of every reached state. The successors are listed as
+    /// states for [`Search::bfs`] and as pairs of states and step costs for the others.
+    pub fn new(successors: F) -> Self {
+        Search {
+            successors,
+            visited: HashMap::new(),
+            dominance: NoDominance,
+            stats: Stats::default(),
+        }
+    }
+}
+
+impl<F> Search<F, Tree, NoDominance> {
+    /// Search remembering no states, see [`Tree`].
+    pub fn tree(successors: F) -> Self {
+        Search {
+            successors,
+            visited: Tree,
+            dominance: NoDominance,
+            stats: Stats::default(),
+        }
+    }
+}
+
+impl<F, KF, K, C> Search<F, ByKey<KF, K, C>, NoDominance> {
+    /// Search remembering the states by the `key` of them, see [`ByKey`].
+    pub fn visited_by(successors: F, key: KF) -> Self {
+        Search {
+            successors,
+            visited: ByKey {
+                key,
+                best: HashMap::new(),
+            },
+            dominance: NoDominance,
+            stats: Stats::default(),
+        }
+    }
+}
+
+impl<F, V, D> Search<F, V, D> {
+    /// Prunes the states for which `dominates(expanded, state)` holds for a state with the same
+    /// `key` expanded at most as cheaply. For example a state holding more keys at the same
+    /// position is at least as good as one holding fewer of them.
+    pub fn dominance<S, C, K, KF, DF>(
+        self,
+        key: KF,
+        dominates: DF,
+    ) -> Search<F, V, Dominated<S, C, KF, K, DF>>
+    where
+        KF: Fn(&S) -> K,
+        DF: Fn(&S, &S) -> bool,
+    {
+        Search {
+            successors: self.successors,
+            visited: self.visited,
+            dominance: Dominated {
+                key,
+                dominates,
+                expanded: HashMap::new(),
+            },
+            stats: self.stats,
+        }
+    }
+
+    /// Counters of the latest run.
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    fn reset<S, C>(&mut self)
+    where
+        V: Visited<S, C>,
+        D: Dominance<S, C>,
+    {
+        self.visited.clear();
+        self.dominance.clear();
+        self.stats = Stats::default();
+    }
+
+    /// Breadth first search, where every step costs one.
+    pub fn bfs<S, I, G>(&mut self, start: S, mut goal: G) -> Option<Found<S, usize>>
+    where
+        S: Clone,
+        F: FnMut(&S) -> I,
+        I: IntoIterator<Item = S>,
+        V: Visited<S, usize>,
+        D: Dominance<S, usize>,
+        G: FnMut(&S) -> bool,
+    {
+        self.reset();
+
+        let mut nodes = Vec::new();
+        let mut frontier = VecDeque::new();
+
+        self.visited.reach(&start, 0);
+        nodes.push(Node {
+            state: start,
+            parent: None,
+            cost: 0,
+        });
+        frontier.push_back(0);
+
+        while let Some(index) = frontier.pop_front() {
+            let node = &nodes[index];
+            let cost = node.cost;
+
+            if goal(&node.state) {
+                return Some(reconstruct(&nodes, index));
+            }
+
+            if self.dominance.is_dominated(&node.state, cost) {
+                self.stats.pruned += 1;
+                continue;
+            }
+
+            self.stats.expanded += 1;
+
+            for next in (self.successors)(&nodes[index].state) {
+                if self.visited.reach(&next, cost + 1) {
+                    frontier.push_back(nodes.len());
+                    nodes.push(Node {
+                        state: next,
+                        parent: Some(index),
+                        cost: cost + 1,
+                    });
+                    self.stats.generated += 1;
+                }
+            }
+
+            self.stats.max_frontier = self.stats.max_frontier.max(frontier.len());
+        }
+
+        None
+    }
+
+    /// Dijkstra's algorithm over non-negative step costs.
+    pub fn dijkstra<S, C, I, G>(&mut self, start: S, goal: G) -> Option<Found<S, C>>
+    where
+        S: Clone,
+        C: Ord + Copy + Default + Add<Output = C>,
+        F: FnMut(&S) -> I,
+        I: IntoIterator<Item = (S, C)>,
+        V: Visited<S, C>,
+        D: Dominance<S, C>,
+        G: FnMut(&S) -> bool,
+    {
+        self.astar(start, |_| C::default(), goal)
+    }
+
+    /// A* search, where `heuristic` must not overestimate the cost from a state to the nearest
+    /// goal for the found path to be the cheapest.
+    pub fn astar<S, C, I, H, G>(
+        &mut self,
+        start: S,
+        mut heuristic: H,
+        mut goal: G,
+    ) -> Option<Found<S, C>>
+    where
+        S: Clone,
+        C: Ord + Copy + Default + Add<Output = C>,
+        F: FnMut(&S) -> I,
+        I: IntoIterator<Item = (S, C)>,
+        V: Visited<S, C>,
+        D: Dominance<S, C>,
+        H: FnMut(&S) -> C,
+        G: FnMut(&S) -> bool,
+    {
+        self.reset();
+
+        let mut nodes = Vec::new();
+        // ties are broken by the order of generation through the node index
+        let mut frontier = BinaryHeap::new();
+
+        self.visited.reach(&start, C::default());
+        frontier.push(Reverse((heuristic(&start), 0)));
+        nodes.push(Node {
+            state: start,
+            parent: None,
+            cost: C::default(),
+        });
+
+        while let Some(Reverse((_, index))) = frontier.pop() {
+            let node = &nodes[index];
+            let cost = node.cost;
+
+            if self.visited.is_stale(&node.state, cost) {
+                continue;
+            }
+
+            if goal(&node.state) {
+                return Some(reconstruct(&nodes, index));
+            }
+
+            if self.dominance.is_dominated(&node.state, cost) {
+                self.stats.pruned += 1;
+                continue;
+            }
+
+            self.stats.expanded += 1;
+
+            for (next, step) in (self.successors)(&nodes[index].state) {
+                let cost = cost + step;
+                if self.visited.reach(&next, cost) {
+                    frontier.push(Reverse((cost + heuristic(&next), nodes.len())));
+                    nodes.push(Node {
+                        state: next,
+                        parent: Some(index),
+                        cost,
+                    });
+                    self.stats.generated += 1;
+                }
+            }
+
+            self.stats.max_frontier = self.stats.max_frontier.max(frontier.len());
+        }
+
+        None
+    }
+}
+
+fn reconstruct<S: Clone, C: Copy>(nodes: &[Node<S, C>], goal: usize) -> Found<S, C> {
+    let path = std::iter::successors(Some(goal), |&i| nodes[i].parent)
+        .map(|i| nodes[i].state.clone())
+        .collect::<Vec<_>>();
+
+    Found {
+        path: path.into_iter().rev().collect(),
+        cost: nodes[goal].cost,
+    }
+}
+
+/// Breadth first search from `start` and `goal` towards each other, expanding the smaller of the
+/// frontiers a layer at a time. `backwards` lists the states from which a state is reached in a
+/// step; for undirected graphs it lists the same states as `forwards`.
+pub fn bidirectional_bfs<S, F, B, I, J>(
+    start: S,
+    goal: S,
+    mut forwards: F,
+    mut backwards: B,
+) -> Option<Found<S, usize>>
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    B: FnMut(&S) -> J,
+    J: IntoIterator<Item = S>,
+{
+    if start == goal {
+        return Some(Found {
+            path: vec![start],
+            cost: 0,
+        });
+    }
+
+    // the depth and the state towards the start of the side
+    let mut from_start = HashMap::new();
+    let mut from_goal = HashMap::new();
+    from_start.insert(start.clone(), (0, None));
+    from_goal.insert(goal.clone(), (0, None));
+
+    let mut start_layer = vec![start];
+    let mut goal_layer = vec![goal];
+
+    while !start_layer.is_empty() && !goal_layer.is_empty() {
+        let forward = start_layer.len() <= goal_layer.len();
+        let (layer, seen, other) = if forward {
+            (&mut start_layer, &mut from_start, &from_goal)
+        } else {
+            (&mut goal_layer, &mut from_goal, &from_start)
+        };
+
+        // the whole layer is expanded as a later meeting in it can still be closer
+        let mut meeting: Option<(usize, S)> = None;
+        let mut next_layer = Vec::new();
+
+        for state in layer.drain(..) {
+            let depth = seen[&state].0 + 1;
+            let successors = if forward {
+                either::Either::Left(forwards(&state).into_iter())
+            } else {
+                either::Either::Right(backwards(&state).into_iter())
+            };
+
+            for next in successors {
+                if seen.contains_key(&next) {
+                    continue;
+                }
+
+                if let Some(&(rest, _)) = other.get(&next) {
+                    if meeting
+                        .as_ref()
+                        .map(|(best, _)| depth + rest < *best)
+                        .unwrap_or(true)
+                    {
+                        meeting = Some((depth + rest, next.clone()));
+                    }
+                }
+
+                seen.insert(next.clone(), (depth, Some(state.clone())));
+                next_layer.push(next);
+            }
+        }
+
+        *layer = next_layer;
+
+        if let Some((cost, middle)) = meeting {
+            let walk = |side: &HashMap<S, (usize, Option<S>)>| {
+                std::iter::successors(Some(middle.clone()), |s| side[s].1.clone())
+                    .collect::<Vec<_>>()
+            };
+
+            let mut path = walk(&from_start);
+            path.reverse();
+            path.extend(walk(&from_goal).into_iter().skip(1));
+
+            return Some(Found { path, cost });
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+fn maze_successors(maze: &'static str) -> impl Fn(&(usize, usize)) -> Vec<(usize, usize)> {
+    let rows = maze.lines().map(str::as_bytes).collect::<Vec<_>>();
+    move |&(x, y)| {
+        let mut next = Vec::new();
+        let mut push = |x: usize, y: usize| {
+            if rows.get(y).and_then(|row| row.get(x)) == Some(&b'.') {
+                next.push((x, y));
+            }
+        };
+        push(x + 1, y);
+        push(x, y + 1);
+        if x > 0 {
+            push(x - 1, y);
+        }
+        if y > 0 {
+            push(x, y - 1);
+        }
+        next
+    }
+}
+
+#[cfg(test)]
+const MAZE: &str = "\
+.....#...
+.###.#.#.
+...#...#.
+##.#####.
+.........";
+
+#[test]
+fn bfs_and_astar_in_a_maze() {
+    let successors = maze_successors(MAZE);
+
+    let mut search = Search::new(&successors);
+    let found = search.bfs((0, 0), |&p| p == (6, 0)).unwrap();
+    assert_eq!(found.cost, 10);
+    assert_eq!(found.path.len(), 11);
+    assert_eq!(found.goal(), &(6, 0));
+    assert!(search.stats().expanded > 0);
+
+    let unit = |p: &(usize, usize)| successors(p).into_iter().map(|n| (n, 1usize));
+    let manhattan = |&(x, y): &(usize, usize)| (6 - x.min(6)) + (x.max(6) - 6) + y;
+
+    let mut search = Search::new(unit);
+    let found = search.astar((0, 0), manhattan, |&p| p == (6, 0)).unwrap();
+    assert_eq!(found.cost, 10);
+    let astar = search.stats();
+
+    let found = search.dijkstra((0, 0), |&p| p == (6, 0)).unwrap();
+    assert_eq!(found.cost, 10);
+    assert!(astar.expanded <= search.stats().expanded);
+
+    assert_eq!(
+        search.dijkstra((0, 0), |&p| p == (9, 9)).map(|f| f.cost),
+        None
+    );
+}
+
+#[test]
+fn dijkstra_prefers_cheaper_longer_paths() {
+    let edges = |&n: &u8| match n {
+        0 => vec![(1, 10), (2, 1)],
+        2 => vec![(3, 1)],
+        3 => vec![(1, 1)],
+        _ => vec![],
+    };
+
+    let mut search = Search::new(edges);
+    let found = search.dijkstra(0, |&n| n == 1).unwrap();
+    assert_eq!(found.path, vec![0, 2, 3, 1]);
+    assert_eq!(found.cost, 3);
+}
+
+#[test]
+fn tree_and_keyed_visiting() {
+    // states carrying their history are all different, so only the key can merge them
+    let successors = |path: &Vec<u8>| {
+        let last = *path.last().unwrap();
+        [1, 2]
+            .iter()
+            .map(|d| {
+                let mut next = path.clone();
+                next.push(last + d);
+                (next, 1u32)
+            })
+            .collect::<Vec<_>>()
+    };
+    let goal = |path: &Vec<u8>| *path.last().unwrap() == 6;
+
+    let mut tree = Search::tree(successors);
+    assert_eq!(tree.dijkstra(vec![0], goal).unwrap().cost, 3);
+    let tree_stats = tree.stats();
+
+    let mut keyed = Search::visited_by(successors, |path: &Vec<u8>| *path.last().unwrap());
+    assert_eq!(keyed.dijkstra(vec![0], goal).unwrap().cost, 3);
+    assert!(keyed.stats().generated < tree_stats.generated);
+}
+
+#[test]
+fn dominated_states_are_pruned() {
+    // walking right either empty handed or picking up a stone; carrying fewer stones to the same
+    // position is at least as good, and the stones do not matter for the goal
+    let successors =
+        |&(x, stones): &(u8, u8)| vec![((x + 1, stones), 2u32), ((x + 1, stones + 1), 2)];
+    let goal = |&(x, _): &(u8, u8)| x == 8;
+
+    let mut plain = Search::new(successors);
+    assert_eq!(plain.dijkstra((0, 0), goal).unwrap().cost, 16);
+
+    let mut pruning = Search::new(successors).dominance(|&(x, _): &(u8, u8)| x, |a, b| a.1 <= b.1);
+    assert_eq!(pruning.dijkstra((0, 0), goal).unwrap().cost, 16);
+    assert!(pruning.stats().pruned > 0);
+    assert!(pruning.stats().expanded < plain.stats().expanded);
+}
+
+#[test]
+fn bidirectional_meets_in_the_middle() {
+    let successors = maze_successors(MAZE);
+
+    let found = bidirectional_bfs((0, 0), (6, 0), &successors, &successors).unwrap();
+    assert_eq!(found.cost, 10);
+    assert_eq!(found.path.first(), Some(&(0, 0)));
+    assert_eq!(found.goal(), &(6, 0));
+    assert!(found
+        .path
+        .windows(2)
+        .all(|w| successors(&w[0]).contains(&w[1])));
+
+    assert_eq!(
+        bidirectional_bfs((0, 0), (9, 9), &successors, &successors),
+        None
+    );
+
+    // directed: only forwards by one
+    let found = bidirectional_bfs(0u8, 5, |&n| vec![n + 1], |&n| vec![n - 1]).unwrap();
+    assert_eq!(found.path, vec![0, 1, 2, 3, 4, 5]);
+}
@@ -0,0 +1,149 @@
+//! The common shape of the solutions of all days.
+//!
+//! Each day implements [`Solution`] on a unit struct in its library target, and the binary of the
+//! day is just `solution::main::<DayNN>()`. [`solve`] runs a solution on any input in-process,
+//! which is what the tests on the examples use.
+
+use std::fmt;
+use std::io::Read;
+
+pub type Error = Box<dyn std::error::Error + 'static>;
+
+pub trait Solution {
+    /// The parsed input shared by both parts.
+    type Input;
+    type PartOne: fmt::Display;
+    type PartTwo: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne, Error>;
+
+    /// Days which have only one part, or where the second part has not been solved, return
+    /// [`Unsolved`].
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Error>;
+}
+
+/// The error of a part which has no solution; not reported as a failure by [`main`] or [`solve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsolved;
+
+impl fmt::Display for Unsolved {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "not solved")
+    }
+}
+
+impl std::error::Error for Unsolved {}
+
+/// The answers of both parts, `None` for the [`Unsolved`] parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(answer) = &self.part_one {
+            writeln!(fmt, "part1: {}", answer)?;
+        }
+        if let Some(answer) = &self.part_two {
+            writeln!(fmt, "part2: {}", answer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the input and solves both parts.
+pub fn solve<S: Solution>(input: &str) -> Result<Answers, Error> {
+    let input = S::parse(input)?;
+
+    Ok(Answers {
+        part_one: answer(S::part_one(&input))?,
+        part_two: answer(S::part_two(&input))?,
+    })
+}
+
+fn answer<T: fmt::Display>(result: Result<T, Error>) -> Result<Option<String>, Error> {
+    match result {
+        Ok(answer) => Ok(Some(answer.to_string())),
+        Err(e) if e.is::<Unsolved>() => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Solves the input read from stdin, printing the answers as `part1: answer` and `part2: answer`
+/// as soon as they are ready. Exits with non-zero status if reading, parsing or either part
+/// fails.
+pub fn main<S: Solution>() {
+    if let Err(e) = run::<S>() {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run<S: Solution>() -> Result<(), Error> {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+
+    let input = S::parse(&input)?;
+
+    if let Some(answer) = answer(S::part_one(&input))? {
+        println!("part1: {}", answer);
+    }
+
+    if let Some(answer) = answer(S::part_two(&input))? {
+        println!("part2: {}", answer);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+struct Sums;
+
+#[cfg(test)]
+impl Solution for Sums {
+    type Input = Vec<i64>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input
+            .lines()
+            .map(str::parse)
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<i64, Error> {
+        Ok(input.iter().sum())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<i64, Error> {
+        match input.iter().filter(|&&x| x < 0).sum() {
+            0 => Err(Unsolved.into()),
+            sum => Ok(sum),
+        }
+    }
+}
+
+#[test]
+fn solve_both_parts() {
+    let answers = solve::<Sums>("1\n-2\n3\n").unwrap();
+    assert_eq!(answers.part_one.as_deref(), Some("2"));
+    assert_eq!(answers.part_two.as_deref(), Some("-2"));
+    assert_eq!(answers.to_string(), "part1: 2\npart2: -2\n");
+}
+
+#[test]
+fn unsolved_part_is_skipped() {
+    let answers = solve::<Sums>("1\n2\n").unwrap();
+    assert_eq!(answers.part_two, None);
+    assert_eq!(answers.to_string(), "part1: 3\n");
+}
+
+#[test]
+fn parse_errors_are_returned() {
+    assert!(solve::<Sums>("1\nfoo\n").is_err());
+}
@@ -0,0 +1,254 @@
+//! Known answers of a year, read from the `answers` file next to the year's inputs.
+//!
+//! Each non-empty line which isn't a `#` comment is `day part verdict answer`, for example:
+//!
+//! ```text
+//! 14 1 ok 5875750429995
+//! 14 2 low 1297026531039
+//! ```
+//!
+//! where the verdict is `ok` for the accepted answer, `high` or `low` for submissions which were
+//! too high or too low, and `wrong` for submissions without a hint. The answer is the rest of the
+//! line, so it can contain spaces.
+
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl Verdict {
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "ok" => Verdict::Correct,
+            "high" => Verdict::TooHigh,
+            "low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wrong => "wrong",
+        };
+        write!(fmt, "{}", s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    day: u8,
+    part: u8,
+    verdict: Verdict,
+    answer: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Answers {
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check<'a> {
+    Correct,
+    /// The answer was not the expected one, or it is a known wrong one without an expected answer
+    /// being recorded.
+    Incorrect {
+        expected: Option<&'a str>,
+        known: Option<Verdict>,
+    },
+    /// Nothing is known about the answer.
+    Unknown,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Answers {
+    /// Reads the answers from `path`; a missing file is the same as an empty one.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s).map_err(|e| format!("{}: {}", path.display(), e).into()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let mut entries = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |reason| ParseError {
+                line: i + 1,
+                reason,
+            };
+
+            let mut fields = line.split_whitespace();
+
+            let day = fields
+                .next()
+                .and_then(|s| s.parse::<u8>().ok())
+                .filter(|d| (1..=25).contains(d))
+                .ok_or_else(|| error("invalid day"))?;
+
+            let part = fields
+                .next()
+                .and_then(|s| s.parse::<u8>().ok())
+                .filter(|&p| p == 1 || p == 2)
+                .ok_or_else(|| error("invalid part"))?;
+
+            let verdict = fields
+                .next()
+                .and_then(Verdict::parse)
+                .ok_or_else(|| error("invalid verdict, expected one of ok, high, low, wrong"))?;
+
+            // the answer is everything after the verdict, whitespace included
+            let answer = fields
+                .next()
+                .map(|first| &line[first.as_ptr() as usize - line.as_ptr() as usize..])
+                .ok_or_else(|| error("missing answer"))?;
+
+            let entry = Entry {
+                day,
+                part,
+                verdict,
+                answer: answer.to_owned(),
+            };
+
+            if verdict == Verdict::Correct
+                && entries
+                    .iter()
+                    .any(|e: &Entry| e.day == day && e.part == part && e.verdict == verdict)
+            {
+                return Err(error("duplicate correct answer"));
+            }
+
+            entries.push(entry);
+        }
+
+        Ok(Answers { entries })
+    }
+
+    /// The accepted answer, if one is known.
+    pub fn expected(&self, day: u8, part: u8) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.day == day && e.part == part && e.verdict == Verdict::Correct)
+            .map(|e| e.answer.as_str())
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Check<'_> {
+        let expected = self.expected(day, part);
+        let known = self
+            .entries
+            .iter()
+            .filter(|e| e.day == day && e.part == part && e.verdict != Verdict::Correct)
+            .find(|e| e.answer == answer)
+            .map(|e| e.verdict);
+
+        match expected {
+            Some(expected) if expected == answer => Check::Correct,
+            None if known.is_none() => Check::Unknown,
+            expected => Check::Incorrect { expected, known },
+        }
+    }
+}
+
+#[test]
+fn parse_and_check() {
+    let answers = Answers::parse(
+        "# comment\n\
+         \n\
+         14 1 ok 5875750429995\n\
+         14 1 wrong 11745848003726\n\
+         14 2 low 1297026531039\n\
+         14 2 ok 5272149590143\n\
+         08 2 ok LEPCPLGZ\n\
+         09 1 high 100\n",
+    )
+    .unwrap();
+
+    assert_eq!(answers.check(14, 1, "5875750429995"), Check::Correct);
+    assert_eq!(answers.check(8, 2, "LEPCPLGZ"), Check::Correct);
+    assert_eq!(
+        answers.check(14, 2, "1297026531039"),
+        Check::Incorrect {
+            expected: Some("5272149590143"),
+            known: Some(Verdict::TooLow)
+        }
+    );
+    assert_eq!(
+        answers.check(14, 1, "1"),
+        Check::Incorrect {
+            expected: Some("5875750429995"),
+            known: None
+        }
+    );
+    assert_eq!(
+        answers.check(9, 1, "100"),
+        Check::Incorrect {
+            expected: None,
+            known: Some(Verdict::TooHigh)
+        }
+    );
+    assert_eq!(answers.check(9, 1, "99"), Check::Unknown);
+    assert_eq!(answers.check(1, 1, "1"), Check::Unknown);
+}
+
+#[test]
+fn parse_mixed_whitespace() {
+    let answers = Answers::parse("13  1\tok   376\n13\t2 ok\t\t18509 \n").unwrap();
+
+    assert_eq!(answers.expected(13, 1), Some("376"));
+    assert_eq!(answers.expected(13, 2), Some("18509"));
+}
+
+#[test]
+fn parse_errors() {
+    let reason = |s| Answers::parse(s).unwrap_err();
+
+    assert_eq!(
+        reason("1 1 ok 1\n26 1 ok 2"),
+        ParseError {
+            line: 2,
+            reason: "invalid day"
+        }
+    );
+    assert_eq!(reason("1 3 ok 1").reason, "invalid part");
+    assert_eq!(
+        reason("1 1 maybe 1").reason,
+        "invalid verdict, expected one of ok, high, low, wrong"
+    );
+    assert_eq!(reason("1 1 ok").reason, "missing answer");
+    assert_eq!(
+        reason("1 1 ok 1\n1 1 ok 2").reason,
+        "duplicate correct answer"
+    );
+}
@@ -0,0 +1,538 @@
+//! Runs the solutions of every year: `aoc` runs everything, `aoc 2019` all days of a year and
+//! `aoc 2019 17` a single day.
+//!
+//! Each day is a binary named `aoc<year>-day<NN>` reading its input from stdin. A year is either a
+//! single crate with `src/bin/dayNN.rs` or a crate per day in `dayNN/`. Inputs are read from the
+//! input store, see `aoc_common::input`, using the set given with `--set` or the default one. The
+//! store directory can be given with `--inputs`.
+//!
+//! Lines printed as `part1: answer` or `part2: answer` (or `stage1:` and `stage2:`) are checked
+//! against the `answers` file of the year in the input store, see [`answers`].
+
+mod answers;
+
+use answers::{Answers, Check};
+use aoc_common::input::{CurlFetcher, InputError, InputStore, DEFAULT_SET};
+use std::collections::{hash_map::Entry, HashMap};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+fn main() {
+    let options = match Options::from_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("usage: aoc [--set name] [--inputs dir] [year [day]]");
+            std::process::exit(2);
+        }
+    };
+
+    let root = std::env::var_os("AOC_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join(".."));
+
+    let store = match options.inputs {
+        Some(ref dir) => InputStore::new(dir),
+        None => InputStore::from_env(),
+    };
+
+    let store = match CurlFetcher::from_env() {
+        Some(fetcher) => store.with_fetcher(fetcher),
+        None => store,
+    };
+
+    match run(&root, &store, &options) {
+        Ok(summary) if summary.failed == 0 => {}
+        Ok(_) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("aoc: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Selection {
+    All,
+    Year(u16),
+    Day(u16, u8),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Options {
+    selection: Selection,
+    set: String,
+    inputs: Option<PathBuf>,
+}
+
+impl Options {
+    fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Self, UsageError> {
+        let mut set = None;
+        let mut inputs = None;
+        let mut positional = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--set" => set = Some(args.next().ok_or(UsageError::MissingValue("--set"))?),
+                "--inputs" => {
+                    inputs = Some(PathBuf::from(
+                        args.next().ok_or(UsageError::MissingValue("--inputs"))?,
+                    ))
+                }
+                flag if flag.starts_with("--") => return Err(UsageError::UnknownFlag(arg)),
+                _ => positional.push(arg),
+            }
+        }
+
+        Ok(Options {
+            selection: Selection::from_args(positional)?,
+            set: set.unwrap_or_else(|| String::from(DEFAULT_SET)),
+            inputs,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum UsageError {
+    InvalidYear(String),
+    InvalidDay(String),
+    TooManyArguments,
+    MissingValue(&'static str),
+    UnknownFlag(String),
+}
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UsageError::InvalidYear(s) => write!(fmt, "invalid year: {:?}", s),
+            UsageError::InvalidDay(s) => write!(fmt, "invalid day: {:?}", s),
+            UsageError::TooManyArguments => write!(fmt, "too many arguments"),
+            UsageError::MissingValue(flag) => write!(fmt, "missing value for {}", flag),
+            UsageError::UnknownFlag(s) => write!(fmt, "unknown flag: {}", s),
+        }
+    }
+}
+
+impl Selection {
+    fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Self, UsageError> {
+        let mut args = args.into_iter();
+
+        let year = match args.next() {
+            Some(s) => s.parse::<u16>().map_err(|_| UsageError::InvalidYear(s))?,
+            None => return Ok(Selection::All),
+        };
+
+        let day = match args.next() {
+            Some(s) => match s.parse::<u8>() {
+                Ok(day @ 1..=25) => day,
+                _ => return Err(UsageError::InvalidDay(s)),
+            },
+            None => return Ok(Selection::Year(year)),
+        };
+
+        if args.next().is_some() {
+            return Err(UsageError::TooManyArguments);
+        }
+
+        Ok(Selection::Day(year, day))
+    }
+
+    fn matches(&self, day: &Day) -> bool {
+        match *self {
+            Selection::All => true,
+            Selection::Year(year) => day.year == year,
+            Selection::Day(year, d) => day.year == year && day.day == d,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// `src/bin/dayNN.rs` in the crate of the year
+    SingleCrate,
+    /// `dayNN/` crates
+    CratePerDay,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Day {
+    year: u16,
+    day: u8,
+    layout: Layout,
+}
+
+impl Day {
+    fn bin(&self) -> String {
+        format!("aoc{}-day{:02}", self.year, self.day)
+    }
+
+    /// The known answers of the year for the set, `answers` for the default set and
+    /// `answers.<set>` for the others.
+    fn answers(&self, store: &InputStore, set: &str) -> PathBuf {
+        let year = store.dir().join(self.year.to_string());
+        if set == DEFAULT_SET {
+            year.join("answers")
+        } else {
+            year.join(format!("answers.{}", set))
+        }
+    }
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{} day{:02}", self.year, self.day)
+    }
+}
+
+/// Finds all days of all years under `root`, in order.
+fn discover(root: &Path) -> io::Result<Vec<Day>> {
+    let mut days = Vec::new();
+
+    for entry in std::fs::read_dir(root)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let year = match name.to_str().filter(|s| s.len() == 4).map(str::parse) {
+            Some(Ok(year)) => year,
+            _ => continue,
+        };
+
+        if entry.file_type()?.is_dir() {
+            days.extend(discover_year(&entry.path(), year)?);
+        }
+    }
+
+    days.sort_by_key(|d| (d.year, d.day));
+    Ok(days)
+}
+
+fn discover_year(dir: &Path, year: u16) -> io::Result<Vec<Day>> {
+    let (layout, candidates) = if dir.join("Cargo.toml").is_file() {
+        (Layout::SingleCrate, dir.join("src").join("bin"))
+    } else {
+        (Layout::CratePerDay, dir.to_owned())
+    };
+
+    let mut days = Vec::new();
+
+    for entry in std::fs::read_dir(candidates)? {
+        let path = entry?.path();
+
+        let stem = match layout {
+            Layout::SingleCrate if path.extension().is_some_and(|e| e == "rs") => path.file_stem(),
+            Layout::CratePerDay if path.join("Cargo.toml").is_file() => path.file_name(),
+            _ => continue,
+        };
+
+        let day = stem
+            .and_then(|s| s.to_str())
+            .and_then(|s| s.strip_prefix("day"))
+            .filter(|s| s.len() == 2)
+            .and_then(|s| s.parse::<u8>().ok());
+
+        if let Some(day) = day {
+            days.push(Day { year, day, layout });
+        }
+    }
+
+    Ok(days)
+}
+
+#[derive(Debug, Default)]
+struct Summary {
+    ran: usize,
+    failed: usize,
+    elapsed: Duration,
+}
+
+fn run(
+    root: &Path,
+    store: &InputStore,
+    options: &Options,
+) -> Result<Summary, Box<dyn std::error::Error>> {
+    let mut summary = Summary::default();
+    let selection = &options.selection;
+
+    let mut selected = Vec::new();
+    for day in discover(root)?.into_iter().filter(|d| selection.matches(d)) {
+        match store.read(day.year, day.day, &options.set) {
+            Ok(input) => selected.push((day, input)),
+            Err(InputError::Missing { .. }) if *selection != Selection::Day(day.year, day.day) => {
+                // asking for a single day should fail loudly, when running many just skip
+            }
+            Err(e) => {
+                eprintln!("{}: {}", day, e);
+                summary.failed += 1;
+            }
+        }
+    }
+
+    if selected.is_empty() {
+        if summary.failed == 0 {
+            eprintln!("aoc: nothing to run for {:?}", selection);
+            summary.failed += 1;
+        }
+        return Ok(summary);
+    }
+
+    build(root, selected.iter().map(|(day, _)| day))?;
+
+    let target = std::env::var_os("CARGO_TARGET_DIR")
+        .map(|dir| root.join(dir))
+        .unwrap_or_else(|| root.join("target"))
+        .join("release");
+
+    let mut answers_by_year = HashMap::new();
+
+    for (day, input) in selected {
+        let day = &day;
+        let answers = match answers_by_year.entry(day.year) {
+            Entry::Occupied(o) => o.into_mut(),
+            Entry::Vacant(v) => v.insert(Answers::load(&day.answers(store, &options.set))?),
+        };
+
+        let mut seen = [false; 2];
+        let mut incorrect = 0;
+
+        let started = Instant::now();
+
+        let mut child = Command::new(target.join(day.bin()))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        // write from another thread so that a solution printing before reading all of the input
+        // cannot block us both
+        let mut stdin = child.stdin.take().expect("stdin was piped");
+        let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+        let stdout = child.stdout.take().expect("stdout was piped");
+        for line in BufReader::new(stdout).lines() {
+            let line = line?;
+            println!("{}: {}", day, line);
+
+            if let Some((part, answer)) = answer_line(&line) {
+                seen[usize::from(part - 1)] = true;
+                if !report(day, part, answers.check(day.day, part, answer)) {
+                    incorrect += 1;
+                }
+            }
+        }
+
+        for part in 1..=2 {
+            if let (false, Some(expected)) =
+                (seen[usize::from(part - 1)], answers.expected(day.day, part))
+            {
+                println!("{}: part{} missing, expected {}", day, part, expected);
+                incorrect += 1;
+            }
+        }
+
+        let status = child.wait()?;
+        let elapsed = started.elapsed();
+
+        // the solution is free to stop reading early
+        match writer.join().expect("writer thread panicked") {
+            Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e.into()),
+            _ => {}
+        }
+
+        summary.ran += 1;
+        summary.elapsed += elapsed;
+
+        if !status.success() {
+            println!("{}: failed with {} after {:.2?}", day, status, elapsed);
+            summary.failed += 1;
+        } else if incorrect > 0 {
+            println!("{}: {} incorrect after {:.2?}", day, incorrect, elapsed);
+            summary.failed += 1;
+        } else {
+            println!("{}: took {:.2?}", day, elapsed);
+        }
+    }
+
+    if summary.ran > 1 {
+        println!(
+            "ran {} days in {:.2?}, {} failed",
+            summary.ran, summary.elapsed, summary.failed
+        );
+    }
+
+    Ok(summary)
+}
+
+/// Recognizes the lines with answers, returning the part and the answer.
+fn answer_line(line: &str) -> Option<(u8, &str)> {
+    let rest = line
+        .strip_prefix("part")
+        .or_else(|| line.strip_prefix("stage"))?;
+
+    let part = match rest.as_bytes().first() {
+        Some(b'1') => 1,
+        Some(b'2') => 2,
+        _ => return None,
+    };
+
+    rest[1..]
+        .strip_prefix(':')
+        .map(|answer| (part, answer.trim()))
+}
+
+/// Prints out any problems with the answer, returning true if the answer was not incorrect.
+fn report(day: &Day, part: u8, check: Check<'_>) -> bool {
+    match check {
+        Check::Correct | Check::Unknown => return true,
+        Check::Incorrect {
+            expected: Some(expected),
+            known: Some(verdict),
+        } => println!(
+            "{}: part{} expected {}, the answer is known to be {}",
+            day, part, expected, verdict
+        ),
+        Check::Incorrect {
+            expected: Some(expected),
+            known: None,
+        } => println!("{}: part{} expected {}", day, part, expected),
+        Check::Incorrect {
+            expected: None,
+            known: Some(verdict),
+        } => println!("{}: part{} is known to be {}", day, part, verdict),
+        Check::Incorrect {
+            expected: None,
+            known: None,
+        } => unreachable!("incorrect answer without anything known about it"),
+    }
+    false
+}
+
+/// Builds all of the selected binaries with a single cargo invocation.
+fn build<'a, I>(root: &Path, days: I) -> Result<(), Box<dyn std::error::Error>>
+where
+    I: IntoIterator<Item = &'a Day>,
+{
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+
+    let mut cmd = Command::new(cargo);
+    cmd.current_dir(root)
+        .args(["build", "--release", "--quiet"]);
+    for day in days {
+        cmd.arg("--bin").arg(day.bin());
+    }
+
+    let status = cmd.status()?;
+    if !status.success() {
+        return Err(format!("cargo build failed with {}", status).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+fn args(s: &str) -> Vec<String> {
+    s.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn selections() {
+    assert_eq!(Selection::from_args(args("")), Ok(Selection::All));
+    assert_eq!(
+        Selection::from_args(args("2019")),
+        Ok(Selection::Year(2019))
+    );
+    assert_eq!(
+        Selection::from_args(args("2019 17")),
+        Ok(Selection::Day(2019, 17))
+    );
+    assert_eq!(
+        Selection::from_args(args("2019 07")),
+        Ok(Selection::Day(2019, 7))
+    );
+
+    assert_eq!(
+        Selection::from_args(args("19x")),
+        Err(UsageError::InvalidYear(String::from("19x")))
+    );
+    assert_eq!(
+        Selection::from_args(args("2019 26")),
+        Err(UsageError::InvalidDay(String::from("26")))
+    );
+    assert_eq!(
+        Selection::from_args(args("2019 1 2")),
+        Err(UsageError::TooManyArguments)
+    );
+}
+
+#[test]
+fn options() {
+    assert_eq!(
+        Options::from_args(args("--set example 2019 17")),
+        Ok(Options {
+            selection: Selection::Day(2019, 17),
+            set: String::from("example"),
+            inputs: None,
+        })
+    );
+    assert_eq!(
+        Options::from_args(args("2020 --inputs /tmp/inputs")),
+        Ok(Options {
+            selection: Selection::Year(2020),
+            set: String::from(DEFAULT_SET),
+            inputs: Some(PathBuf::from("/tmp/inputs")),
+        })
+    );
+    assert_eq!(
+        Options::from_args(args("2020 --set")),
+        Err(UsageError::MissingValue("--set"))
+    );
+    assert_eq!(
+        Options::from_args(args("--verbose")),
+        Err(UsageError::UnknownFlag(String::from("--verbose")))
+    );
+}
+
+#[test]
+fn answer_lines() {
+    assert_eq!(answer_line("part1: 406"), Some((1, "406")));
+    assert_eq!(answer_line("stage2: LEPCPLGZ "), Some((2, "LEPCPLGZ")));
+    assert_eq!(answer_line("part3: 1"), None);
+    assert_eq!(answer_line("queries: (605, 9716)"), None);
+    assert_eq!(answer_line("part1 406"), None);
+}
+
+#[test]
+fn discovers_both_layouts() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let days = discover(&root).unwrap();
+
+    let day17 = days
+        .iter()
+        .filter(|d| d.day == 17)
+        .map(|d| (d.year, d.layout))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        day17,
+        vec![(2019, Layout::CratePerDay), (2020, Layout::SingleCrate)]
+    );
+
+    let bins = days
+        .iter()
+        .filter(|d| Selection::Year(2018).matches(d))
+        .map(Day::bin)
+        .collect::<Vec<_>>();
+
+    assert_eq!(bins.first().map(String::as_str), Some("aoc2018-day01"));
+
+    let store = InputStore::new(root.join("inputs"));
+    let day17 = days.iter().find(|d| d.year == 2019 && d.day == 17).unwrap();
+    assert_eq!(
+        day17.answers(&store, DEFAULT_SET),
+        root.join("inputs").join("2019").join("answers")
+    );
+    assert_eq!(
+        day17.answers(&store, "example"),
+        root.join("inputs").join("2019").join("answers.example")
+    );
+}